@@ -0,0 +1,402 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, DeriveInput, Error, LitInt, LitStr};
+
+#[derive(Default)]
+struct TaskAttributes {
+    directory: Option<LitStr>,
+    name: Option<LitStr>,
+    year: Option<LitInt>,
+    day: Option<LitInt>,
+    phases: Option<LitInt>,
+    input: Option<LitStr>,
+    solution: Option<LitStr>,
+}
+
+fn parse_attributes(input: &DeriveInput) -> syn::Result<TaskAttributes> {
+    let mut attributes = TaskAttributes::default();
+    for attribute in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("aoc"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("dir") {
+                attributes.directory = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("name") {
+                attributes.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("year") {
+                attributes.year = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("day") {
+                let day: LitInt = meta.value()?.parse()?;
+                if !(1..=25).contains(&day.base10_parse::<u8>()?) {
+                    return Err(Error::new(day.span(), "the day must be between 1 and 25"));
+                }
+                attributes.day = Some(day);
+            } else if meta.path.is_ident("phases") {
+                attributes.phases = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("input") {
+                attributes.input = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("solution") {
+                attributes.solution = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error(
+                    "expected one of `dir`, `name`, `year`, `day`, `phases`, `input` or `solution`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attributes)
+}
+
+// Implements `AocTask` from the `#[aoc(...)]` attributes. The solution is forwarded to the
+// inherent `solve_input` method, or the one named by `solution`, which takes the input as
+// lines, a `str` or bytes depending on `input`
+#[proc_macro_derive(AocTask, attributes(aoc))]
+pub fn derive_aoc_task(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attributes = parse_attributes(input)?;
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let directory = attributes.directory.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "missing the task directory, add `#[aoc(dir = \"...\")]`",
+        )
+    })?;
+    let name = attributes.name.map(|name| {
+        quote! {
+            fn name(&self) -> ::std::string::String {
+                ::std::string::String::from(#name)
+            }
+        }
+    });
+    let phases = attributes.phases.map(|phases| {
+        quote! {
+            fn phases(&self) -> usize {
+                #phases
+            }
+        }
+    });
+
+    let method = match &attributes.solution {
+        Some(solution) => solution.parse()?,
+        None => format_ident!("solve_input"),
+    };
+    // `solution` is required, tasks solving the whole input join the lines back together
+    let joined_lines = quote!(&input.collect::<::std::vec::Vec<_>>().join("\n"));
+    let (solution, input_type, forward) =
+        match attributes.input.as_ref().map(LitStr::value).as_deref() {
+            None | Some("lines") => (
+                format_ident!("solution"),
+                quote!(::aoc_framework::AocStringIter),
+                None,
+            ),
+            Some("str") => (
+                format_ident!("solution_raw"),
+                quote!(&str),
+                Some(quote!(::aoc_framework::AocTask::solution_raw(
+                    self,
+                    #joined_lines,
+                    context
+                ))),
+            ),
+            Some("bytes") => (
+                format_ident!("solution_bytes"),
+                quote!(&[u8]),
+                Some(quote!(::aoc_framework::AocTask::solution_bytes(
+                    self,
+                    (#joined_lines).as_bytes(),
+                    context
+                ))),
+            ),
+            Some(_) => {
+                return Err(Error::new(
+                    attributes.input.as_ref().unwrap().span(),
+                    "expected `lines`, `str` or `bytes`",
+                ))
+            }
+        };
+
+    let year = attributes.year.as_ref().map(|year| {
+        quote! {
+            fn year(&self) -> ::std::option::Option<u16> {
+                ::std::option::Option::Some(#year)
+            }
+        }
+    });
+    let day = attributes.day.as_ref().map(|day| {
+        quote! {
+            fn day(&self) -> ::std::option::Option<u8> {
+                ::std::option::Option::Some(#day)
+            }
+        }
+    });
+    let year_const = attributes
+        .year
+        .map(|year| quote!(pub const YEAR: u16 = #year;));
+    let day_const = attributes.day.map(|day| quote!(pub const DAY: u8 = #day;));
+    let metadata = (year_const.is_some() || day_const.is_some()).then(|| {
+        quote! {
+            impl #impl_generics #ident #type_generics #where_clause {
+                #year_const
+                #day_const
+            }
+        }
+    });
+
+    let forward = forward.map(|forward| {
+        quote! {
+            fn solution(
+                &self,
+                input: ::aoc_framework::AocStringIter,
+                context: &::aoc_framework::AocContext,
+            ) -> ::std::result::Result<
+                ::aoc_framework::AocSolution,
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+            > {
+                #forward
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::aoc_framework::AocTask for #ident #type_generics #where_clause {
+            fn directory(&self) -> ::std::path::PathBuf {
+                ::std::path::PathBuf::from(#directory)
+            }
+
+            #name
+            #year
+            #day
+            #phases
+            #forward
+
+            fn #solution(
+                &self,
+                input: #input_type,
+                context: &::aoc_framework::AocContext,
+            ) -> ::std::result::Result<
+                ::aoc_framework::AocSolution,
+                ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+            > {
+                Self::#method(self, input, context)
+            }
+        }
+
+        #metadata
+    })
+}
+
+#[derive(Default)]
+struct InputAttributes {
+    format: Option<LitStr>,
+    split: Option<LitStr>,
+}
+
+fn parse_input_attributes(attrs: &[syn::Attribute]) -> syn::Result<InputAttributes> {
+    let mut attributes = InputAttributes::default();
+    for attribute in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("aoc_input"))
+    {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                attributes.format = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("split") {
+                attributes.split = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `format` or `split`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attributes)
+}
+
+// Splits a format like `Game {id}: {cubes}` into its literals and the fields between them,
+// `{{` and `}}` stand for literal braces
+fn parse_format(format: &LitStr) -> syn::Result<(Vec<String>, Vec<String>)> {
+    let error = |message: &str| Error::new(format.span(), message);
+    let mut literals = vec![String::new()];
+    let mut fields = vec![];
+    let mut chars = format
+        .value()
+        .chars()
+        .collect::<Vec<_>>()
+        .into_iter()
+        .peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literals.last_mut().unwrap().push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literals.last_mut().unwrap().push('}');
+            }
+            '{' => {
+                let field = chars.by_ref().take_while(|&c| c != '}').collect::<String>();
+                if field.is_empty() {
+                    return Err(error("fields of the format must be named, e.g. `{id}`"));
+                }
+                if !fields.is_empty() && literals.last().unwrap().is_empty() {
+                    return Err(error("fields of the format must be separated by some text"));
+                }
+                fields.push(field);
+                literals.push(String::new());
+            }
+            '}' => {
+                return Err(error(
+                    "unmatched `}` in the format, use `}}` for a literal one",
+                ))
+            }
+            c => literals.last_mut().unwrap().push(c),
+        }
+    }
+    Ok((literals, fields))
+}
+
+fn snake_case(ident: &syn::Ident) -> String {
+    let mut name = String::new();
+    for (index, c) in ident.to_string().chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+fn expand_struct(
+    ident: &syn::Ident,
+    attributes: &InputAttributes,
+    fields: &syn::FieldsNamed,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let format = attributes.format.as_ref().ok_or_else(|| {
+        Error::new(
+            ident.span(),
+            "missing the input format, add `#[aoc_input(format = \"...\")]`",
+        )
+    })?;
+    let (literals, format_fields) = parse_format(format)?;
+    for format_field in &format_fields {
+        if !fields.named.iter().any(|field| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == format_field)
+        }) {
+            return Err(Error::new(
+                format.span(),
+                format!("the format refers to the unknown field `{format_field}`"),
+            ));
+        }
+    }
+
+    let mut initializers = vec![];
+    for field in &fields.named {
+        let field_ident = field.ident.as_ref().unwrap();
+        let name = field_ident.to_string();
+        let index = format_fields
+            .iter()
+            .position(|format_field| *format_field == name)
+            .ok_or_else(|| {
+                Error::new(
+                    field_ident.span(),
+                    format!("the field `{name}` is missing from the format"),
+                )
+            })?;
+        let parse = match parse_input_attributes(&field.attrs)?.split {
+            Some(separator) => quote! {
+                ::aoc_framework::parse::parse_list(fields[#index], #separator, #name)?
+            },
+            None => quote!(::aoc_framework::parse::parse_field(fields[#index], #name)?),
+        };
+        initializers.push(quote!(#field_ident: #parse));
+    }
+
+    Ok(quote! {
+        let fields = ::aoc_framework::parse::split_format(text.trim(), &[#(#literals),*])?;
+        ::std::result::Result::Ok(Self { #(#initializers),* })
+    })
+}
+
+fn expand_enum(data: &syn::DataEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let mut names = vec![];
+    let mut variants = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(Error::new(
+                variant.ident.span(),
+                "only enums with unit variants can be derived",
+            ));
+        }
+        let name = match parse_input_attributes(&variant.attrs)?.format {
+            Some(format) => format.value(),
+            None => snake_case(&variant.ident),
+        };
+        names.push(format!("`{name}`"));
+        let variant = &variant.ident;
+        variants.push(quote!(#name => ::std::result::Result::Ok(Self::#variant)));
+    }
+    let expected = format!("Expected one of {}", names.join(", "));
+
+    Ok(quote! {
+        match text.trim() {
+            #(#variants,)*
+            _ => ::std::result::Result::Err(::aoc_framework::parse::FormatError {
+                message: ::std::string::String::from(#expected),
+            }),
+        }
+    })
+}
+
+// Implements `FromStr` for structs with named fields from their `#[aoc_input(format = "...")]`
+// and for enums with unit variants, which match their snake case names by default
+#[proc_macro_derive(AocInput, attributes(aoc_input))]
+pub fn derive_aoc_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_input(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_input(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attributes = parse_input_attributes(&input.attrs)?;
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let body = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => expand_struct(ident, &attributes, fields)?,
+        syn::Data::Enum(data) => expand_enum(data)?,
+        _ => {
+            return Err(Error::new(
+                ident.span(),
+                "only structs with named fields and enums can be derived",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #ident #type_generics #where_clause {
+            type Err = ::aoc_framework::parse::FormatError;
+
+            fn from_str(text: &str) -> ::std::result::Result<Self, Self::Err> {
+                #body
+            }
+        }
+    })
+}
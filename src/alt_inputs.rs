@@ -0,0 +1,238 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    answers,
+    context::AocContext,
+    error::AocError,
+    filter::Filter,
+    runner::{filter_tasks, task_phases},
+    source::FileSource,
+    style::Stylize,
+    AocTask, SharedAocTask, CHECKMARK, CROSS, DOT,
+};
+
+// Inputs collected from other people, next to the input of the task
+pub const ALT_INPUTS_DIRECTORY: &str = "alt_inputs";
+// The known answers of an input, one line per phase, are kept in a file with this suffix
+const ANSWERS_SUFFIX: &str = "_out";
+
+// The alternative inputs of the task, sorted by name, without the files of their answers
+pub fn alt_input_paths(task: &dyn AocTask) -> Result<Vec<PathBuf>, AocError> {
+    let directory = task.data_directory().join(ALT_INPUTS_DIRECTORY);
+    if !directory.is_dir() {
+        return Ok(vec![]);
+    }
+    let read_error = |err| AocError::IOReadError {
+        path: directory.to_string_lossy().to_string(),
+        source: err,
+    };
+    let mut paths = fs::read_dir(&directory)
+        .map_err(read_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(read_error)?;
+    paths.retain(|path| path.is_file() && !path.to_string_lossy().ends_with(ANSWERS_SUFFIX));
+    paths.sort();
+    Ok(paths)
+}
+
+fn known_answer(input_path: &Path, phase: usize) -> Option<String> {
+    let mut answers_path = input_path.as_os_str().to_owned();
+    answers_path.push(ANSWERS_SUFFIX);
+    let answers = fs::read_to_string(answers_path).ok()?;
+    answers
+        .lines()
+        .nth(phase - 1)
+        .map(str::trim)
+        .filter(|answer| !answer.is_empty())
+        .map(str::to_owned)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AltInputResult {
+    pub input_path: PathBuf,
+    pub phase: usize,
+    // The error of the solution when it failed
+    pub answer: Result<String, String>,
+    pub known_answer: Option<String>,
+    // Compared by the comparator of the phase, answers are only wrong when they are known
+    pub matches_known_answer: bool,
+}
+
+impl AltInputResult {
+    pub fn passed(&self) -> bool {
+        self.answer.is_ok() && self.matches_known_answer
+    }
+}
+
+pub fn solve_alt_inputs(
+    task: &SharedAocTask,
+    phases: &[usize],
+) -> Result<Vec<AltInputResult>, AocError> {
+    let mut results = vec![];
+    for input_path in alt_input_paths(task.as_ref())? {
+        for &phase in phases {
+            let context = AocContext::new(phase)
+                .with_input_source(Some(Arc::new(FileSource::new(&input_path))));
+            let output = task.solve_with_context(context);
+            let known_answer = known_answer(&input_path, phase);
+            let matches_known_answer = match (&output, &known_answer) {
+                (Ok(output), Some(known)) => {
+                    task.comparator(phase).matches(output, &vec![known.clone()])
+                }
+                _ => true,
+            };
+            results.push(AltInputResult {
+                phase,
+                answer: output
+                    .map(|output| answers::answer_text(&output))
+                    .map_err(|err| err.to_string()),
+                known_answer,
+                matches_known_answer,
+                input_path: input_path.clone(),
+            });
+        }
+    }
+    Ok(results)
+}
+
+fn print_result(result: &AltInputResult) {
+    let name = result
+        .input_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let symbol = if result.passed() {
+        CHECKMARK.success()
+    } else {
+        CROSS.failure()
+    };
+    let details = match (&result.answer, &result.known_answer) {
+        (Ok(answer), Some(known)) if !result.matches_known_answer => {
+            format!(
+                "{} (expected {})",
+                answer.clone().failure(),
+                known.clone().bold()
+            )
+        }
+        (Ok(answer), _) => answer.clone().highlight().to_string(),
+        (Err(err), _) => err.clone().failure().to_string(),
+    };
+    println!(
+        "  {symbol} {:<16} phase {}: {details}",
+        name,
+        result.phase.to_string().highlight()
+    );
+}
+
+// Returns whether every alternative input was solved and matched its known answers
+pub fn run_alt_inputs(tasks: &[SharedAocTask], filter: &Filter) -> Result<bool, AocError> {
+    let mut all_passed = true;
+    let mut any_inputs = false;
+    for task in filter_tasks(tasks, filter) {
+        let results = solve_alt_inputs(task, &task_phases(task, filter))?;
+        if results.is_empty() {
+            continue;
+        }
+        any_inputs = true;
+        println!("{} {}:", DOT.info(), task.name().bold());
+        for result in &results {
+            print_result(result);
+        }
+        all_passed &= results.iter().all(AltInputResult::passed);
+    }
+    if !any_inputs {
+        println!(
+            "{} No alternative inputs found, add them to the {} directories of the tasks",
+            CROSS.failure(),
+            ALT_INPUTS_DIRECTORY.bold()
+        );
+    }
+    Ok(all_passed && any_inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+    use crate::{compare::Comparator, testing::TempDir, AocSolution, AocStringIter};
+
+    struct LineCountTask {
+        directory: PathBuf,
+        comparator: Comparator,
+    }
+
+    impl AocTask for LineCountTask {
+        fn directory(&self) -> PathBuf {
+            self.directory.clone()
+        }
+
+        fn comparator(&self, _phase: usize) -> Comparator {
+            self.comparator
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(vec![(input.count() * context.phase()).to_string()])
+        }
+    }
+
+    #[test]
+    fn alt_inputs_are_solved_and_checked() {
+        let directory = TempDir::new("alt_inputs");
+        let alt_inputs = Path::new(ALT_INPUTS_DIRECTORY);
+        directory.write(alt_inputs.join("alice"), "a\nb\n");
+        directory.write(alt_inputs.join("alice_out"), "2\n5\n");
+        directory.write(alt_inputs.join("bob"), "a\n");
+        let task: SharedAocTask = Arc::new(LineCountTask {
+            directory: directory.path().to_owned(),
+            comparator: Comparator::default(),
+        });
+
+        let results = solve_alt_inputs(&task, &[1, 2]).unwrap();
+        let summary = results
+            .iter()
+            .map(|result| {
+                (
+                    result.input_path.file_name().unwrap().to_str().unwrap(),
+                    result.phase,
+                    result.answer.clone().unwrap(),
+                    result.passed(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                ("alice", 1, "2".to_owned(), true),
+                ("alice", 2, "4".to_owned(), false),
+                ("bob", 1, "1".to_owned(), true),
+                ("bob", 2, "2".to_owned(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn known_answers_are_compared_like_the_outputs() {
+        let directory = TempDir::new("alt_inputs_comparator");
+        let alt_inputs = Path::new(ALT_INPUTS_DIRECTORY);
+        directory.write(alt_inputs.join("alice"), "a\nb\n");
+        directory.write(alt_inputs.join("alice_out"), "2.4\n");
+        let task: SharedAocTask = Arc::new(LineCountTask {
+            directory: directory.path().to_owned(),
+            comparator: Comparator::Numeric { tolerance: 0.5 },
+        });
+
+        let results = solve_alt_inputs(&task, &[1]).unwrap();
+        assert_eq!(results[0].answer, Ok("2".to_owned()));
+        assert!(results[0].passed());
+    }
+}
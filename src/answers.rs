@@ -0,0 +1,174 @@
+use std::fmt::{self, Display};
+
+use crate::{error::AocError, ocr, prompt, state, AocSolution, AocTask};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        }
+    }
+
+    pub(crate) fn parse(verdict: &str) -> Option<Self> {
+        match verdict {
+            "correct" => Some(Verdict::Correct),
+            "wrong" => Some(Verdict::Wrong),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str().replace('_', " "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedAnswer {
+    pub phase: usize,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+pub struct AnswerLog<'t> {
+    task: &'t dyn AocTask,
+    answers: Vec<RecordedAnswer>,
+}
+
+// Answers drawn as letters are submitted as the letters they read as
+pub fn answer_text(solution: &AocSolution) -> String {
+    ocr::recognize(solution).unwrap_or_else(|| solution.join("\n").trim().to_owned())
+}
+
+// The `.answers` files of older versions have an answer per line, with the line breaks escaped
+pub(crate) fn parse_line(line: &str) -> Option<RecordedAnswer> {
+    let mut fields = line.splitn(3, '\t');
+    Some(RecordedAnswer {
+        phase: fields.next()?.parse().ok()?,
+        verdict: Verdict::parse(fields.next()?)?,
+        answer: fields.next()?.replace("\\n", "\n"),
+    })
+}
+
+impl<'t> AnswerLog<'t> {
+    pub fn load(task: &'t dyn AocTask) -> Result<Self, AocError> {
+        let answers = state::with_task_state(task, |state| state.answers.clone())?;
+        Ok(Self { task, answers })
+    }
+
+    pub fn answers(&self) -> &[RecordedAnswer] {
+        &self.answers
+    }
+
+    pub fn record(
+        &mut self,
+        phase: usize,
+        solution: &AocSolution,
+        verdict: Verdict,
+    ) -> Result<(), AocError> {
+        let recorded = RecordedAnswer {
+            phase,
+            verdict,
+            answer: answer_text(solution),
+        };
+        state::with_task_state(self.task, |state| state.answers.push(recorded.clone()))?;
+        self.answers.push(recorded);
+        Ok(())
+    }
+
+    // The latest answer of the phase that was confirmed to be correct
+    pub fn accepted(&self, phase: usize) -> Option<&str> {
+        self.answers
+            .iter()
+            .rev()
+            .find(|recorded| recorded.phase == phase && recorded.verdict == Verdict::Correct)
+            .map(|recorded| recorded.answer.as_str())
+    }
+
+    // Explains why the solution cannot be the answer of the phase based on the earlier verdicts
+    pub fn rejection(&self, phase: usize, solution: &AocSolution) -> Option<String> {
+        let answer = answer_text(solution);
+        let number = answer.parse::<i128>().ok();
+        self.answers
+            .iter()
+            .filter(|recorded| recorded.phase == phase)
+            .find_map(|recorded| {
+                let bound = recorded.answer.parse::<i128>().ok();
+                match (recorded.verdict, number, bound) {
+                    (Verdict::Correct, _, _) => None,
+                    _ if recorded.answer == answer => {
+                        Some(format!("{answer} was already {}", recorded.verdict))
+                    }
+                    (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => Some(
+                        format!("{answer} is too high, {bound} was already too high"),
+                    ),
+                    (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => {
+                        Some(format!("{answer} is too low, {bound} was already too low"))
+                    }
+                    _ => None,
+                }
+            })
+    }
+}
+
+pub(crate) fn ask_verdict(phase: usize) -> Result<Option<Verdict>, AocError> {
+    let verdicts = [
+        (Some(Verdict::Wrong), "It was wrong"),
+        (Some(Verdict::TooHigh), "It was too high"),
+        (Some(Verdict::TooLow), "It was too low"),
+        (None, "It was not submitted"),
+    ];
+    let selected = prompt::select(
+        format!("What happened to the answer of phase {phase}?"),
+        &verdicts.map(|(_, label)| label),
+    )?;
+    Ok(selected.and_then(|selected| verdicts[selected].0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn known_wrong_answers_are_rejected() {
+        let answer = |text: &str| vec![text.to_owned()];
+        let answers = [
+            "1\ttoo_high\t100",
+            "1\ttoo_low\t10",
+            "1\twrong\tabc",
+            "2\tcorrect\t50\\n60",
+        ]
+        .iter()
+        .filter_map(|line| parse_line(line))
+        .collect();
+        let task = MockTask::new(|_, _| Ok(vec![]));
+        let log = AnswerLog {
+            task: &task,
+            answers,
+        };
+
+        assert_eq!(log.answers().len(), 4);
+        assert!(log.rejection(1, &answer("120")).is_some());
+        assert!(log.rejection(1, &answer("5")).is_some());
+        assert!(log.rejection(1, &answer("abc")).is_some());
+        assert_eq!(log.rejection(1, &answer("50")), None);
+        assert_eq!(log.rejection(2, &answer("50")), None);
+        assert_eq!(log.accepted(1), None);
+        assert_eq!(log.accepted(2), Some("50\n60"));
+    }
+}
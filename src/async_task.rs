@@ -0,0 +1,244 @@
+use std::{
+    borrow::Cow,
+    error::Error,
+    path::PathBuf,
+    sync::{Arc, OnceLock},
+};
+
+use itertools::Itertools;
+use tokio::runtime::{Builder, Handle, Runtime};
+
+use crate::{
+    compare::Comparator, context::AocContext, error::AocError, example::Example, input,
+    report::RunReport, reporter::Reporter, run_tasks, source::InputSource, AocSolution,
+    AocStringIter, AocTask, RunOptions, SharedAocTask,
+};
+
+// A task whose solution is async, e.g. to fetch data or to use async channels and timers. Wrap
+// it in an `AsyncTask` to run it like any other task.
+pub trait AsyncAocTask: Send + Sync {
+    fn directory(&self) -> PathBuf;
+
+    fn year(&self) -> Option<u16> {
+        input::puzzle_date(&self.directory()).map(|(year, _)| year)
+    }
+
+    fn day(&self) -> Option<u8> {
+        input::puzzle_day(&self.directory())
+    }
+
+    fn data_directory(&self) -> PathBuf {
+        input::dated_data_directory(&self.directory(), self.year().zip(self.day()))
+    }
+
+    fn phases(&self) -> usize {
+        2
+    }
+
+    fn comparator(&self, _phase: usize) -> Comparator {
+        Comparator::default()
+    }
+
+    fn inline_examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    fn variants(&self, _phase: usize) -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn generate_input(&self, _scale: usize) -> String {
+        String::new()
+    }
+
+    fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        None
+    }
+
+    fn preprocess<'i>(&self, raw: Cow<'i, str>) -> Cow<'i, str> {
+        raw
+    }
+
+    #[allow(async_fn_in_trait)]
+    async fn solution(
+        &self,
+        input: &str,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>>;
+}
+
+pub struct AsyncTask<T: AsyncAocTask> {
+    task: T,
+    runtime: OnceLock<Runtime>,
+}
+
+impl<T: AsyncAocTask> AsyncTask<T> {
+    pub fn new(task: T) -> Self {
+        Self {
+            task,
+            runtime: OnceLock::new(),
+        }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.task
+    }
+
+    // Solutions run on the runtime of `run_tasks_async` when there is one, otherwise on a
+    // runtime of the task
+    fn block_on<F: std::future::Future>(&self, future: F) -> Result<F::Output, AocError> {
+        if let Ok(handle) = Handle::try_current() {
+            return Ok(handle.block_on(future));
+        }
+        let runtime = match self.runtime.get() {
+            Some(runtime) => runtime,
+            None => {
+                let runtime = Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|err| AocError::AsyncRuntimeError { source: err })?;
+                self.runtime.get_or_init(|| runtime)
+            }
+        };
+        Ok(runtime.block_on(future))
+    }
+}
+
+impl<T: AsyncAocTask> From<T> for AsyncTask<T> {
+    fn from(task: T) -> Self {
+        Self::new(task)
+    }
+}
+
+impl<T: AsyncAocTask> AocTask for AsyncTask<T> {
+    fn directory(&self) -> PathBuf {
+        self.task.directory()
+    }
+
+    fn year(&self) -> Option<u16> {
+        self.task.year()
+    }
+
+    fn day(&self) -> Option<u8> {
+        self.task.day()
+    }
+
+    fn data_directory(&self) -> PathBuf {
+        self.task.data_directory()
+    }
+
+    fn phases(&self) -> usize {
+        self.task.phases()
+    }
+
+    fn comparator(&self, phase: usize) -> Comparator {
+        self.task.comparator(phase)
+    }
+
+    fn inline_examples(&self) -> Vec<Example> {
+        self.task.inline_examples()
+    }
+
+    fn variants(&self, phase: usize) -> Vec<&'static str> {
+        self.task.variants(phase)
+    }
+
+    fn generate_input(&self, scale: usize) -> String {
+        self.task.generate_input(scale)
+    }
+
+    fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        self.task.input_source()
+    }
+
+    fn preprocess<'i>(&self, raw: Cow<'i, str>) -> Cow<'i, str> {
+        self.task.preprocess(raw)
+    }
+
+    fn solution(
+        &self,
+        mut input: AocStringIter,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        self.solution_raw(&input.join("\n"), context)
+    }
+
+    fn solution_raw(
+        &self,
+        input: &str,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        self.block_on(self.task.solution(input, context))?
+    }
+}
+
+// Runs the tasks on a blocking thread of the current tokio runtime, which the async tasks then
+// solve their phases on. Sync tasks run unchanged.
+pub async fn run_tasks_async(
+    tasks: Vec<SharedAocTask>,
+    options: RunOptions,
+    mut reporter: impl Reporter + Send + 'static,
+) -> Result<RunReport, AocError> {
+    let run = tokio::task::spawn_blocking(move || run_tasks(&tasks, &options, &mut reporter));
+    match run.await {
+        Ok(result) => result,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::ChannelReporter, source::FileSource};
+
+    struct AsyncSumTask;
+
+    impl AsyncAocTask for AsyncSumTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        async fn solution(
+            &self,
+            input: &str,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            tokio::task::yield_now().await;
+            Ok(input
+                .lines()
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|num| num.parse::<i32>().unwrap_or(0))
+                        .sum::<i32>()
+                        .to_string()
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn async_tasks_run_with_and_without_a_runtime() {
+        let task = AsyncTask::new(AsyncSumTask);
+        let expected = task.solve(2).unwrap();
+
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(AsyncTask::new(AsyncSumTask))];
+        // Passing the input in keeps the race timer of the task from starting
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            fail_fast: false,
+            input_source: Some(Arc::new(FileSource::new(task.input_path()))),
+            ..RunOptions::default()
+        };
+        let (sender, _receiver) = std::sync::mpsc::channel();
+        let runtime = Builder::new_current_thread().build().unwrap();
+        let report = runtime
+            .block_on(run_tasks_async(
+                tasks,
+                options,
+                ChannelReporter::new(sender),
+            ))
+            .unwrap();
+        assert_eq!(report.tasks[0].phases[1].output, Some(expected));
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    bench::{BenchResult, BenchStats},
+    error::AocError,
+    style::Stylize,
+    CHECKMARK, CROSS, DOT,
+};
+
+pub const BASELINES_ENV_VAR: &str = "AOC_BASELINES_FILE";
+const DEFAULT_BASELINES_FILE: &str = ".aoc-baselines";
+pub const DEFAULT_BASELINE: &str = "base";
+
+// Named baseline, task directory and phase
+type BaselineKey = (String, String, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Baseline {
+    pub mean: Duration,
+    pub median: Duration,
+}
+
+impl From<&BenchStats> for Baseline {
+    fn from(stats: &BenchStats) -> Self {
+        Self {
+            mean: stats.mean,
+            median: stats.median,
+        }
+    }
+}
+
+impl Baseline {
+    // Relative change of the median solve time in percent, positive when the run got slower
+    pub fn change(&self, stats: &BenchStats) -> f64 {
+        let baseline = self.median.as_secs_f64().max(1e-9);
+        (stats.median.as_secs_f64() - baseline) / baseline * 100.0
+    }
+}
+
+// All baselines are kept in a single file, with one record per line
+#[derive(Debug, Clone, Default)]
+pub struct BaselineStore {
+    path: PathBuf,
+    baselines: BTreeMap<BaselineKey, Baseline>,
+}
+
+// The baselines are kept in the project directory unless the environment variable moves them
+pub fn baselines_path() -> PathBuf {
+    env::var_os(BASELINES_ENV_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINES_FILE))
+}
+
+fn result_key(name: &str, result: &BenchResult) -> BaselineKey {
+    (
+        name.to_owned(),
+        result.directory.to_string_lossy().to_string(),
+        result.phase,
+    )
+}
+
+fn parse_line(line: &str) -> Option<(BaselineKey, Baseline)> {
+    let mut fields = line.split('\t');
+    let (name, task, phase) = (fields.next()?, fields.next()?, fields.next()?);
+    let mean = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let median = Duration::from_nanos(fields.next()?.parse().ok()?);
+    Some((
+        (name.to_owned(), task.to_owned(), phase.parse().ok()?),
+        Baseline { mean, median },
+    ))
+}
+
+impl BaselineStore {
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let mut baselines = BTreeMap::new();
+        if path.is_file() {
+            let content = fs::read_to_string(path).map_err(|err| AocError::IOReadError {
+                path: path.to_string_lossy().to_string(),
+                source: err,
+            })?;
+            baselines.extend(content.lines().filter_map(parse_line));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            baselines,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), AocError> {
+        let mut content = String::new();
+        for ((name, task, phase), baseline) in &self.baselines {
+            let _ = writeln!(
+                content,
+                "{name}\t{task}\t{phase}\t{}\t{}",
+                baseline.mean.as_nanos(),
+                baseline.median.as_nanos()
+            );
+        }
+
+        let write_error = |err| AocError::IOWriteError {
+            path: self.path.to_string_lossy().to_string(),
+            source: err,
+        };
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        fs::write(&self.path, content).map_err(write_error)
+    }
+
+    pub fn get(&self, name: &str, result: &BenchResult) -> Option<&Baseline> {
+        self.baselines.get(&result_key(name, result))
+    }
+
+    pub fn insert(&mut self, name: &str, result: &BenchResult) {
+        self.baselines
+            .insert(result_key(name, result), Baseline::from(&result.solve));
+    }
+
+    // The baseline name, task directory, phase and baseline of every record
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, usize, &Baseline)> {
+        self.baselines
+            .iter()
+            .map(|((name, task, phase), baseline)| (name.as_str(), task.as_str(), *phase, baseline))
+    }
+}
+
+pub fn save_baseline(results: &[BenchResult], name: &str) -> Result<(), AocError> {
+    let mut store = BaselineStore::load(&baselines_path())?;
+    for result in results {
+        store.insert(name, result);
+    }
+    store.save()?;
+    println!(
+        "{} Saved {} results as the baseline {}",
+        DOT.info(),
+        results.len(),
+        name.bold()
+    );
+    Ok(())
+}
+
+// Returns whether none of the results regressed by more than the threshold, results without a
+// stored baseline are reported and skipped
+pub fn compare_baseline(
+    results: &[BenchResult],
+    name: &str,
+    threshold: f64,
+) -> Result<bool, AocError> {
+    let store = BaselineStore::load(&baselines_path())?;
+    println!("{} Compared to the baseline {}:", DOT.info(), name.bold());
+    let mut regressed = false;
+    for result in results {
+        let label = format!("{} phase {}", result.task_name, result.phase);
+        let Some(baseline) = store.get(name, result) else {
+            println!("  {} {label}: {}", DOT.muted(), "no baseline".muted());
+            continue;
+        };
+        let change = baseline.change(&result.solve);
+        let change_text = format!("{change:+.1}%");
+        if change > threshold {
+            regressed = true;
+            println!(
+                "  {} {label}: {} ({:?} -> {:?})",
+                CROSS.failure(),
+                change_text.failure(),
+                baseline.median,
+                result.solve.median
+            );
+        } else {
+            println!(
+                "  {} {label}: {}",
+                CHECKMARK.success(),
+                if change < -threshold {
+                    change_text.success()
+                } else {
+                    change_text.muted()
+                }
+            );
+        }
+    }
+    Ok(!regressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    fn stats(median: u64) -> BenchStats {
+        BenchStats::from_samples(&[Duration::from_millis(median)])
+    }
+
+    #[test]
+    fn baselines_round_trip() {
+        let directory = TempDir::new("baselines");
+        let path = directory.join("baselines");
+        let result = BenchResult {
+            task_name: "Day 01".to_owned(),
+            directory: PathBuf::from("src/day_01"),
+            phase: 2,
+            input: stats(1),
+            solve: stats(10),
+        };
+
+        let mut store = BaselineStore::load(&path).unwrap();
+        store.insert("before", &result);
+        store.save().unwrap();
+
+        let store = BaselineStore::load(&path).unwrap();
+        let baseline = store.get("before", &result).unwrap();
+        assert_eq!(baseline.median, Duration::from_millis(10));
+        assert!(store.get(DEFAULT_BASELINE, &result).is_none());
+        assert!((baseline.change(&stats(12)) - 20.0).abs() < 1e-6);
+        assert!((baseline.change(&stats(5)) + 50.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    context::AocContext,
+    diff,
+    error::AocError,
+    filter::Filter,
+    runner::{filter_tasks, task_phases},
+    style::Stylize,
+    AocSolution, SharedAocTask, CHECKMARK, CROSS, DOT,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    pub warmup_iterations: u32,
+    pub iterations: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup_iterations: 3,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl BenchStats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self {
+                mean: Duration::ZERO,
+                median: Duration::ZERO,
+                stddev: Duration::ZERO,
+                min: Duration::ZERO,
+                max: Duration::ZERO,
+            };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+
+        let mean_secs = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean_secs).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Self {
+            mean: Duration::from_secs_f64(mean_secs),
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            min: sorted[0],
+            max: sorted[count - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub task_name: String,
+    pub directory: PathBuf,
+    pub phase: usize,
+    pub input: BenchStats,
+    pub solve: BenchStats,
+}
+
+pub(crate) fn measure<F>(options: &BenchOptions, mut run: F) -> Result<BenchStats, AocError>
+where
+    F: FnMut() -> Result<(), AocError>,
+{
+    for _ in 0..options.warmup_iterations {
+        run()?;
+    }
+
+    let mut samples = Vec::with_capacity(options.iterations as usize);
+    for _ in 0..options.iterations.max(1) {
+        let start = Instant::now();
+        run()?;
+        samples.push(start.elapsed());
+    }
+    Ok(BenchStats::from_samples(&samples))
+}
+
+pub fn bench_task(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &BenchOptions,
+) -> Result<BenchResult, AocError> {
+    let input_path = task.input_path();
+    let input = measure(options, || task.get_file_output(&input_path).map(|_| ()))?;
+    let solve = measure(options, || task.solve(phase).map(|_| ()))?;
+
+    Ok(BenchResult {
+        task_name: task.name(),
+        directory: task.directory(),
+        phase,
+        input,
+        solve,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantResult {
+    // `None` is the default implementation, which the other variants are checked against
+    pub variant: Option<&'static str>,
+    pub output: AocSolution,
+    pub agrees: bool,
+    pub solve: BenchStats,
+}
+
+pub fn bench_variants(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &BenchOptions,
+) -> Result<Vec<VariantResult>, AocError> {
+    let comparator = task.comparator(phase);
+    let mut results: Vec<VariantResult> = vec![];
+    for variant in std::iter::once(None).chain(task.variants(phase).into_iter().map(Some)) {
+        let solve = || task.solve_with_context(AocContext::new(phase).with_variant(variant));
+        let output = solve()?;
+        let agrees = results
+            .first()
+            .is_none_or(|default| comparator.matches(&output, &default.output));
+        results.push(VariantResult {
+            variant,
+            output,
+            agrees,
+            solve: measure(options, || solve().map(|_| ()))?,
+        });
+    }
+    Ok(results)
+}
+
+fn print_stats(label: &str, stats: &BenchStats) {
+    println!(
+        "  {:<6} mean {} median {} stddev {} (min {}, max {})",
+        label,
+        format!("{:?}", stats.mean).info(),
+        format!("{:?}", stats.median).info(),
+        format!("{:?}", stats.stddev).info(),
+        format!("{:?}", stats.min).muted(),
+        format!("{:?}", stats.max).muted(),
+    );
+}
+
+pub fn bench_tasks(
+    tasks: &[SharedAocTask],
+    filter: &Filter,
+    options: &BenchOptions,
+) -> Result<Vec<BenchResult>, AocError> {
+    let mut results = vec![];
+    for task in filter_tasks(tasks, filter) {
+        for phase in task_phases(task, filter) {
+            let result = bench_task(task, phase, options)?;
+            println!(
+                "{} {} phase {} ({} iterations):",
+                DOT.info(),
+                result.task_name.clone().bold(),
+                phase.to_string().highlight(),
+                options.iterations.max(1)
+            );
+            print_stats("input", &result.input);
+            print_stats("solve", &result.solve);
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+fn print_variant(result: &VariantResult, default: &VariantResult) {
+    let label = result.variant.unwrap_or("default");
+    let relative = result.solve.mean.as_secs_f64() / default.solve.mean.as_secs_f64().max(1e-9);
+    println!(
+        "  {} {:<10} mean {} median {} ({})",
+        if result.agrees {
+            CHECKMARK.success()
+        } else {
+            CROSS.failure()
+        },
+        label,
+        format!("{:?}", result.solve.mean).info(),
+        format!("{:?}", result.solve.median).info(),
+        format!("{relative:.2}x").highlight(),
+    );
+    if !result.agrees {
+        print!("{}", diff::render(&default.output, &result.output));
+    }
+}
+
+// Runs every variant of the phases that have any, returns whether they all agree with the
+// default implementation
+pub fn compare_variants(
+    tasks: &[SharedAocTask],
+    filter: &Filter,
+    options: &BenchOptions,
+) -> Result<bool, AocError> {
+    let mut all_agree = true;
+    for task in filter_tasks(tasks, filter) {
+        for phase in task_phases(task, filter) {
+            if task.variants(phase).is_empty() {
+                continue;
+            }
+            let results = bench_variants(task, phase, options)?;
+            println!(
+                "{} {} phase {} variants ({} iterations):",
+                DOT.info(),
+                task.name().bold(),
+                phase.to_string().highlight(),
+                options.iterations.max(1)
+            );
+            for result in &results {
+                print_variant(result, &results[0]);
+            }
+            all_agree &= results.iter().all(|result| result.agrees);
+        }
+    }
+    Ok(all_agree)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn variants_are_checked_against_the_default() {
+        let task = MockTask::new(|input, context| match context.variant() {
+            Some("broken") => Ok(vec![]),
+            Some(_) => Ok(vec![input.lines().collect::<Vec<_>>().len().to_string()]),
+            None => Ok(vec![input.lines().count().to_string()]),
+        })
+        .with_input("1 2\n3 4\n5 6")
+        .with_variants(&["lines", "broken"]);
+        let task: SharedAocTask = Arc::new(task);
+        let options = BenchOptions {
+            warmup_iterations: 0,
+            iterations: 1,
+        };
+        let results = bench_variants(&task, 1, &options).unwrap();
+        let agreeing = results
+            .iter()
+            .map(|result| (result.variant, result.agrees))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            agreeing,
+            [(None, true), (Some("lines"), true), (Some("broken"), false)]
+        );
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(4));
+        assert!(stats.mean.abs_diff(Duration::from_micros(2500)) < Duration::from_nanos(10));
+        assert!(stats.stddev.abs_diff(Duration::from_micros(1118)) < Duration::from_micros(1));
+    }
+}
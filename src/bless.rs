@@ -0,0 +1,165 @@
+use std::{collections::HashSet, fs, path::PathBuf};
+
+use crate::{
+    diff,
+    error::AocError,
+    filter::Filter,
+    hashed,
+    prompt::ConfirmPolicy,
+    runner::{filter_tasks, task_phases},
+    style::Stylize,
+    AocSolution, SharedAocTask, CHECKMARK, CROSS, DOT,
+};
+
+// An example whose expected output differs from what the solution currently returns
+#[derive(Debug, Clone)]
+pub struct ExpectedUpdate {
+    pub task_name: String,
+    pub phase: usize,
+    pub output_path: PathBuf,
+    pub expected_output: AocSolution,
+    pub output: AocSolution,
+}
+
+impl ExpectedUpdate {
+    pub fn write(&self) -> Result<(), AocError> {
+        let mut contents = self.output.join("\n");
+        contents.push('\n');
+        fs::write(&self.output_path, contents).map_err(|err| AocError::IOWriteError {
+            path: self.output_path.to_string_lossy().to_string(),
+            source: err,
+        })
+    }
+}
+
+// Examples shared by several phases are only updated with the output of the first one
+pub fn pending_updates(
+    tasks: &[SharedAocTask],
+    filter: &Filter,
+) -> Result<Vec<ExpectedUpdate>, AocError> {
+    let mut updates = vec![];
+    let mut seen = HashSet::new();
+    for task in filter_tasks(tasks, filter) {
+        for phase in task_phases(task, filter) {
+            for io_pair in task.phase_example_paths(phase)? {
+                // Hashed outputs are written with `inputs hash` instead
+                if hashed::is_hashed(&io_pair.1) || !seen.insert(io_pair.1.clone()) {
+                    continue;
+                }
+                let result = task.run_example_test(&io_pair, phase)?;
+                if !result.passed {
+                    updates.push(ExpectedUpdate {
+                        task_name: task.name(),
+                        phase,
+                        output_path: io_pair.1,
+                        expected_output: result.expected_output,
+                        output: result.output,
+                    });
+                }
+            }
+        }
+    }
+    Ok(updates)
+}
+
+// Writes the current outputs into the `_out` files of the failing examples, asking before each
+// one unless they are all accepted. Skipping only lists the changes. Returns the number of
+// updated files.
+pub fn update_expected(
+    tasks: &[SharedAocTask],
+    filter: &Filter,
+    policy: ConfirmPolicy,
+) -> Result<usize, AocError> {
+    let policy = policy.resolve();
+    let updates = pending_updates(tasks, filter)?;
+    let mut updated = 0;
+    for update in &updates {
+        println!(
+            "{} {} phase {}, {}:",
+            DOT.info(),
+            update.task_name.clone().bold(),
+            update.phase.to_string().highlight(),
+            update.output_path.to_string_lossy().to_string().muted()
+        );
+        print!("{}", diff::render(&update.expected_output, &update.output));
+        if policy.confirm("Record the new output as expected?".to_owned())? {
+            update.write()?;
+            updated += 1;
+        }
+    }
+    if policy == ConfirmPolicy::Skip && !updates.is_empty() {
+        println!(
+            "{} Left {} expected outputs unchanged, pass --yes to record them without asking",
+            CROSS.failure(),
+            updates.len().to_string().highlight()
+        );
+        return Ok(0);
+    }
+    println!(
+        "{} Updated {} expected outputs",
+        CHECKMARK.success(),
+        updated.to_string().highlight()
+    );
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, sync::Arc};
+
+    use super::*;
+    use crate::{testing::TempDir, AocContext, AocStringIter, AocTask};
+
+    struct CountTask {
+        directory: PathBuf,
+    }
+
+    impl AocTask for CountTask {
+        fn directory(&self) -> PathBuf {
+            self.directory.clone()
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(vec![input.count().to_string(), "lines".to_owned()])
+        }
+    }
+
+    #[test]
+    fn failing_examples_are_updated() {
+        let directory = TempDir::new("bless");
+        directory.write("example_in", "a\nb\n");
+        directory.write("example_out", "");
+        directory.write("example_phase2_in", "a\n");
+        directory.write("example_phase2_out", "1\nlines\n");
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(CountTask {
+            directory: directory.path().to_owned(),
+        })];
+
+        let updates = pending_updates(&tasks, &Filter::all()).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].output_path, directory.join("example_out"));
+        assert_eq!(updates[0].output, ["2", "lines"]);
+
+        assert_eq!(
+            update_expected(&tasks, &Filter::all(), ConfirmPolicy::Skip).unwrap(),
+            0
+        );
+        assert_eq!(
+            fs::read_to_string(directory.join("example_out")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            update_expected(&tasks, &Filter::all(), ConfirmPolicy::AcceptAll).unwrap(),
+            1
+        );
+        assert_eq!(
+            fs::read_to_string(directory.join("example_out")).unwrap(),
+            "2\nlines\n"
+        );
+        assert!(pending_updates(&tasks, &Filter::all()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{client::AOC_URL, error::AocError, style::Stylize, AocTask, DOT};
+
+pub fn puzzle_url(year: u16, day: u8) -> String {
+    format!("{AOC_URL}/{year}/day/{day}")
+}
+
+// The part is shown below the previous ones, its answer form is at the end of it
+pub fn part_url(year: u16, day: u8, part: usize) -> String {
+    format!("{}#part{part}", puzzle_url(year, day))
+}
+
+pub fn open_url(url: &str) -> Result<(), AocError> {
+    open::that(url).map_err(|err| AocError::BrowserError {
+        url: url.to_owned(),
+        source: err,
+    })?;
+    println!("{} Opened {}", DOT.info(), url.to_owned().muted());
+    Ok(())
+}
+
+// The given part of the puzzle of the task, or the whole puzzle, when the task has a date
+pub fn task_url(task: &dyn AocTask, part: Option<usize>) -> Option<String> {
+    let (year, day) = (task.year()?, task.day()?);
+    Some(match part {
+        Some(part) => part_url(year, day, part),
+        None => puzzle_url(year, day),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn puzzle_urls() {
+        let task = MockTask::new(|_, _| Ok(vec![])).with_date(2022, 5);
+        assert_eq!(
+            task_url(&task, Some(1)).as_deref(),
+            Some("https://adventofcode.com/2022/day/5#part1")
+        );
+        assert_eq!(task_url(&MockTask::new(|_, _| Ok(vec![])), None), None);
+        assert_eq!(puzzle_url(2023, 1), "https://adventofcode.com/2023/day/1");
+        assert_eq!(
+            part_url(2024, 12, 2),
+            "https://adventofcode.com/2024/day/12#part2"
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, Once,
+};
+
+use thiserror::Error;
+
+use crate::{style::Stylize, DOT};
+
+static INTERRUPTED_RUN: Mutex<Option<CancellationToken>> = Mutex::new(None);
+static INTERRUPT_HANDLER: Once = Once::new();
+
+// Shared flag that long running solutions poll through `AocContext::check_cancelled` to stop
+// early, the phase is then reported as cancelled
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("The solution was cancelled")]
+pub struct Cancelled;
+
+fn interrupted_run() -> std::sync::MutexGuard<'static, Option<CancellationToken>> {
+    INTERRUPTED_RUN
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+// Clears the token of the run once it is over, Ctrl-C then ends the process again
+pub(crate) struct InterruptGuard {
+    token: CancellationToken,
+}
+
+impl Drop for InterruptGuard {
+    fn drop(&mut self) {
+        let mut run = interrupted_run();
+        if run
+            .as_ref()
+            .is_some_and(|token| Arc::ptr_eq(&token.cancelled, &self.token.cancelled))
+        {
+            *run = None;
+        }
+    }
+}
+
+// While the guard lives, the first Ctrl-C cancels the token of the run and the second one ends
+// the process. Outside of a run, Ctrl-C ends the process right away.
+pub(crate) fn cancel_on_interrupt(token: &CancellationToken) -> InterruptGuard {
+    *interrupted_run() = Some(token.clone());
+    INTERRUPT_HANDLER.call_once(|| {
+        // Another handler installed by the binary takes precedence
+        let _ = ctrlc::set_handler(|| {
+            let token = interrupted_run().clone();
+            match token {
+                Some(token) if !token.is_cancelled() => {
+                    eprintln!(
+                        "{} Cancelling the run, press Ctrl-C again to exit",
+                        DOT.highlight()
+                    );
+                    token.cancel();
+                }
+                _ => std::process::exit(130),
+            }
+        });
+    });
+    InterruptGuard {
+        token: token.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finished_runs_are_not_cancelled_on_interrupt() {
+        let token = CancellationToken::new();
+        let guard = cancel_on_interrupt(&token);
+        let is_active = || {
+            interrupted_run()
+                .as_ref()
+                .is_some_and(|run| Arc::ptr_eq(&run.cancelled, &token.cancelled))
+        };
+        assert!(is_active());
+
+        drop(guard);
+        assert!(!is_active());
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::{self, Read, Write};
+#[cfg(unix)]
+use std::{fs::File, sync::OnceLock};
+
+use gag::BufferRedirect;
+
+use crate::progress;
+
+// Redirects the process-wide stdout and stderr into temporary files until the capture is
+// finished. Only stdout is captured on platforms where the progress bars cannot keep drawing
+// to the original stderr.
+pub(crate) struct OutputCapture {
+    stdout: BufferRedirect,
+    #[cfg(unix)]
+    stderr: BufferRedirect,
+}
+
+// A duplicate of stdout from before it was first redirected
+#[cfg(unix)]
+fn original_stdout() -> Option<&'static File> {
+    use std::os::fd::AsFd;
+
+    static STDOUT: OnceLock<Option<File>> = OnceLock::new();
+    STDOUT
+        .get_or_init(|| {
+            io::stdout()
+                .as_fd()
+                .try_clone_to_owned()
+                .ok()
+                .map(File::from)
+        })
+        .as_ref()
+}
+
+// Prints past an active capture, for what the runner shows while a solution is running
+pub(crate) fn print_uncaptured(text: &str) {
+    let _ = io::stdout().flush();
+    #[cfg(unix)]
+    if let Some(mut stdout) = original_stdout() {
+        let _ = stdout.write_all(text.as_bytes());
+        return;
+    }
+    print!("{text}");
+}
+
+impl OutputCapture {
+    // Fails when another capture is already active
+    pub(crate) fn start() -> Option<Self> {
+        progress::init();
+        #[cfg(unix)]
+        original_stdout();
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+        Some(Self {
+            stdout: BufferRedirect::stdout().ok()?,
+            #[cfg(unix)]
+            stderr: BufferRedirect::stderr().ok()?,
+        })
+    }
+
+    pub(crate) fn finish(self) -> String {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+
+        let mut output = vec![];
+        let _ = self.stdout.into_inner().read_to_end(&mut output);
+        #[cfg(unix)]
+        let _ = self.stderr.into_inner().read_to_end(&mut output);
+        String::from_utf8_lossy(&output).into_owned()
+    }
+}
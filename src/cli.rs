@@ -0,0 +1,1085 @@
+use std::{
+    ffi::OsString,
+    io::{self, IsTerminal},
+    path::PathBuf,
+    process::ExitCode,
+    time::Duration,
+};
+
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+use clap::{Args, Parser, Subcommand};
+use dialoguer::{theme::ColorfulTheme, MultiSelect, Select};
+use itertools::Itertools;
+
+#[cfg(feature = "leaderboard")]
+use crate::leaderboard;
+#[cfg(feature = "dhat")]
+use crate::profile;
+#[cfg(feature = "describe")]
+use crate::puzzle;
+#[cfg(feature = "clipboard")]
+use crate::reporter::ClipboardReporter;
+#[cfg(feature = "serde")]
+use crate::reporter::JsonReporter;
+#[cfg(feature = "notifications")]
+use crate::reporter::NotificationReporter;
+#[cfg(feature = "serve")]
+use crate::serve;
+#[cfg(feature = "tui")]
+use crate::tui;
+#[cfg(feature = "serde")]
+use crate::typed;
+#[cfg(feature = "watch")]
+use crate::watch::{watch, WatchOptions};
+#[cfg(any(feature = "watch", feature = "serve"))]
+use crate::RunOptions;
+use crate::{
+    alt_inputs::run_alt_inputs,
+    baseline::{compare_baseline, save_baseline, DEFAULT_BASELINE},
+    bench::{bench_tasks, compare_variants, BenchOptions},
+    bless,
+    client::AocClient,
+    config::{config_path, Config},
+    differential::{self, Implementation},
+    error::AocError,
+    hashed, input, input_files, inspect,
+    prompt::{self, ConfirmPolicy},
+    report::{RunStatus, INTERNAL_ERROR_EXIT_CODE},
+    reporter::{ConsoleReporter, JunitReporter, Verbosity, WebhookReporter},
+    scaffold,
+    session::{self, Session, SessionSource},
+    source, status,
+    stress::{stress_tasks, StressOptions},
+    style::{self, Stylize, Theme},
+    unlock, AocRunner, Filter, SharedAocTask, CHECKMARK, CROSS,
+};
+#[cfg(feature = "browser")]
+use crate::{browser, reporter::BrowserReporter};
+
+#[derive(Parser, Debug)]
+#[command(about = "Run and manage Advent of Code tasks")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Drop colors, unicode symbols and emoji, which is the default when NO_COLOR is set or the output is piped
+    #[arg(long, global = true)]
+    pub plain: bool,
+    /// Use ASCII symbols instead of the unicode ones
+    #[arg(long, global = true)]
+    pub ascii: bool,
+}
+
+#[derive(Args, Debug, Default)]
+pub struct FilterArgs {
+    /// Only include the task of the given day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+    /// Only include the given phase
+    #[arg(long)]
+    pub phase: Option<usize>,
+    /// Only include tasks with names containing the given text
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+impl From<FilterArgs> for Filter {
+    fn from(args: FilterArgs) -> Self {
+        Filter {
+            name: args.name,
+            day: args.day,
+            phase: args.phase,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Check the examples and solve the inputs of the registered tasks
+    Run {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Never prompt, report unsolved phases instead
+        #[arg(long)]
+        non_interactive: bool,
+        /// Run all tasks instead of picking them from a menu when no filter is given
+        #[arg(long)]
+        all: bool,
+        /// Run the tasks and their examples on multiple threads
+        #[arg(long)]
+        parallel: bool,
+        /// Keep running the remaining tasks after a failure
+        #[arg(long)]
+        keep_going: bool,
+        /// Hide the spinners and progress bars of the solved phases
+        #[arg(long)]
+        no_progress: bool,
+        /// Fail the phases that take longer than the given number of seconds to solve
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Skip the examples and inputs of the phases that are already marked as solved
+        #[arg(long)]
+        skip_solved: bool,
+        /// Ignore the solved markers and ask whether the phases are solved again
+        #[arg(long)]
+        force: bool,
+        /// Show the answer when asking whether it was accepted
+        #[arg(long)]
+        answer_prompt: bool,
+        /// Hide the answers of the inputs, e.g. while streaming, and copy them to the clipboard when it is available
+        #[arg(long)]
+        spoiler_free: bool,
+        /// Accept the answers of the unsolved phases, and the outputs recorded by --bless, without asking
+        #[arg(short, long, conflicts_with = "no_prompt")]
+        yes: bool,
+        /// Leave the answers of the unsolved phases unconfirmed instead of asking
+        #[arg(long)]
+        no_prompt: bool,
+        /// Let the solutions print directly instead of showing their output only on failure
+        #[arg(long)]
+        no_capture: bool,
+        /// Show long outputs and diffs in full instead of cutting them off
+        #[arg(long)]
+        full: bool,
+        /// Solve another input instead, a path, a URL, `-` for stdin with --non-interactive or `cmd:` followed by a command printing it
+        #[arg(long, value_name = "SOURCE")]
+        input: Option<String>,
+        /// Record the current outputs of the failing examples as expected, confirming each one unless --yes is passed
+        #[arg(long)]
+        bless: bool,
+        /// Only show failures and the final summary
+        #[arg(short, long, conflicts_with = "verbose")]
+        quiet: bool,
+        /// Show the outputs of all examples, pass it twice to also show timings
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+        /// Write a JSON summary of the run to a file, `-` writes it to stdout
+        #[cfg(feature = "serde")]
+        #[arg(long)]
+        json: Option<PathBuf>,
+        /// Write a JUnit XML report of the run to a file
+        #[arg(long)]
+        junit: Option<PathBuf>,
+        /// Show a desktop notification when a phase that ran for at least the given number of seconds finishes
+        #[cfg(feature = "notifications")]
+        #[arg(long, value_name = "SECONDS")]
+        notify_after: Option<u64>,
+        /// Copy the answers of the unsolved phases to the clipboard
+        #[cfg(feature = "clipboard")]
+        #[arg(long)]
+        copy: bool,
+        /// Open the puzzle in the browser once a phase is solved
+        #[cfg(feature = "browser")]
+        #[arg(long)]
+        open: bool,
+    },
+    /// Rerun the solved phases without prompting and check them against their accepted answers
+    Verify {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Run the tasks and their examples on multiple threads
+        #[arg(long)]
+        parallel: bool,
+    },
+    /// Solve the inputs in the alt_inputs directories of the tasks and check them against their `_out` answers
+    AltInputs {
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// List the registered tasks and their solved phases
+    List,
+    /// Mark a phase of a day as unsolved, or all of its phases when no phase is given
+    Unsolve {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        phase: Option<usize>,
+        /// The year of the day, needed when tasks of several years are registered
+        #[arg(long)]
+        year: Option<u16>,
+    },
+    /// Show statistics, the detected structure and the first and last lines of the input of a day
+    Inspect {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The year of the day, needed when tasks of several years are registered
+        #[arg(long)]
+        year: Option<u16>,
+        /// The number of lines to show from the start and the end of the input
+        #[arg(long, default_value_t = 5)]
+        lines: usize,
+    },
+    /// Show a calendar of the collected stars
+    Status {
+        /// Mark the phases solved on adventofcode.com as solved first
+        #[arg(long)]
+        sync: bool,
+    },
+    /// Measure how long the solutions take to solve the inputs
+    Bench {
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[arg(long, default_value_t = 3)]
+        warmup: u32,
+        #[arg(long, default_value_t = 10)]
+        iterations: u32,
+        /// Compare the alternate implementations of the solutions against the default ones
+        #[arg(long)]
+        variants: bool,
+        /// Save the results as a named baseline
+        #[arg(long, value_name = "NAME", num_args = 0..=1, default_missing_value = DEFAULT_BASELINE)]
+        save_baseline: Option<String>,
+        /// Compare the results against a saved baseline
+        #[arg(long, value_name = "NAME", num_args = 0..=1, default_missing_value = DEFAULT_BASELINE)]
+        compare: Option<String>,
+        /// Slowdown in percent that is reported as a regression
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+    },
+    /// Measure the solutions on generated inputs of growing scales to estimate their complexity
+    Stress {
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[arg(long, default_value_t = 1)]
+        start: usize,
+        /// Multiplies the scale after every step
+        #[arg(long, default_value_t = 2)]
+        factor: usize,
+        #[arg(long, default_value_t = 8)]
+        steps: u32,
+        #[arg(long, default_value_t = 3)]
+        iterations: u32,
+        /// Stop growing the scale once a solve takes longer than this many seconds
+        #[arg(long, default_value_t = 10)]
+        time_limit: u64,
+    },
+    /// Run two implementations on the same inputs and show the first input their outputs differ on
+    Differential {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The year of the days, needed when tasks of several years are registered
+        #[arg(long)]
+        year: Option<u16>,
+        #[arg(long, default_value_t = 1)]
+        phase: usize,
+        /// Compare against the task of another day instead of the same task
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        against: Option<u8>,
+        /// The variant of the first implementation, the default one when left out
+        #[arg(long)]
+        left: Option<String>,
+        /// The variant of the second implementation, the default one when left out
+        #[arg(long)]
+        right: Option<String>,
+        /// The number of generated inputs to try when no input files are given
+        #[arg(long, default_value_t = 100)]
+        scales: usize,
+        inputs: Vec<PathBuf>,
+    },
+    /// Solve a single phase under the dhat heap profiler
+    #[cfg(feature = "dhat")]
+    Profile {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Defaults to a file in the data directory of the task
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Browse, run and mark the tasks in a full-screen dashboard
+    #[cfg(feature = "tui")]
+    Tui {
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Run the tasks and show their progress, diffs and benchmark baselines on a local web page
+    #[cfg(feature = "serve")]
+    Serve {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// The address to serve the page on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: std::net::SocketAddr,
+    },
+    /// Rerun the tasks whenever their inputs, examples or sources change
+    #[cfg(feature = "watch")]
+    Watch {
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Never prompt, report unsolved phases instead
+        #[arg(long)]
+        non_interactive: bool,
+        /// Source directories that trigger a rebuild when changed
+        #[arg(long, default_value = "src")]
+        source: Vec<PathBuf>,
+    },
+    /// Show the description of a puzzle
+    #[cfg(feature = "describe")]
+    Describe {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Defaults to the year of the registered task of the day
+        #[arg(long)]
+        year: Option<u16>,
+        /// Show the parts up to the given one, defaults to the first unsolved part
+        #[arg(long)]
+        part: Option<usize>,
+    },
+    /// Open a puzzle in the browser
+    #[cfg(feature = "browser")]
+    Open {
+        /// Defaults to the most recently unlocked puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Defaults to the year of the registered task of the day
+        #[arg(long)]
+        year: Option<u16>,
+        /// Open the given part instead of the top of the puzzle
+        #[arg(long)]
+        part: Option<usize>,
+    },
+    /// Show the standings of a private leaderboard
+    #[cfg(feature = "leaderboard")]
+    Leaderboard {
+        /// The id of the leaderboard, which is also the id of its owner
+        id: u64,
+        #[arg(long)]
+        year: u16,
+        /// Also show the completion times of the given day
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+    /// Keep the puzzle inputs out of the repository or share them encrypted
+    Inputs {
+        #[command(subcommand)]
+        command: InputsCommand,
+    },
+    /// Store or check the adventofcode.com session cookie
+    Session {
+        #[command(subcommand)]
+        command: SessionCommand,
+    },
+    /// Wait for the next puzzle to unlock, then create its task and download its input
+    WaitForUnlock {
+        /// The task is created in a `year_<year>` directory within it, unless it names a year
+        #[arg(long, default_value = "src/tasks")]
+        directory: PathBuf,
+    },
+    /// Download the inputs of the unlocked puzzles that are not on disk yet
+    Download {
+        #[arg(
+            required_unless_present = "all",
+            conflicts_with = "all",
+            value_parser = clap::value_parser!(u8).range(1..=25)
+        )]
+        day: Option<u8>,
+        /// Defaults to the year of the registered task of the day, or of the latest event
+        #[arg(long)]
+        year: Option<u16>,
+        /// Download every day of the year
+        #[arg(long)]
+        all: bool,
+    },
+    /// Keep waiting for the remaining puzzles of the event and download their inputs as soon as they unlock
+    Prefetch,
+    /// Create and register a new task from a template
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, default_value = "src/tasks")]
+        directory: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum InputsCommand {
+    /// Add the downloaded inputs to the `.gitignore` files next to them
+    Ignore,
+    /// Write salted hashes of the example outputs and accepted answers that can be committed instead of them
+    Hash,
+    /// Write an encrypted `in.age` copy of every input that can be committed
+    #[cfg(feature = "encrypt")]
+    Encrypt,
+    /// Restore the inputs from their encrypted `in.age` copies
+    #[cfg(feature = "encrypt")]
+    Decrypt,
+    /// Remove the parsed inputs that the typed tasks cached on disk
+    #[cfg(feature = "serde")]
+    ClearCache,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SessionCommand {
+    /// Store the session cookie in the config file
+    Set {
+        token: String,
+        /// Store the session cookie in the OS keyring instead
+        #[cfg(feature = "keyring")]
+        #[arg(long)]
+        keyring: bool,
+    },
+    /// Check that the stored session cookie is still valid
+    Check,
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Run {
+            filter: FilterArgs::default(),
+            non_interactive: false,
+            all: false,
+            parallel: false,
+            keep_going: false,
+            no_progress: false,
+            timeout: None,
+            skip_solved: false,
+            force: false,
+            answer_prompt: false,
+            spoiler_free: false,
+            yes: false,
+            no_prompt: false,
+            no_capture: false,
+            full: false,
+            input: None,
+            bless: false,
+            quiet: false,
+            verbose: 0,
+            #[cfg(feature = "serde")]
+            json: None,
+            junit: None,
+            #[cfg(feature = "notifications")]
+            notify_after: None,
+            #[cfg(feature = "clipboard")]
+            copy: false,
+            #[cfg(feature = "browser")]
+            open: false,
+        }
+    }
+}
+
+pub fn run(tasks: Vec<SharedAocTask>) -> Result<bool, AocError> {
+    run_with_args(tasks, std::env::args_os())
+}
+
+pub fn run_with_args<I, T>(tasks: Vec<SharedAocTask>, args: I) -> Result<bool, AocError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    execute_with_args(tasks, args).map(|status| status == RunStatus::Solved)
+}
+
+// Entry point for binaries, `fn main() -> ExitCode { aoc_framework::cli::main(tasks()) }`, the
+// exit code is 0 when all phases were solved, 1 on example failures, 2 on unsolved phases and
+// 3 on errors
+pub fn main(tasks: Vec<SharedAocTask>) -> ExitCode {
+    main_with_args(tasks, std::env::args_os())
+}
+
+pub fn main_with_args<I, T>(tasks: Vec<SharedAocTask>, args: I) -> ExitCode
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    match execute_with_args(tasks, args) {
+        Ok(status) => ExitCode::from(status.exit_code()),
+        Err(err) => {
+            eprintln!("{} {}", CROSS.failure(), err.to_string().failure());
+            ExitCode::from(INTERNAL_ERROR_EXIT_CODE)
+        }
+    }
+}
+
+pub fn execute_with_args<I, T>(tasks: Vec<SharedAocTask>, args: I) -> Result<RunStatus, AocError>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let cli = Cli::parse_from(args);
+    if cli.plain {
+        style::set_plain(true);
+    }
+    if cli.ascii {
+        style::set_theme(Theme::ASCII);
+    }
+    match cli.command.unwrap_or_default() {
+        Command::Run {
+            filter,
+            non_interactive,
+            all,
+            parallel,
+            keep_going,
+            no_progress,
+            timeout,
+            skip_solved,
+            force,
+            answer_prompt,
+            spoiler_free,
+            yes,
+            no_prompt,
+            no_capture,
+            full,
+            input,
+            bless,
+            quiet,
+            verbose,
+            #[cfg(feature = "serde")]
+            json,
+            junit,
+            #[cfg(feature = "notifications")]
+            notify_after,
+            #[cfg(feature = "clipboard")]
+            copy,
+            #[cfg(feature = "browser")]
+            open,
+        } => {
+            // The prompts would read from the same stdin as the input
+            if input.as_deref() == Some("-") && !non_interactive {
+                return Err(AocError::InteractiveStdinInput);
+            }
+            let mut filter = Filter::from(filter);
+            let mut tasks = tasks;
+            // The menu is only shown when it can be answered and there is more than one choice
+            if filter == Filter::all() && !all && !non_interactive && io::stdin().is_terminal() {
+                (tasks, filter) = pick_tasks(tasks)?;
+            }
+            if bless {
+                // Without anybody to confirm them, the changes are only listed
+                let policy = match confirm_policy(yes, no_prompt)? {
+                    ConfirmPolicy::Ask if non_interactive => ConfirmPolicy::Skip,
+                    policy => policy,
+                };
+                bless::update_expected(&tasks, &filter, policy)?;
+                return Ok(RunStatus::Solved);
+            }
+            let mut runner = AocRunner::new(&tasks)
+                .interactive(!non_interactive)
+                .parallel(parallel)
+                .fail_fast(!keep_going)
+                .progress(!no_progress)
+                .timeout(timeout.map(Duration::from_secs))
+                .skip_solved(skip_solved)
+                .force(force)
+                .answer_prompt(answer_prompt && !spoiler_free)
+                .capture_output(!no_capture)
+                .input_source(input.as_deref().map(source::parse_source))
+                .filter(filter);
+            #[cfg(feature = "clipboard")]
+            let copy = copy || spoiler_free;
+            #[cfg(not(feature = "clipboard"))]
+            let copy = false;
+            let console = ConsoleReporter::default()
+                .verbosity(Verbosity::from_flags(quiet, verbose))
+                .hide_answers(spoiler_free)
+                .answers_copied(copy);
+            runner = runner.reporter(if full {
+                console.max_lines(None)
+            } else {
+                console
+            });
+            #[cfg(feature = "serde")]
+            match json {
+                // Keep stdout machine-readable when the summary is written to it
+                Some(path) if path == Path::new("-") => {
+                    runner = runner.reporter(JsonReporter::stdout())
+                }
+                Some(path) => runner = runner.add_reporter(JsonReporter::to_file(&path)?),
+                None => {}
+            }
+            if let Some(path) = junit {
+                runner = runner.add_reporter(JunitReporter::to_file(&path)?);
+            }
+            runner = add_webhooks(runner)?;
+            runner = runner.confirm(confirm_policy(yes, no_prompt)?);
+            #[cfg(feature = "notifications")]
+            if let Some(seconds) = notify_after {
+                runner = runner.add_reporter(
+                    NotificationReporter::new(Duration::from_secs(seconds))
+                        .hide_answers(spoiler_free),
+                );
+            }
+            #[cfg(feature = "clipboard")]
+            if copy {
+                runner = runner.add_reporter(ClipboardReporter::new());
+            }
+            #[cfg(feature = "browser")]
+            if open {
+                runner = runner.add_reporter(BrowserReporter::new());
+            }
+            let report = runner.run()?;
+            Ok(report.status())
+        }
+        Command::Verify { filter, parallel } => {
+            let runner = AocRunner::new(&tasks)
+                .interactive(false)
+                .parallel(parallel)
+                .fail_fast(false)
+                .solved_only(true)
+                .filter(filter.into());
+            let report = add_webhooks(runner)?.run()?;
+            Ok(report.status())
+        }
+        Command::List => {
+            list(&tasks)?;
+            Ok(RunStatus::Solved)
+        }
+        Command::Unsolve { day, phase, year } => {
+            crate::unsolve(&tasks, year, day, phase)?;
+            println!(
+                "{} Cleared the solved markers of day {}",
+                CHECKMARK.success(),
+                day.to_string().bold()
+            );
+            Ok(RunStatus::Solved)
+        }
+        Command::Inspect { day, year, lines } => {
+            let task = crate::find_task(&tasks, year, day)?;
+            let input = input::map_input(&task.input_path())?;
+            let input = String::from_utf8_lossy(&input);
+            print!(
+                "{}",
+                inspect::render(&input, &inspect::inspect(&input), lines)
+            );
+            Ok(RunStatus::Solved)
+        }
+        Command::Status { sync } => {
+            if sync {
+                status::sync_solved_phases(&AocClient::load()?, &tasks)?;
+            }
+            for (year, calendar) in status::local_calendars(&tasks)? {
+                println!("{}", status::render_calendar(year, &calendar));
+            }
+            Ok(RunStatus::Solved)
+        }
+        Command::Bench {
+            filter,
+            warmup,
+            iterations,
+            variants,
+            save_baseline: baseline_name,
+            compare,
+            threshold,
+        } => {
+            let options = BenchOptions {
+                warmup_iterations: warmup,
+                iterations,
+            };
+            if variants {
+                return compare_variants(&tasks, &filter.into(), &options).map(RunStatus::from);
+            }
+            let results = bench_tasks(&tasks, &filter.into(), &options)?;
+            let no_regressions = match compare {
+                Some(name) => compare_baseline(&results, &name, threshold)?,
+                None => true,
+            };
+            if let Some(name) = baseline_name {
+                save_baseline(&results, &name)?;
+            }
+            Ok(no_regressions.into())
+        }
+        Command::Stress {
+            filter,
+            start,
+            factor,
+            steps,
+            iterations,
+            time_limit,
+        } => {
+            let options = StressOptions {
+                start_scale: start,
+                factor,
+                steps,
+                iterations,
+                time_limit: Duration::from_secs(time_limit),
+            };
+            if stress_tasks(&tasks, &filter.into(), &options)?.is_empty() {
+                println!(
+                    "{} No tasks implement {}",
+                    CROSS.failure(),
+                    "generate_input".bold()
+                );
+                return Ok(RunStatus::Unsolved);
+            }
+            Ok(RunStatus::Solved)
+        }
+        Command::AltInputs { filter } => {
+            run_alt_inputs(&tasks, &filter.into()).map(RunStatus::from)
+        }
+        Command::Differential {
+            day,
+            year,
+            phase,
+            against,
+            left,
+            right,
+            scales,
+            inputs,
+        } => {
+            let left_task = crate::find_task(&tasks, year, day)?;
+            let right_task = crate::find_task(&tasks, year, against.unwrap_or(day))?;
+            let left = Implementation::new(left_task, left.as_deref(), phase)?;
+            let right = Implementation::new(right_task, right.as_deref(), phase)?;
+            let inputs = if inputs.is_empty() {
+                differential::generated_inputs(left_task, scales)
+            } else {
+                differential::file_inputs(&inputs)?
+            };
+            if inputs.is_empty() {
+                println!(
+                    "{} Pass input files or implement {} for {}",
+                    CROSS.failure(),
+                    "generate_input".bold(),
+                    left_task.name().bold()
+                );
+                return Ok(RunStatus::Unsolved);
+            }
+            differential::run_differential(&left, &right, phase, inputs).map(RunStatus::from)
+        }
+        #[cfg(feature = "dhat")]
+        Command::Profile { filter, output } => {
+            let (task, phase) = profile::select_phase(&tasks, &filter.into())?;
+            let output = output.unwrap_or_else(|| profile::profile_path(task.as_ref(), phase));
+            profile::profile_phase(task, phase, &output)?;
+            Ok(RunStatus::Solved)
+        }
+        #[cfg(feature = "tui")]
+        Command::Tui { filter } => {
+            tui::run_dashboard(&tasks, &filter.into())?;
+            Ok(RunStatus::Solved)
+        }
+        #[cfg(feature = "serve")]
+        Command::Serve { filter, address } => {
+            let options = RunOptions {
+                interactive: false,
+                progress: false,
+                fail_fast: false,
+                capture_output: true,
+                filter: filter.into(),
+                ..RunOptions::default()
+            };
+            serve::serve(tasks, options, address)?;
+            Ok(RunStatus::Solved)
+        }
+        #[cfg(feature = "watch")]
+        Command::Watch {
+            filter,
+            non_interactive,
+            source,
+        } => {
+            let options = WatchOptions {
+                run_options: RunOptions {
+                    interactive: !non_interactive,
+                    filter: filter.into(),
+                    ..RunOptions::default()
+                },
+                source_paths: source,
+                ..WatchOptions::default()
+            };
+            watch(&tasks, &options, &mut ConsoleReporter::default())?;
+            Ok(RunStatus::Solved)
+        }
+        #[cfg(feature = "describe")]
+        Command::Describe { day, year, part } => {
+            describe(&tasks, day, year, part)?;
+            Ok(RunStatus::Solved)
+        }
+        #[cfg(feature = "browser")]
+        Command::Open { day, year, part } => {
+            open_puzzle(&tasks, day, year, part)?;
+            Ok(RunStatus::Solved)
+        }
+        #[cfg(feature = "leaderboard")]
+        Command::Leaderboard { id, year, day } => {
+            let leaderboard = leaderboard::fetch_leaderboard(&AocClient::load()?, year, id)?;
+            print!("{}", leaderboard::render_leaderboard(&leaderboard, day));
+            Ok(RunStatus::Solved)
+        }
+        Command::Inputs { command } => {
+            manage_inputs(&tasks, command)?;
+            Ok(RunStatus::Solved)
+        }
+        Command::Session { command } => {
+            session(command)?;
+            Ok(RunStatus::Solved)
+        }
+        Command::WaitForUnlock { directory } => {
+            unlock::wait_for_unlock(&directory)?;
+            Ok(RunStatus::Solved)
+        }
+        Command::Download { day, year, .. } => {
+            let registered_year = match (year, day) {
+                (None, Some(day)) => registered_year(&tasks, day)?,
+                _ => None,
+            };
+            let year = year
+                .or(registered_year)
+                .unwrap_or_else(|| unlock::latest_unlock(chrono::Utc::now()).0);
+            let days = match day {
+                Some(day) => day..=day,
+                None => 1..=unlock::event_days(year),
+            };
+            let downloaded = unlock::download_inputs(&AocClient::load()?, &tasks, year, days)?;
+            print_paths("Downloaded", &downloaded);
+            Ok(RunStatus::Solved)
+        }
+        Command::Prefetch => {
+            unlock::prefetch_inputs(&tasks)?;
+            Ok(RunStatus::Solved)
+        }
+        Command::New { day, directory } => {
+            new_task(day, directory)?;
+            Ok(RunStatus::Solved)
+        }
+    }
+}
+
+fn list(tasks: &[SharedAocTask]) -> Result<(), AocError> {
+    for task in tasks {
+        let solved = (1..=task.phases())
+            .map(|phase| {
+                Ok(if task.phase_is_solved(phase)? {
+                    CHECKMARK.success()
+                } else {
+                    CROSS.failure()
+                })
+            })
+            .collect::<Result<Vec<_>, AocError>>()?;
+        println!("{} {}", solved.iter().join(" "), task.name().bold());
+    }
+    Ok(())
+}
+
+// The webhooks of `aoc.toml` are notified about solved phases and failed runs
+fn add_webhooks(runner: AocRunner<'_>) -> Result<AocRunner<'_>, AocError> {
+    let config = Config::load(&config_path())?;
+    if config.webhooks.is_empty() {
+        return Ok(runner);
+    }
+    Ok(runner.add_reporter(WebhookReporter::new(config.webhooks)))
+}
+
+// The flags take precedence over the prompt settings of `aoc.toml`
+fn confirm_policy(yes: bool, no_prompt: bool) -> Result<ConfirmPolicy, AocError> {
+    let config = Config::load(&config_path())?;
+    prompt::set_default_answer(config.default_answer);
+    Ok(if yes {
+        ConfirmPolicy::AcceptAll
+    } else if no_prompt {
+        ConfirmPolicy::Skip
+    } else {
+        config.confirm.unwrap_or_default()
+    })
+}
+
+// Lets the user choose the tasks and the phase to run, the tasks with unsolved phases are
+// preselected
+fn pick_tasks(tasks: Vec<SharedAocTask>) -> Result<(Vec<SharedAocTask>, Filter), AocError> {
+    if tasks.len() < 2 {
+        return Ok((tasks, Filter::all()));
+    }
+    let solved = tasks
+        .iter()
+        .map(|task| {
+            (1..=task.phases())
+                .map(|phase| task.phase_is_solved(phase))
+                .collect::<Result<Vec<_>, AocError>>()
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+    let labels = tasks
+        .iter()
+        .zip(&solved)
+        .map(|(task, solved)| {
+            let solved = solved
+                .iter()
+                .map(|&solved| if solved { CHECKMARK } else { CROSS })
+                .join(" ");
+            format!("{solved} {}", task.name())
+        })
+        .collect_vec();
+    let unsolved = solved
+        .iter()
+        .map(|solved| solved.contains(&false))
+        .collect_vec();
+    let picked = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which tasks should be run?")
+        .items(&labels)
+        .defaults(&unsolved)
+        .interact()
+        .map_err(|dialog_err| AocError::UserInterractionError { source: dialog_err })?;
+
+    let tasks = tasks
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| picked.contains(index))
+        .map(|(_, task)| task)
+        .collect_vec();
+    let phases = tasks.iter().map(|task| task.phases()).max().unwrap_or(0);
+    if phases < 2 {
+        return Ok((tasks, Filter::all()));
+    }
+    let phase_labels = std::iter::once("All phases".to_owned())
+        .chain((1..=phases).map(|phase| format!("Phase {phase}")))
+        .collect_vec();
+    let phase = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which phases should be run?")
+        .items(&phase_labels)
+        .default(0)
+        .interact()
+        .map_err(|dialog_err| AocError::UserInterractionError { source: dialog_err })?;
+    let filter = match phase {
+        0 => Filter::all(),
+        phase => Filter::all().phase(phase),
+    };
+    Ok((tasks, filter))
+}
+
+fn print_paths(action: &str, paths: &[PathBuf]) {
+    if paths.is_empty() {
+        println!("{} Nothing to do", CHECKMARK.success());
+    }
+    for path in paths {
+        println!(
+            "{} {} {}",
+            CHECKMARK.success(),
+            action,
+            path.to_string_lossy().bold()
+        );
+    }
+}
+
+fn manage_inputs(tasks: &[SharedAocTask], command: InputsCommand) -> Result<(), AocError> {
+    match command {
+        InputsCommand::Ignore => print_paths("Updated", &input_files::ignore_inputs(tasks)?),
+        InputsCommand::Hash => print_paths("Wrote", &hashed::hash_outputs(tasks)?),
+        #[cfg(feature = "encrypt")]
+        InputsCommand::Encrypt => {
+            let passphrase = input_files::passphrase(true)?;
+            print_paths("Wrote", &input_files::encrypt_inputs(tasks, &passphrase)?);
+        }
+        #[cfg(feature = "encrypt")]
+        InputsCommand::Decrypt => {
+            let passphrase = input_files::passphrase(false)?;
+            print_paths(
+                "Restored",
+                &input_files::decrypt_inputs(tasks, &passphrase)?,
+            );
+        }
+        #[cfg(feature = "serde")]
+        InputsCommand::ClearCache => print_paths("Removed", &typed::clear_disk_caches(tasks)?),
+    }
+    Ok(())
+}
+
+fn new_task(day: u8, directory: PathBuf) -> Result<(), AocError> {
+    let task_directory = scaffold::scaffold_task(day, &directory)?;
+    println!(
+        "{} Created {}",
+        CHECKMARK.success(),
+        task_directory.to_string_lossy().bold()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "describe")]
+fn describe(
+    tasks: &[SharedAocTask],
+    day: u8,
+    year: Option<u16>,
+    part: Option<usize>,
+) -> Result<(), AocError> {
+    let year = match year {
+        Some(year) => Some(year),
+        None => registered_year(tasks, day)?,
+    };
+    // Days without a task can be described as well
+    let task = match crate::find_task(tasks, year, day) {
+        Ok(task) => Some(task),
+        Err(AocError::MissingTask { .. }) => None,
+        Err(err) => return Err(err),
+    };
+    let year = year.ok_or(AocError::MissingPuzzleYear { day })?;
+    let mut parts = part.unwrap_or(puzzle::PARTS_PER_DAY);
+    if let (None, Some(task)) = (part, task) {
+        for phase in 1..=task.phases() {
+            if !task.phase_is_solved(phase)? {
+                parts = phase;
+                break;
+            }
+        }
+    }
+
+    let client = AocClient::load()?;
+    println!("{}", puzzle::describe(&client, year, day, parts)?);
+    Ok(())
+}
+
+// The year of the registered task of the day, tasks of several years need the year to be passed
+fn registered_year(tasks: &[SharedAocTask], day: u8) -> Result<Option<u16>, AocError> {
+    match crate::find_task(tasks, None, day) {
+        Ok(task) => Ok(task.year()),
+        Err(AocError::MissingTask { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(feature = "browser")]
+fn open_puzzle(
+    tasks: &[SharedAocTask],
+    day: Option<u8>,
+    year: Option<u16>,
+    part: Option<usize>,
+) -> Result<(), AocError> {
+    let (year, day) = match (day, year) {
+        (Some(day), Some(year)) => (year, day),
+        (Some(day), None) => {
+            let year = registered_year(tasks, day)?.ok_or(AocError::MissingPuzzleYear { day })?;
+            (year, day)
+        }
+        (None, _) => unlock::latest_unlock(chrono::Utc::now()),
+    };
+    let url = match part {
+        Some(part) => browser::part_url(year, day, part),
+        None => browser::puzzle_url(year, day),
+    };
+    browser::open_url(&url)
+}
+
+fn session(command: SessionCommand) -> Result<(), AocError> {
+    match command {
+        SessionCommand::Set {
+            token,
+            #[cfg(feature = "keyring")]
+            keyring,
+        } => {
+            #[cfg(feature = "keyring")]
+            if keyring {
+                let session = Session::new(&token, SessionSource::Keyring)
+                    .ok_or_else(session::missing_session_error)?;
+                session.save_to_keyring()?;
+                println!(
+                    "{} Stored the session in the OS keyring",
+                    CHECKMARK.success()
+                );
+                return Ok(());
+            }
+
+            let session = Session::new(&token, SessionSource::ConfigFile)
+                .ok_or_else(session::missing_session_error)?;
+            let path = session.save_to_config_file()?;
+            println!(
+                "{} Stored the session in {}",
+                CHECKMARK.success(),
+                path.to_string_lossy().bold()
+            );
+        }
+        SessionCommand::Check => {
+            let client = AocClient::load()?;
+            client.validate_session()?;
+            println!(
+                "{} The session from {} is valid",
+                CHECKMARK.success(),
+                client.session().source()
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{error::AocError, session::Session};
+
+pub const AOC_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+const USER_AGENT: &str = concat!(
+    "aoc-framework/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/Michal-Miko/aoc-framework)"
+);
+
+// Shared by every client, so creating more clients does not bypass the rate limit
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+fn input_path(year: u16, day: u8) -> String {
+    format!("/{year}/day/{day}/input")
+}
+
+pub fn default_cache_directory() -> Option<PathBuf> {
+    dirs::cache_dir().map(|directory| directory.join("aoc-framework"))
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    session: Session,
+    user_agent: String,
+    cache_directory: Option<PathBuf>,
+    request_interval: Duration,
+}
+
+impl AocClient {
+    pub fn new(session: Session) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().redirects(0).build(),
+            session,
+            user_agent: USER_AGENT.to_owned(),
+            cache_directory: default_cache_directory(),
+            request_interval: DEFAULT_REQUEST_INTERVAL,
+        }
+    }
+
+    pub fn load() -> Result<Self, AocError> {
+        Ok(Self::new(Session::load()?))
+    }
+
+    // The automation guidelines ask for a way to contact the author of the requests
+    pub fn contact(mut self, contact: &str) -> Self {
+        self.user_agent = format!("{USER_AGENT} contact: {contact}");
+        self
+    }
+
+    pub fn cache_directory(mut self, cache_directory: Option<PathBuf>) -> Self {
+        self.cache_directory = cache_directory;
+        self
+    }
+
+    pub fn request_interval(mut self, request_interval: Duration) -> Self {
+        self.request_interval = request_interval.max(MIN_REQUEST_INTERVAL);
+        self
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    fn throttle(&self) {
+        let mut last_request = LAST_REQUEST.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(elapsed) = last_request.map(|last_request| last_request.elapsed()) {
+            if elapsed < self.request_interval {
+                thread::sleep(self.request_interval - elapsed);
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    fn request_error(&self, url: String, err: ureq::Error) -> AocError {
+        match err {
+            // Requests with an invalid session are rejected or redirected to the login page
+            ureq::Error::Status(400 | 401, _) => self.session.expired_error(),
+            err => AocError::RequestError {
+                url,
+                source: Box::new(err),
+            },
+        }
+    }
+
+    fn read_response(&self, url: String, response: ureq::Response) -> Result<String, AocError> {
+        if response.status() != 200 {
+            return Err(self.session.expired_error());
+        }
+        response.into_string().map_err(|err| AocError::IOReadError {
+            path: url,
+            source: err,
+        })
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, AocError> {
+        let url = format!("{AOC_URL}{path}");
+        self.throttle();
+        let response = self
+            .agent
+            .get(&url)
+            .set("User-Agent", &self.user_agent)
+            .set("Cookie", &self.session.cookie())
+            .call()
+            .map_err(|err| self.request_error(url.clone(), err))?;
+        self.read_response(url, response)
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, AocError> {
+        let url = format!("{AOC_URL}{path}");
+        self.throttle();
+        let response = self
+            .agent
+            .post(&url)
+            .set("User-Agent", &self.user_agent)
+            .set("Cookie", &self.session.cookie())
+            .send_form(form)
+            .map_err(|err| self.request_error(url.clone(), err))?;
+        self.read_response(url, response)
+    }
+
+    pub fn cache_path(&self, path: &str) -> Option<PathBuf> {
+        self.cache_directory
+            .as_ref()
+            .map(|directory| directory.join(path.trim_start_matches('/')))
+    }
+
+    pub fn read_cache(&self, path: &str) -> Option<String> {
+        self.cache_path(path)
+            .and_then(|cache_path| fs::read_to_string(cache_path).ok())
+    }
+
+    pub fn write_cache(&self, path: &str, body: &str) -> Result<(), AocError> {
+        let Some(cache_path) = self.cache_path(path) else {
+            return Ok(());
+        };
+        let write_error = |err| AocError::IOWriteError {
+            path: cache_path.to_string_lossy().to_string(),
+            source: err,
+        };
+        if let Some(directory) = cache_path.parent() {
+            fs::create_dir_all(directory).map_err(write_error)?;
+        }
+        fs::write(&cache_path, body).map_err(write_error)
+    }
+
+    // Only use for pages that do not change, every other request has to go through `get`
+    pub fn get_cached(&self, path: &str) -> Result<String, AocError> {
+        if let Some(cached) = self.read_cache(path) {
+            return Ok(cached);
+        }
+
+        let body = self.get(path)?;
+        self.write_cache(path, &body)?;
+        Ok(body)
+    }
+
+    // Every account gets different inputs, so they are cached per session
+    fn input_cache_key(&self, year: u16, day: u8) -> String {
+        format!("/inputs/{}{}", self.session.id(), input_path(year, day))
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, AocError> {
+        let cache_key = self.input_cache_key(year, day);
+        if let Some(cached) = self.read_cache(&cache_key) {
+            return Ok(cached);
+        }
+
+        let input = self.get(&input_path(year, day))?;
+        self.write_cache(&cache_key, &input)?;
+        Ok(input)
+    }
+
+    pub fn input_cache_path(&self, year: u16, day: u8) -> Option<PathBuf> {
+        self.cache_path(&self.input_cache_key(year, day))
+    }
+
+    // The settings page redirects to the login page when the session is not valid
+    pub fn validate_session(&self) -> Result<(), AocError> {
+        self.get("/settings").map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{session::SessionSource, testing::TempDir};
+
+    #[test]
+    fn cached_input_skips_request() {
+        let cache_directory = TempDir::new("cache");
+        let session = Session::new("invalid", SessionSource::Environment).unwrap();
+        let client = AocClient::new(session).cache_directory(Some(cache_directory.path().into()));
+
+        let cache_path = client.input_cache_path(2023, 1).unwrap();
+        assert_eq!(
+            cache_path,
+            cache_directory.join("inputs/f1234d75178d892a/2023/day/1/input")
+        );
+        fs::create_dir_all(cache_path.parent().unwrap()).unwrap();
+        fs::write(&cache_path, "1abc2\n").unwrap();
+
+        assert_eq!(client.input(2023, 1).unwrap(), "1abc2\n");
+        let other_session = Session::new("other", SessionSource::Environment).unwrap();
+        let other_client = AocClient::new(other_session).cache_directory(client.cache_directory);
+        assert_ne!(other_client.input_cache_path(2023, 1), Some(cache_path));
+    }
+}
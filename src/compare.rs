@@ -0,0 +1,137 @@
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::{ocr, AocSolution};
+
+const WILDCARD: &str = "*";
+const REGEX_PREFIX: &str = "~re:";
+
+// Decides whether the output of an example matches its expected output
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Comparator {
+    Exact,
+    #[default]
+    Trimmed,
+    CaseInsensitive,
+    Unordered,
+    Numeric {
+        tolerance: f64,
+    },
+    Custom(fn(&AocSolution, &AocSolution) -> bool),
+}
+
+// Expected lines like `3.14159 ± 1e-6` accept any number within the tolerance
+fn parse_tolerance(expected: &str) -> Option<(f64, f64)> {
+    let (value, tolerance) = expected
+        .split_once('±')
+        .or_else(|| expected.split_once("+-"))?;
+    Some((value.trim().parse().ok()?, tolerance.trim().parse().ok()?))
+}
+
+// Expected lines can also be a `*` wildcard or a `~re:` regex, for answers that are only
+// known by their shape
+fn pattern_matches(output: &str, expected: &str) -> Option<bool> {
+    if expected.trim() == WILDCARD {
+        return Some(true);
+    }
+    if let Some(pattern) = expected.trim().strip_prefix(REGEX_PREFIX) {
+        return Some(Regex::new(pattern).is_ok_and(|regex| regex.is_match(output.trim())));
+    }
+    let (value, tolerance) = parse_tolerance(expected)?;
+    Some(within_tolerance(output.trim(), value, tolerance))
+}
+
+fn lines_match(
+    output: &AocSolution,
+    expected: &AocSolution,
+    line_matches: impl Fn(&str, &str) -> bool,
+) -> bool {
+    output.len() == expected.len()
+        && output
+            .iter()
+            .zip(expected)
+            .all(|(output_line, expected_line)| {
+                pattern_matches(output_line, expected_line)
+                    .unwrap_or_else(|| line_matches(output_line, expected_line))
+            })
+}
+
+fn within_tolerance(output: &str, expected: f64, tolerance: f64) -> bool {
+    output
+        .parse::<f64>()
+        .is_ok_and(|output| (output - expected).abs() <= tolerance)
+}
+
+// Lines that are not numbers still have to match exactly
+fn numbers_match(output: &str, expected: &str, tolerance: f64) -> bool {
+    match expected.parse::<f64>() {
+        Ok(expected) => within_tolerance(output, expected, tolerance),
+        Err(_) => output == expected,
+    }
+}
+
+fn sorted_lines(solution: &AocSolution) -> Vec<&str> {
+    solution.iter().map(|line| line.trim()).sorted().collect()
+}
+
+// Letters drawn by the solution can be compared against the letters written out
+fn letters_match(output: &AocSolution, expected: &AocSolution) -> bool {
+    match (ocr::recognize(output), expected.as_slice()) {
+        (Some(letters), [expected]) => letters == expected.trim(),
+        _ => false,
+    }
+}
+
+impl Comparator {
+    pub fn matches(&self, output: &AocSolution, expected: &AocSolution) -> bool {
+        if !matches!(self, Comparator::Exact | Comparator::Custom(_))
+            && letters_match(output, expected)
+        {
+            return true;
+        }
+        match self {
+            Comparator::Exact => output == expected,
+            Comparator::Trimmed => lines_match(output, expected, |output, expected| {
+                output.trim() == expected.trim()
+            }),
+            Comparator::CaseInsensitive => lines_match(output, expected, |output, expected| {
+                output.trim().to_lowercase() == expected.trim().to_lowercase()
+            }),
+            Comparator::Unordered => sorted_lines(output) == sorted_lines(expected),
+            Comparator::Numeric { tolerance } => {
+                lines_match(output, expected, |output, expected| {
+                    numbers_match(output.trim(), expected.trim(), *tolerance)
+                })
+            }
+            Comparator::Custom(matches) => matches(output, expected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparators_match_outputs() {
+        let solution = |lines: &[&str]| lines.iter().map(|line| line.to_string()).collect_vec();
+        let output = solution(&["b ", "A", "3.1415"]);
+
+        assert!(!Comparator::Exact.matches(&output, &solution(&["b", "A", "3.1415"])));
+        assert!(Comparator::Trimmed.matches(&output, &solution(&["b", "A", "3.1415"])));
+        assert!(!Comparator::Trimmed.matches(&output, &solution(&["b", "a", "3.1415"])));
+        assert!(Comparator::CaseInsensitive.matches(&output, &solution(&["B", "a", "3.1415"])));
+        assert!(Comparator::Unordered.matches(&output, &solution(&["3.1415", "A", "b"])));
+        assert!(!Comparator::Unordered.matches(&output, &solution(&["3.1415", "A"])));
+
+        let numeric = Comparator::Numeric { tolerance: 1e-3 };
+        assert!(numeric.matches(&output, &solution(&["b", "A", "3.141"])));
+        assert!(!numeric.matches(&output, &solution(&["b", "A", "3.14"])));
+        assert!(!numeric.matches(&output, &solution(&["c", "A", "3.141"])));
+        assert!(Comparator::Trimmed.matches(&output, &solution(&["b", "A", "3.14 ± 0.01"])));
+        assert!(Comparator::Trimmed.matches(&output, &solution(&["b", "A", "3.14 +- 2e-3"])));
+        assert!(!Comparator::Trimmed.matches(&output, &solution(&["b", "A", "3.14 ± 1e-3"])));
+        assert!(Comparator::Trimmed.matches(&output, &solution(&["*", "A", r"~re:^\d\.\d{4}$"])));
+        assert!(!Comparator::Trimmed.matches(&output, &solution(&["*", "A", r"~re:^\d{5}$"])));
+    }
+}
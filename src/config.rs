@@ -0,0 +1,160 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::AocError,
+    prompt::ConfirmPolicy,
+    reporter::{Webhook, WebhookKind},
+};
+
+pub const CONFIG_ENV_VAR: &str = "AOC_CONFIG_FILE";
+const DEFAULT_CONFIG_FILE: &str = "aoc.toml";
+
+// Optional `aoc.toml` project configuration, e.g.
+// ```toml
+// [[webhooks]]
+// url = "https://discord.com/api/webhooks/..."
+// kind = "discord"
+// events = ["solved"]
+// ```
+// The answers can be confirmed with `confirm = "ask" | "accept" | "skip"`, and
+// `default_answer = true` picks the answer of the prompts on enter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub webhooks: Vec<Webhook>,
+    pub confirm: Option<ConfirmPolicy>,
+    pub default_answer: Option<bool>,
+}
+
+// The configuration is read from the project directory unless the environment variable moves it
+pub fn config_path() -> PathBuf {
+    env::var_os(CONFIG_ENV_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE))
+}
+
+fn parse_webhook(value: &toml::Value) -> Result<Webhook, String> {
+    let table = value
+        .as_table()
+        .ok_or("`webhooks` must be a list of tables")?;
+    let mut url = None;
+    let mut webhook = Webhook::new("");
+    for (key, value) in table {
+        match (key.as_str(), value) {
+            ("url", toml::Value::String(value)) => url = Some(value.clone()),
+            ("kind", toml::Value::String(kind)) => {
+                webhook.kind = match kind.as_str() {
+                    "discord" => WebhookKind::Discord,
+                    "slack" => WebhookKind::Slack,
+                    "generic" => WebhookKind::Generic,
+                    _ => return Err(format!("unknown webhook kind `{kind}`")),
+                };
+            }
+            ("events", toml::Value::Array(events)) => {
+                webhook.on_solved = false;
+                webhook.on_failed = false;
+                for event in events {
+                    match event.as_str() {
+                        Some("solved") => webhook.on_solved = true,
+                        Some("failed") => webhook.on_failed = true,
+                        _ => return Err("`events` may only contain `solved` and `failed`".into()),
+                    }
+                }
+            }
+            ("url" | "kind" | "events", _) => {
+                return Err(format!("`{key}` has an unexpected type"));
+            }
+            _ => return Err(format!("unknown webhook key `{key}`")),
+        }
+    }
+    webhook.url = url.ok_or("a webhook is missing its `url`")?;
+    Ok(webhook)
+}
+
+impl Config {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let table = content
+            .parse::<toml::Table>()
+            .map_err(|err| err.to_string().trim().to_owned())?;
+        let mut config = Self::default();
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("webhooks", toml::Value::Array(webhooks)) => {
+                    config.webhooks = webhooks
+                        .iter()
+                        .map(parse_webhook)
+                        .collect::<Result<_, _>>()?;
+                }
+                ("webhooks", _) => return Err("`webhooks` must be a list of tables".into()),
+                ("confirm", toml::Value::String(policy)) => {
+                    config.confirm = Some(
+                        ConfirmPolicy::parse(policy)
+                            .ok_or_else(|| format!("unknown confirm policy `{policy}`"))?,
+                    );
+                }
+                ("default_answer", toml::Value::Boolean(default)) => {
+                    config.default_answer = Some(*default);
+                }
+                ("confirm" | "default_answer", _) => {
+                    return Err(format!("`{key}` has an unexpected type"));
+                }
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        }
+        Ok(config)
+    }
+
+    // Projects without a configuration file have the default configuration
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|err| AocError::IOReadError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        Self::parse(&content).map_err(|message| AocError::InvalidConfig {
+            path: path.to_string_lossy().to_string(),
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_webhooks() {
+        let config = Config::parse(
+            r#"
+            [[webhooks]]
+            url = "https://discord.com/api/webhooks/1"
+            kind = "discord"
+            events = ["solved"]
+
+            [[webhooks]]
+            url = "https://example.com/hook"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.webhooks.len(), 2);
+        assert_eq!(config.webhooks[0].kind, WebhookKind::Discord);
+        assert!(config.webhooks[0].on_solved && !config.webhooks[0].on_failed);
+        assert_eq!(config.webhooks[1], Webhook::new("https://example.com/hook"));
+
+        assert!(Config::parse("[[webhooks]]\nkind = \"slack\"").is_err());
+        assert!(Config::parse("unknown = 1").is_err());
+    }
+
+    #[test]
+    fn parses_prompt_settings() {
+        let config = Config::parse("confirm = \"skip\"\ndefault_answer = false").unwrap();
+        assert_eq!(config.confirm, Some(ConfirmPolicy::Skip));
+        assert_eq!(config.default_answer, Some(false));
+        assert!(Config::parse("confirm = \"always\"").is_err());
+        assert!(Config::parse("default_answer = \"yes\"").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    fmt::Display,
+    mem,
+    path::{Path, PathBuf},
+    sync::{mpsc::Sender, Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cancel::{CancellationToken, Cancelled},
+    example::ExampleArgs,
+    source::InputSource,
+    ProgressHandle,
+};
+
+// Everything a solution gets to know about the input it is solving, besides the input itself
+#[derive(Debug, Clone)]
+pub struct AocContext {
+    phase: usize,
+    is_example: bool,
+    input_path: Option<PathBuf>,
+    args: ExampleArgs,
+    deadline: Option<Instant>,
+    progress: ProgressHandle,
+    variant: Option<String>,
+    input_source: Option<Arc<dyn InputSource>>,
+    cancellation: CancellationToken,
+    streamed: Arc<Mutex<Vec<String>>>,
+    stream: Option<Sender<String>>,
+}
+
+impl AocContext {
+    // The progress handle is the one of the phase solved on the current thread
+    pub fn new(phase: usize) -> Self {
+        Self {
+            phase,
+            is_example: false,
+            input_path: None,
+            args: ExampleArgs::default(),
+            deadline: None,
+            progress: ProgressHandle::current(),
+            variant: None,
+            input_source: None,
+            cancellation: CancellationToken::default(),
+            streamed: Arc::default(),
+            stream: None,
+        }
+    }
+
+    pub fn example(phase: usize, args: ExampleArgs) -> Self {
+        Self {
+            is_example: true,
+            args,
+            ..Self::new(phase)
+        }
+    }
+
+    pub fn with_input_path(mut self, input_path: impl Into<PathBuf>) -> Self {
+        self.input_path = Some(input_path.into());
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn with_variant(mut self, variant: Option<&str>) -> Self {
+        self.variant = variant.map(str::to_owned);
+        self
+    }
+
+    // Solves a different input than the one of the task
+    pub fn with_input_source(mut self, input_source: Option<Arc<dyn InputSource>>) -> Self {
+        self.input_source = input_source;
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    // Lines passed to `emit` are also sent to the runner as they are emitted
+    pub(crate) fn with_stream(mut self, stream: Sender<String>) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    pub fn is_example(&self) -> bool {
+        self.is_example
+    }
+
+    // Inline examples have no input file
+    pub fn input_path(&self) -> Option<&Path> {
+        self.input_path.as_deref()
+    }
+
+    pub fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        self.input_source.clone()
+    }
+
+    // Example specific parameters, which are always empty for the real input
+    pub fn args(&self) -> &ExampleArgs {
+        &self.args
+    }
+
+    // The phase is reported as timed out when a solution doesn't finish before its deadline
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    // One of the alternate implementations listed by `AocTask::variants`, `None` is the default
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+
+    // Set when the run is interrupted or the deadline has passed, slow solutions should poll it
+    // and return early
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    // For returning early with `?`, e.g. `context.check_cancelled()?;`
+    pub fn check_cancelled(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Streams a line of the answer before the solution returns, e.g. the answer of the first
+    // phase while the second one is still being computed. The emitted lines are the output of
+    // solutions that return an empty one.
+    pub fn emit(&self, line: impl Display) {
+        let line = line.to_string();
+        if let Some(stream) = &self.stream {
+            let _ = stream.send(line.clone());
+        }
+        self.streamed
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(line);
+    }
+
+    pub(crate) fn finish_output(&self, output: Vec<String>) -> Vec<String> {
+        let mut streamed = self.streamed.lock().unwrap_or_else(|err| err.into_inner());
+        if output.is_empty() {
+            mem::take(&mut streamed)
+        } else {
+            output
+        }
+    }
+
+    pub fn progress(&self) -> &ProgressHandle {
+        &self.progress
+    }
+
+    // Logged messages are captured with the rest of the output of the solution
+    pub fn log(&self, message: impl Display) {
+        let source = if self.is_example { "example" } else { "input" };
+        eprintln!("[phase {} {source}] {message}", self.phase);
+    }
+}
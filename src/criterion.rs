@@ -0,0 +1,77 @@
+use std::{error::Error, path::Path};
+
+pub use ::criterion::{criterion_group, criterion_main, Criterion};
+
+use crate::{context::AocContext, input, AocTask, SharedAocTask, TypedAocTask, TypedTask};
+
+// Registers a benchmark group per task, with a benchmark of reading the input and one of solving
+// each phase. Typed tasks cache their parsed input, so their phase benchmarks only measure the
+// solving, see `bench_typed_task` for benchmarking the parsing.
+pub fn bench_tasks(criterion: &mut Criterion, tasks: &[SharedAocTask]) {
+    for task in tasks {
+        let input_path = task.input_path();
+        if !input_path.is_file() {
+            eprintln!("Skipping {}, its input is missing", task.name());
+            continue;
+        }
+
+        let mut group = criterion.benchmark_group(task.name());
+        group.bench_function("input", |bencher| {
+            bencher.iter(|| task.get_file_output(&input_path))
+        });
+        for phase in 1..=task.phases() {
+            if let Err(err) = task.solve(phase) {
+                eprintln!("Skipping {} phase {phase}: {err}", task.name());
+                continue;
+            }
+            group.bench_function(format!("phase {phase}"), |bencher| {
+                bencher.iter(|| task.solve(phase))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn read_input(input_path: &Path) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let input = input::map_input(input_path)?;
+    Ok(std::str::from_utf8(&input)?.to_owned())
+}
+
+// Benchmarks the parsing of the input and the solving of each phase of a typed task separately
+pub fn bench_typed_task<T: TypedAocTask>(criterion: &mut Criterion, task: &TypedTask<T>) {
+    let input_path = task.input_path();
+    let input = read_input(&input_path).and_then(|input| Ok((task.inner().parse(&input)?, input)));
+    let (parsed, input) = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Skipping {}: {err}", task.name());
+            return;
+        }
+    };
+
+    let mut group = criterion.benchmark_group(task.name());
+    group.bench_function("parse", |bencher| {
+        bencher.iter(|| task.inner().parse(&input))
+    });
+    for phase in 1..=task.phases() {
+        let context = AocContext::new(phase).with_input_path(&input_path);
+        group.bench_function(format!("phase {phase}"), |bencher| {
+            bencher.iter(|| task.inner().solve_parsed(&parsed, &context))
+        });
+    }
+    group.finish();
+}
+
+// Generates the `main` of a Criterion benchmark target from an expression evaluating to the
+// shared tasks, e.g. `aoc_framework::criterion_main_for_tasks!(my_solutions::tasks());`
+#[macro_export]
+macro_rules! criterion_main_for_tasks {
+    ($tasks:expr) => {
+        fn aoc_framework_benches(criterion: &mut $crate::criterion::Criterion) {
+            let tasks: ::std::vec::Vec<$crate::SharedAocTask> = $tasks;
+            $crate::criterion::bench_tasks(criterion, &tasks);
+        }
+        $crate::criterion::criterion_group!(benches, aoc_framework_benches);
+        $crate::criterion::criterion_main!(benches);
+    };
+}
@@ -0,0 +1,98 @@
+use std::{collections::HashMap, hash::Hash};
+
+// States repeat every `length` iterations once `start` iterations have been done
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The earliest iteration with the same state as the given one, e.g. iteration 1000000000
+    pub fn equivalent(&self, iteration: usize) -> usize {
+        if iteration < self.start {
+            iteration
+        } else {
+            self.start + (iteration - self.start) % self.length
+        }
+    }
+}
+
+// Brent's algorithm, which only compares states and keeps two of them around, for states that
+// cannot be hashed or are too large to remember. The step never returning to an earlier state
+// makes it run forever.
+pub fn find_cycle<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+// The state after the given number of iterations, simulating only until the first repeated
+// state and extrapolating from its cycle
+pub fn state_after<S: Clone + Eq + Hash>(
+    initial: S,
+    iterations: usize,
+    mut step: impl FnMut(&S) -> S,
+) -> S {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+    for iteration in 1..=iterations {
+        let state = step(&states[iteration - 1]);
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: iteration - start,
+            };
+            return states.swap_remove(cycle.equivalent(iterations));
+        }
+        seen.insert(state.clone(), iteration);
+        states.push(state);
+    }
+    states.swap_remove(iterations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_are_extrapolated() {
+        // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+        let collatz = |&n: &u64| if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        let cycle = find_cycle(3, collatz);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 5,
+                length: 3
+            }
+        );
+        assert_eq!(cycle.equivalent(4), 4);
+        assert_eq!(cycle.equivalent(1_000_000_000), 7);
+        assert_eq!(state_after(3, 1_000_000_000, collatz), 1);
+        assert_eq!(state_after(3, 3, collatz), 16);
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt::Write as _;
+
+use crate::style::Stylize;
+
+const CONTEXT_LINES: usize = 3;
+// Larger differences are shown as a single replacement instead of searching for common lines
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+impl<'a> DiffLine<'a> {
+    pub fn text(&self) -> &'a str {
+        match *self {
+            DiffLine::Same(text) | DiffLine::Removed(text) | DiffLine::Added(text) => text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk<'a> {
+    pub expected_start: usize,
+    pub expected_len: usize,
+    pub output_start: usize,
+    pub output_len: usize,
+    pub lines: Vec<DiffLine<'a>>,
+}
+
+fn same_line(expected: &str, output: &str) -> bool {
+    expected.trim() == output.trim()
+}
+
+// Line diff based on the longest common subsequence, from the expected output to the output
+pub fn diff_lines<'a>(expected: &'a [String], output: &'a [String]) -> Vec<DiffLine<'a>> {
+    let prefix = expected
+        .iter()
+        .zip(output)
+        .take_while(|(expected, output)| same_line(expected, output))
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(output[prefix..].iter().rev())
+        .take_while(|(expected, output)| same_line(expected, output))
+        .count();
+    let expected_middle = &expected[prefix..expected.len() - suffix];
+    let output_middle = &output[prefix..output.len() - suffix];
+
+    let mut diff = output[..prefix]
+        .iter()
+        .map(|line| DiffLine::Same(line))
+        .collect::<Vec<_>>();
+    if expected_middle.len() * output_middle.len() > MAX_DIFF_CELLS {
+        diff.extend(expected_middle.iter().map(|line| DiffLine::Removed(line)));
+        diff.extend(output_middle.iter().map(|line| DiffLine::Added(line)));
+    } else {
+        diff.extend(lcs_diff(expected_middle, output_middle));
+    }
+    diff.extend(
+        output[output.len() - suffix..]
+            .iter()
+            .map(|line| DiffLine::Same(line)),
+    );
+    diff
+}
+
+fn lcs_diff<'a>(expected: &'a [String], output: &'a [String]) -> Vec<DiffLine<'a>> {
+    let columns = output.len() + 1;
+    let mut lengths = vec![0usize; (expected.len() + 1) * columns];
+    for i in (0..expected.len()).rev() {
+        for j in (0..output.len()).rev() {
+            lengths[i * columns + j] = if same_line(&expected[i], &output[j]) {
+                lengths[(i + 1) * columns + j + 1] + 1
+            } else {
+                lengths[(i + 1) * columns + j].max(lengths[i * columns + j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < output.len() {
+        if same_line(&expected[i], &output[j]) {
+            diff.push(DiffLine::Same(&output[j]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * columns + j] >= lengths[i * columns + j + 1] {
+            diff.push(DiffLine::Removed(&expected[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(&output[j]));
+            j += 1;
+        }
+    }
+    diff.extend(expected[i..].iter().map(|line| DiffLine::Removed(line)));
+    diff.extend(output[j..].iter().map(|line| DiffLine::Added(line)));
+    diff
+}
+
+// Groups the changes with the unchanged lines around them
+pub fn hunks<'a>(diff: &[DiffLine<'a>], context: usize) -> Vec<Hunk<'a>> {
+    let changes = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for index in changes {
+        let start = index.saturating_sub(context);
+        let end = (index + context + 1).min(diff.len());
+        match ranges.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            let count = |lines: &[DiffLine], counted: fn(&DiffLine) -> bool| {
+                lines.iter().filter(|line| counted(line)).count()
+            };
+            let in_expected = |line: &DiffLine| !matches!(line, DiffLine::Added(_));
+            let in_output = |line: &DiffLine| !matches!(line, DiffLine::Removed(_));
+            Hunk {
+                expected_start: count(&diff[..start], in_expected) + 1,
+                expected_len: count(&diff[start..end], in_expected),
+                output_start: count(&diff[..start], in_output) + 1,
+                output_len: count(&diff[start..end], in_output),
+                lines: diff[start..end].to_vec(),
+            }
+        })
+        .collect()
+}
+
+fn hunk_header(hunk: &Hunk) -> String {
+    format!(
+        "@@ -{},{} +{},{} @@",
+        hunk.expected_start, hunk.expected_len, hunk.output_start, hunk.output_len
+    )
+}
+
+pub fn render_plain(expected: &[String], output: &[String]) -> String {
+    let mut out = String::new();
+    for hunk in hunks(&diff_lines(expected, output), CONTEXT_LINES) {
+        let _ = writeln!(out, "{}", hunk_header(&hunk));
+        for line in hunk.lines {
+            let _ = match line {
+                DiffLine::Same(line) => writeln!(out, "  {line}"),
+                DiffLine::Removed(line) => writeln!(out, "- {line}"),
+                DiffLine::Added(line) => writeln!(out, "+ {line}"),
+            };
+        }
+    }
+    out
+}
+
+// Splits a changed line into the parts it shares with its replacement and the part that changed
+fn split_change<'a>(line: &'a str, other: &str) -> (&'a str, &'a str, &'a str) {
+    let prefix = line
+        .char_indices()
+        .zip(other.chars())
+        .take_while(|((_, a), b)| a == b)
+        .last()
+        .map_or(0, |((index, c), _)| index + c.len_utf8());
+    let suffix = line[prefix..]
+        .chars()
+        .rev()
+        .zip(other[prefix.min(other.len())..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum::<usize>();
+    let middle_end = line.len() - suffix;
+    (
+        &line[..prefix],
+        &line[prefix..middle_end],
+        &line[middle_end..],
+    )
+}
+
+fn render_changed(marker: &str, line: &str, other: Option<&str>, removed: bool) -> String {
+    let (prefix, middle, suffix) = match other {
+        Some(other) => split_change(line, other),
+        None => ("", line, ""),
+    };
+    let color = |text: &str| {
+        if removed {
+            text.to_owned().failure()
+        } else {
+            text.to_owned().success()
+        }
+    };
+    let highlight = if other.is_some() {
+        color(middle).reverse()
+    } else {
+        color(middle)
+    };
+    format!(
+        "{}{}{}{}",
+        color(marker),
+        color(prefix),
+        highlight,
+        color(suffix)
+    )
+}
+
+// Removed lines directly followed by as many added lines are highlighted character by character
+pub fn render(expected: &[String], output: &[String]) -> String {
+    let mut out = String::new();
+    for hunk in hunks(&diff_lines(expected, output), CONTEXT_LINES) {
+        let _ = writeln!(out, "{}", hunk_header(&hunk).accent());
+        let lines = &hunk.lines;
+        let mut index = 0;
+        while index < lines.len() {
+            let removed = lines[index..]
+                .iter()
+                .take_while(|line| matches!(line, DiffLine::Removed(_)))
+                .count();
+            let added = lines[index + removed..]
+                .iter()
+                .take_while(|line| matches!(line, DiffLine::Added(_)))
+                .count();
+            if removed == 0 && added == 0 {
+                let _ = writeln!(out, "  {}", lines[index].text());
+                index += 1;
+                continue;
+            }
+
+            let paired = removed == added;
+            for offset in 0..removed {
+                let other = paired.then(|| lines[index + removed + offset].text());
+                let line = lines[index + offset].text();
+                let _ = writeln!(out, "{}", render_changed("- ", line, other, true));
+            }
+            for offset in 0..added {
+                let other = paired.then(|| lines[index + offset].text());
+                let line = lines[index + removed + offset].text();
+                let _ = writeln!(out, "{}", render_changed("+ ", line, other, false));
+            }
+            index += removed + added;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_of_shifted_lines() {
+        let lines = |text: &str| text.split(' ').map(str::to_owned).collect::<Vec<_>>();
+        let expected = lines("1 2 3 4 5 6 7 8 9 10");
+        let output = lines("0 1 2 3 4 5 6 7 8 9 11");
+
+        let diff = render_plain(&expected, &output);
+        assert_eq!(
+            diff,
+            "@@ -1,3 +1,4 @@\n+ 0\n  1\n  2\n  3\n@@ -7,4 +8,4 @@\n  7\n  8\n  9\n- 10\n+ 11\n"
+        );
+        assert_eq!(split_change("abcde", "abXde"), ("ab", "c", "de"));
+        assert_eq!(split_change("abc", "abc!"), ("abc", "", ""));
+    }
+}
@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+
+use crate::{
+    context::AocContext, diff, error::AocError, input, style::Stylize, AocSolution, SharedAocTask,
+    CHECKMARK, CROSS,
+};
+
+// One of the two implementations being compared, a task and one of its variants
+#[derive(Clone, Copy)]
+pub struct Implementation<'t> {
+    pub task: &'t SharedAocTask,
+    pub variant: Option<&'t str>,
+}
+
+impl<'t> Implementation<'t> {
+    // Fails when the variant is not one of the variants the task lists for the phase
+    pub fn new(
+        task: &'t SharedAocTask,
+        variant: Option<&'t str>,
+        phase: usize,
+    ) -> Result<Self, AocError> {
+        match variant {
+            Some(variant) if !task.variants(phase).contains(&variant) => {
+                Err(AocError::UnknownVariant {
+                    task_name: task.name(),
+                    variant: variant.to_owned(),
+                })
+            }
+            _ => Ok(Self { task, variant }),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("{} ({variant})", self.task.name()),
+            None => self.task.name(),
+        }
+    }
+
+    fn solve(&self, input: &DiffInput, phase: usize) -> Result<AocSolution, AocError> {
+        let context = AocContext::new(phase).with_variant(self.variant);
+        let output = self
+            .task
+            .solution_bytes(input.contents.as_bytes(), &context)
+            .map_err(|err| AocError::SolutionExecutionError {
+                input_path: input.label.clone(),
+                source: err,
+            })?;
+        Ok(context.finish_output(output))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffInput {
+    pub label: String,
+    pub contents: String,
+}
+
+// The inputs the task generates at the scales from 1 up to `scales`, smallest first so the
+// first divergence is found on the simplest input
+pub fn generated_inputs(task: &SharedAocTask, scales: usize) -> Vec<DiffInput> {
+    (1..=scales)
+        .map(|scale| DiffInput {
+            label: format!("generated input at scale {scale}"),
+            contents: task.generate_input(scale),
+        })
+        .take_while(|input| !input.contents.is_empty())
+        .collect()
+}
+
+pub fn file_inputs(paths: &[PathBuf]) -> Result<Vec<DiffInput>, AocError> {
+    paths
+        .iter()
+        .map(|path| {
+            let contents = input::map_input(path)?;
+            Ok(DiffInput {
+                label: path.to_string_lossy().to_string(),
+                contents: String::from_utf8_lossy(&contents).into_owned(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub input: DiffInput,
+    pub left: AocSolution,
+    pub right: AocSolution,
+}
+
+// Solves the inputs with both implementations until their outputs differ, the outputs are
+// compared with the comparator of the left one
+pub fn find_divergence(
+    left: &Implementation,
+    right: &Implementation,
+    phase: usize,
+    inputs: impl IntoIterator<Item = DiffInput>,
+) -> Result<Option<Divergence>, AocError> {
+    let comparator = left.task.comparator(phase);
+    for input in inputs {
+        let left_output = left.solve(&input, phase)?;
+        let right_output = right.solve(&input, phase)?;
+        if !comparator.matches(&right_output, &left_output) {
+            return Ok(Some(Divergence {
+                input,
+                left: left_output,
+                right: right_output,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+// Returns whether the implementations agreed on all of the inputs
+pub fn run_differential(
+    left: &Implementation,
+    right: &Implementation,
+    phase: usize,
+    inputs: Vec<DiffInput>,
+) -> Result<bool, AocError> {
+    let count = inputs.len();
+    let Some(divergence) = find_divergence(left, right, phase, inputs)? else {
+        println!(
+            "{} {} and {} agree on {} inputs",
+            CHECKMARK.success(),
+            left.label().bold(),
+            right.label().bold(),
+            count.to_string().highlight()
+        );
+        return Ok(true);
+    };
+    println!(
+        "{} {} and {} differ on the {}:",
+        CROSS.failure(),
+        left.label().bold(),
+        right.label().bold(),
+        divergence.input.label.clone().highlight()
+    );
+    println!();
+    print!("{}", divergence.input.contents);
+    if !divergence.input.contents.ends_with('\n') {
+        println!();
+    }
+    println!();
+    print!("{}", diff::render(&divergence.left, &divergence.right));
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn the_first_differing_input_is_found() {
+        // The optimized variant forgets about negative numbers
+        let task = MockTask::new(|input, context| {
+            let numbers = input
+                .lines()
+                .map(|line| line.parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()?;
+            let sum = match context.variant() {
+                Some(_) => numbers.iter().filter(|&&n| n > 0).sum::<i64>(),
+                None => numbers.iter().sum(),
+            };
+            Ok(vec![sum.to_string()])
+        })
+        .with_variants(&["optimized"])
+        .with_generator(|scale| {
+            (0..scale)
+                .map(|i| format!("{}\n", 3 - i as i64 * 2))
+                .collect()
+        });
+        let task: SharedAocTask = Arc::new(task);
+        let default = Implementation::new(&task, None, 1).unwrap();
+        let optimized = Implementation::new(&task, Some("optimized"), 1).unwrap();
+        assert!(Implementation::new(&task, Some("missing"), 1).is_err());
+
+        let inputs = generated_inputs(&task, 10);
+        assert_eq!(inputs.len(), 10);
+        let divergence = find_divergence(&default, &optimized, 1, inputs)
+            .unwrap()
+            .unwrap();
+        assert_eq!(divergence.input.contents, "3\n1\n-1\n");
+        assert_eq!(
+            (divergence.left, divergence.right),
+            (vec!["3".to_owned()], vec!["4".to_owned()])
+        );
+        assert!(
+            find_divergence(&default, &default, 1, generated_inputs(&task, 5))
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,31 +1,125 @@
-use std::error::Error;
+use std::{error::Error, time::Duration};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum AocError {
-    #[error(
-        "Failed to mark the task {task_name} as solved by creating the solved file: {solved_path}"
-    )]
-    MarkSolvedError {
-        task_name: String,
-        solved_path: String,
-        source: std::io::Error,
-    },
     #[error("Failed to read the IO file: {path}")]
     IOReadError {
         path: String,
         source: std::io::Error,
     },
+    #[error("Failed to write the IO file: {path}")]
+    IOWriteError {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Could not find the `{marker}` marker used to register new tasks in {path}")]
+    MissingScaffoldMarker { path: String, marker: String },
+    #[error("Missing the adventofcode.com session cookie, set it using the {variable} environment variable, the {config_path} file or the `session` command")]
+    MissingSession {
+        variable: String,
+        config_path: String,
+    },
+    #[error("The adventofcode.com session cookie from {origin} has expired or is invalid, log in again and replace it")]
+    ExpiredSession { origin: String },
+    #[error("Could not find the config directory of the current user")]
+    MissingConfigDirectory,
+    #[cfg(feature = "keyring")]
+    #[error("Failed to access the OS keyring")]
+    KeyringError { source: keyring::Error },
+    #[error("The input can only be read from stdin with --non-interactive")]
+    InteractiveStdinInput,
+    #[error("There is no puzzle for day {day} of {year}")]
+    MissingPuzzle { year: u16, day: u8 },
+    #[error("Could not determine the year of day {day}, pass it explicitly or register the task in a directory named after the year")]
+    MissingPuzzleYear { day: u8 },
+    #[error("Could not find a registered task for day {day}")]
+    MissingTask { day: u8 },
+    #[error("Day {day} is not a puzzle day, the days go from 1 to 25")]
+    InvalidDay { day: u8 },
+    #[error("Several registered tasks are for day {day}, pass the year to pick one of them")]
+    AmbiguousTask { day: u8 },
+    #[error("{task_name} has no phase {phase}, it only has {phases}")]
+    MissingPhase {
+        task_name: String,
+        phase: usize,
+        phases: usize,
+    },
+    #[cfg(feature = "leaderboard")]
+    #[error("Failed to parse the private leaderboard")]
+    LeaderboardParseError { source: serde_json::Error },
+    #[error("The request to {url} failed")]
+    RequestError {
+        url: String,
+        source: Box<ureq::Error>,
+    },
     #[error("Could not find any example inputs/outputs in the folder {directory}. Expected at least one pair of files that start with `example_` and end with `_in`/`_out`. Error: {source}")]
     MissingExample {
         directory: String,
         source: std::io::Error,
     },
+    #[error("Invalid example metadata in {path}: {message}")]
+    InvalidExampleMetadata { path: String, message: String },
+    #[error("Invalid configuration in {path}: {message}")]
+    InvalidConfig { path: String, message: String },
+    #[error("Invalid state in {path}: {message}")]
+    InvalidState { path: String, message: String },
+    #[error("Invalid hashed output in {path}, expected `<salt>:<sha256>`")]
+    InvalidHashedOutput { path: String },
+    #[error("{task_name} has no variant named `{variant}`")]
+    UnknownVariant { task_name: String, variant: String },
     #[error("Your solution returned an error: {source}")]
     SolutionExecutionError {
         input_path: String,
         source: Box<dyn Error + Send + Sync>,
     },
+    #[error("Your solution panicked while solving {input_path}: {message}")]
+    SolutionPanicked { input_path: String, message: String },
+    #[error("The solution of {task_name} phase {phase} did not finish within {timeout:?}")]
+    Timeout {
+        task_name: String,
+        phase: usize,
+        timeout: Duration,
+    },
+    #[error("Failed to run the command `{command}`")]
+    CommandError {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("The command `{command}` failed with {status}")]
+    CommandFailed { command: String, status: String },
+    #[cfg(feature = "watch")]
+    #[error("Failed to watch the task files for changes")]
+    WatchError { source: notify::Error },
+    #[error("The file watcher stopped unexpectedly")]
+    WatchStopped,
+    #[cfg(feature = "tui")]
+    #[error("Failed to draw the dashboard")]
+    TerminalError { source: std::io::Error },
+    #[cfg(feature = "async")]
+    #[error("Failed to start the async runtime")]
+    AsyncRuntimeError { source: std::io::Error },
+    #[cfg(feature = "serve")]
+    #[error("Failed to serve the dashboard")]
+    ServerError { source: std::io::Error },
+    #[cfg(feature = "dhat")]
+    #[error("Expected the filter to select a single task phase, it selected {count}")]
+    AmbiguousFilter { count: usize },
+    #[cfg(feature = "encrypt")]
+    #[error("Failed to encrypt the input {path}")]
+    EncryptionError {
+        path: String,
+        source: Box<age::EncryptError>,
+    },
+    #[cfg(feature = "encrypt")]
+    #[error("Failed to decrypt the input {path}, check the passphrase")]
+    DecryptionError {
+        path: String,
+        source: Box<age::DecryptError>,
+    },
     #[error("Failed to get user input")]
     UserInterractionError { source: dialoguer::Error },
+    #[cfg(feature = "browser")]
+    #[error("Failed to open {url} in the browser")]
+    BrowserError { url: String, source: std::io::Error },
 }
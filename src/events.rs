@@ -0,0 +1,214 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    error::AocError,
+    report::{ExampleReport, PhaseReport, RunReport, TaskReport},
+    reporter::Reporter,
+    run_tasks, AocSolution, AocTask, RunOptions, SharedAocTask,
+};
+
+// Owned copies of the reporter callbacks, for consuming the progress of a run from another thread
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "event", rename_all = "snake_case"))]
+pub enum RunEvent {
+    TaskStarted {
+        task: String,
+    },
+    ExampleFinished {
+        task: String,
+        phase: usize,
+        example: ExampleReport,
+    },
+    StreamedOutput {
+        task: String,
+        phase: usize,
+        line: String,
+    },
+    PhaseOutput {
+        task: String,
+        phase: usize,
+        output: AocSolution,
+    },
+    PhaseFinished {
+        task: String,
+        phase: PhaseReport,
+    },
+    TaskFinished {
+        index: usize,
+        task_count: usize,
+        report: TaskReport,
+    },
+    RunFinished {
+        report: RunReport,
+    },
+}
+
+// Sends every reporter callback as a `RunEvent`, events sent after the receiver is dropped are lost
+#[derive(Debug, Clone)]
+pub struct ChannelReporter {
+    sender: Sender<RunEvent>,
+}
+
+impl ChannelReporter {
+    pub fn new(sender: Sender<RunEvent>) -> Self {
+        Self { sender }
+    }
+
+    fn send(&self, event: RunEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Reporter for ChannelReporter {
+    fn on_task_start(&mut self, task: &dyn AocTask) {
+        self.send(RunEvent::TaskStarted { task: task.name() });
+    }
+
+    fn on_example_result(&mut self, task: &dyn AocTask, phase: usize, example: &ExampleReport) {
+        self.send(RunEvent::ExampleFinished {
+            task: task.name(),
+            phase,
+            example: example.clone(),
+        });
+    }
+
+    fn on_streamed_output(&mut self, task: &dyn AocTask, phase: usize, line: &str) {
+        self.send(RunEvent::StreamedOutput {
+            task: task.name(),
+            phase,
+            line: line.to_owned(),
+        });
+    }
+
+    fn on_phase_output(&mut self, task: &dyn AocTask, phase: usize, output: &AocSolution) {
+        self.send(RunEvent::PhaseOutput {
+            task: task.name(),
+            phase,
+            output: output.clone(),
+        });
+    }
+
+    fn on_phase_result(&mut self, task: &dyn AocTask, report: &PhaseReport) {
+        self.send(RunEvent::PhaseFinished {
+            task: task.name(),
+            phase: report.clone(),
+        });
+    }
+
+    fn on_task_done(
+        &mut self,
+        _task: &dyn AocTask,
+        index: usize,
+        task_count: usize,
+        report: &TaskReport,
+    ) {
+        self.send(RunEvent::TaskFinished {
+            index,
+            task_count,
+            report: report.clone(),
+        });
+    }
+
+    fn on_run_done(&mut self, report: &RunReport) {
+        self.send(RunEvent::RunFinished {
+            report: report.clone(),
+        });
+    }
+}
+
+// A run on a background thread, iterating it blocks until the next event arrives
+#[derive(Debug)]
+pub struct RunEvents {
+    receiver: Receiver<RunEvent>,
+    handle: JoinHandle<Result<RunReport, AocError>>,
+}
+
+impl RunEvents {
+    // Waits for the run to finish, the remaining events are dropped
+    pub fn finish(self) -> Result<RunReport, AocError> {
+        drop(self.receiver);
+        match self.handle.join() {
+            Ok(result) => result,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl Iterator for RunEvents {
+    type Item = RunEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+// Nothing is printed, prompts would still be shown unless the options are non-interactive
+pub fn run_events(tasks: Vec<SharedAocTask>, options: RunOptions) -> RunEvents {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let mut reporter = ChannelReporter::new(sender);
+        run_tasks(&tasks, &options, &mut reporter)
+    });
+    RunEvents { receiver, handle }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{testing::MockTask, Example};
+
+    #[test]
+    fn events_follow_the_run() {
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            fail_fast: false,
+            ..RunOptions::default()
+        };
+        let task = MockTask::new(|input, context| {
+            for line in input.lines() {
+                let sum = line
+                    .split_whitespace()
+                    .map(|num| num.parse::<i32>().unwrap_or(0))
+                    .sum::<i32>();
+                context.emit(sum);
+            }
+            Ok(vec![])
+        })
+        .with_input("1 2\n3 4")
+        .with_example(Example::new("2 2", "4"));
+        let mut run = run_events(vec![Arc::new(task)], options);
+        assert!(matches!(run.next(), Some(RunEvent::TaskStarted { .. })));
+        let events = run.by_ref().collect::<Vec<_>>();
+        let phases = events
+            .iter()
+            .filter(|event| matches!(event, RunEvent::PhaseFinished { .. }))
+            .count();
+        assert_eq!(phases, 2);
+        // Streamed lines arrive before the output they make up
+        let mut streamed = vec![];
+        let mut outputs = 0;
+        for event in &events {
+            match event {
+                RunEvent::StreamedOutput { line, .. } => streamed.push(line.clone()),
+                RunEvent::PhaseOutput { output, .. } => {
+                    assert_eq!(output, &std::mem::take(&mut streamed));
+                    outputs += 1;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(outputs, 2);
+        assert!(matches!(
+            events.last(),
+            Some(RunEvent::RunFinished { report }) if report.tasks.len() == 1
+        ));
+        assert_eq!(run.finish().unwrap().tasks.len(), 1);
+    }
+}
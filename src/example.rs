@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::error::AocError;
+
+// Example specific parameters, e.g. a number of iterations that differs from the real input.
+// The real input is always solved without any arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExampleArgs(BTreeMap<String, String>);
+
+impl ExampleArgs {
+    pub fn get<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.0.get(name)?.parse().ok()
+    }
+
+    pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> T {
+        self.get(name).unwrap_or(default)
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: impl ToString) {
+        self.0.insert(name.into(), value.to_string());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+// An example declared in code instead of a pair of example files, multiline literals are
+// dedented and may start on the line after the opening quote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expected_output: String,
+    pub args: ExampleArgs,
+    // Unscoped examples only have the expected outputs of the first phase, like example files
+    pub phase: Option<usize>,
+}
+
+impl Example {
+    pub fn new(input: &str, expected_output: &str) -> Self {
+        Self {
+            name: String::new(),
+            input: dedent(input),
+            expected_output: dedent(expected_output),
+            args: ExampleArgs::default(),
+            phase: None,
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn arg(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.args.insert(name, value);
+        self
+    }
+
+    pub fn phase(mut self, phase: usize) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    pub fn runs_in_phase(&self, phase: usize) -> bool {
+        self.phase
+            .is_none_or(|example_phase| example_phase == phase)
+    }
+}
+
+// Optional `<example>.toml` sidecar of an example file, e.g. `example_1.toml` for
+// `example_1_in`, with the phases to run and check it in, a display name and extra arguments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExampleMetadata {
+    pub name: Option<String>,
+    pub phases: Vec<usize>,
+    pub args: ExampleArgs,
+}
+
+pub fn metadata_path(input_path: &Path) -> PathBuf {
+    let file_name = input_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = file_name.strip_suffix("_in").unwrap_or(&file_name);
+    input_path.with_file_name(format!("{stem}.toml"))
+}
+
+fn toml_scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(value) => Some(value.clone()),
+        toml::Value::Integer(value) => Some(value.to_string()),
+        toml::Value::Float(value) => Some(value.to_string()),
+        toml::Value::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+impl ExampleMetadata {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let table = content
+            .parse::<toml::Table>()
+            .map_err(|err| err.to_string().trim().to_owned())?;
+        let mut metadata = Self::default();
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("name", toml::Value::String(name)) => metadata.name = Some(name.clone()),
+                ("phases", toml::Value::Array(phases)) => {
+                    metadata.phases = phases
+                        .iter()
+                        .map(|phase| match phase {
+                            toml::Value::Integer(phase) if *phase > 0 => Some(*phase as usize),
+                            _ => None,
+                        })
+                        .collect::<Option<_>>()
+                        .ok_or("`phases` must be a list of phase numbers")?;
+                }
+                ("args", toml::Value::Table(args)) => {
+                    for (name, value) in args {
+                        let value = toml_scalar(value)
+                            .ok_or_else(|| format!("the argument `{name}` is not a scalar"))?;
+                        metadata.args.insert(name.clone(), value);
+                    }
+                }
+                ("name" | "phases" | "args", _) => {
+                    return Err(format!("`{key}` has an unexpected type"));
+                }
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        }
+        Ok(metadata)
+    }
+
+    // Examples without a sidecar have the default metadata
+    pub fn load(input_path: &Path) -> Result<Self, AocError> {
+        let path = metadata_path(input_path);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|err| AocError::IOReadError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        Self::parse(&content).map_err(|message| AocError::InvalidExampleMetadata {
+            path: path.to_string_lossy().to_string(),
+            message,
+        })
+    }
+}
+
+pub(crate) fn dedent(text: &str) -> String {
+    let text = text.strip_prefix('\n').unwrap_or(text);
+    let text = match text.rsplit_once('\n') {
+        Some((rest, last)) if last.trim().is_empty() => rest,
+        _ => text,
+    };
+    let indent = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    text.lines()
+        .map(|line| line.get(indent..).unwrap_or(line.trim_start()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_example_metadata() {
+        let metadata = ExampleMetadata::parse(
+            "name = \"large sample\"\nphases = [2]\nargs = { iterations = 10, unit = \"m\" }",
+        )
+        .unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("large sample"));
+        assert_eq!(metadata.phases, vec![2]);
+        assert_eq!(metadata.args.get::<usize>("iterations"), Some(10));
+        assert_eq!(metadata.args.get_or("unit", String::new()), "m");
+        assert_eq!(metadata.args.get_or("steps", 64), 64);
+
+        assert!(ExampleMetadata::parse("phase = 2").is_err());
+        assert_eq!(
+            metadata_path(Path::new("day_01/example_1_in")),
+            Path::new("day_01/example_1.toml")
+        );
+    }
+
+    #[test]
+    fn dedent_example_literals() {
+        let example = Example::new(
+            r"
+            1 2
+              3 4
+            ",
+            "3",
+        )
+        .phase(2);
+        assert_eq!(example.input, "1 2\n  3 4");
+        assert_eq!(example.expected_output, "3");
+        assert!(example.runs_in_phase(2));
+        assert!(!example.runs_in_phase(1));
+    }
+}
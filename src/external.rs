@@ -0,0 +1,205 @@
+use std::{
+    error::Error,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+};
+
+use itertools::Itertools;
+
+use crate::{context::AocContext, error::AocError, input, AocSolution, AocStringIter, AocTask};
+
+pub const PHASE_ENV_VAR: &str = "AOC_PHASE";
+// Followed by the upper case name of the argument
+pub const ARG_ENV_VAR_PREFIX: &str = "AOC_ARG_";
+const PHASE_PLACEHOLDER: &str = "{phase}";
+
+// Solves the task with an external program, which gets the input on stdin and the phase
+// through the environment or a `{phase}` argument, and prints the solution to stdout.
+// Example arguments are passed through the environment as well.
+#[derive(Debug, Clone)]
+pub struct ExternalTask {
+    directory: PathBuf,
+    program: String,
+    args: Vec<String>,
+    phases: usize,
+    name: Option<String>,
+    date: Option<(u16, u8)>,
+}
+
+impl ExternalTask {
+    pub fn new(directory: impl Into<PathBuf>, program: impl Into<String>) -> Self {
+        Self {
+            directory: directory.into(),
+            program: program.into(),
+            args: vec![],
+            phases: 2,
+            name: None,
+            date: None,
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_phases(mut self, phases: usize) -> Self {
+        self.phases = phases;
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    // Only needed when the year and day can't be parsed out of the directory
+    pub fn with_date(mut self, year: u16, day: u8) -> Self {
+        self.date = Some((year, day));
+        self
+    }
+
+    fn command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl AocTask for ExternalTask {
+    fn directory(&self) -> PathBuf {
+        self.directory.clone()
+    }
+
+    fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self.title_case(
+                self.directory
+                    .file_name()
+                    .map(|name| name.to_string_lossy().replace('_', " "))
+                    .unwrap_or_else(|| self.program.clone()),
+            ),
+        }
+    }
+
+    fn year(&self) -> Option<u16> {
+        match self.date {
+            Some((year, _)) => Some(year),
+            None => input::puzzle_date(&self.directory).map(|(year, _)| year),
+        }
+    }
+
+    fn day(&self) -> Option<u8> {
+        match self.date {
+            Some((_, day)) => Some(day),
+            None => input::puzzle_day(&self.directory),
+        }
+    }
+
+    fn phases(&self) -> usize {
+        self.phases
+    }
+
+    fn solution(
+        &self,
+        mut input: AocStringIter,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        self.solution_bytes(input.join("\n").as_bytes(), context)
+    }
+
+    // The stderr of the program is inherited, so it is captured together with the output of
+    // the framework
+    fn solution_bytes(
+        &self,
+        input: &[u8],
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        let phase = context.phase();
+        let command_error = |err| AocError::CommandError {
+            command: self.command_line(),
+            source: err,
+        };
+        let mut child = Command::new(&self.program)
+            .args(
+                self.args
+                    .iter()
+                    .map(|arg| arg.replace(PHASE_PLACEHOLDER, &phase.to_string())),
+            )
+            .env(PHASE_ENV_VAR, phase.to_string())
+            .envs(context.args().iter().map(|(name, value)| {
+                (
+                    format!("{ARG_ENV_VAR_PREFIX}{}", name.to_uppercase()),
+                    value,
+                )
+            }))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(command_error)?;
+
+        // The input is written from another thread, so that a program printing its output
+        // before reading all of the input cannot block
+        let mut stdin = child.stdin.take().expect("stdin of the command is piped");
+        let output = thread::scope(|scope| {
+            let writer = scope.spawn(move || stdin.write_all(input));
+            let output = child.wait_with_output();
+            // A program may exit without reading its whole input
+            let _ = writer.join();
+            output
+        })
+        .map_err(command_error)?;
+
+        if !output.status.success() {
+            return Err(AocError::CommandFailed {
+                command: self.command_line(),
+                status: output.status.to_string(),
+            }
+            .into());
+        }
+        Ok(String::from_utf8(output.stdout)?
+            .lines()
+            .map(str::to_owned)
+            .collect())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ExampleArgs;
+
+    #[test]
+    fn external_task_examples() {
+        let task = ExternalTask::new("tests/sum_task", "awk")
+            .arg("{ sum = 0; for (i = 1; i <= NF; i++) sum += $i; print sum }");
+        assert_eq!(task.name(), "Sum Task");
+        for example in task.example_paths().unwrap() {
+            assert!(task.run_example_test(&example, 1).unwrap().passed);
+        }
+
+        let task = ExternalTask::new("tests/sum_task", "sh").args(["-c", "exit {phase}"]);
+        assert!(task.solution_raw("", &AocContext::new(2)).is_err());
+
+        let task = ExternalTask::new("tests/sum_task", "sh").args(["-c", "echo $AOC_ARG_STEPS"]);
+        let mut args = ExampleArgs::default();
+        args.insert("steps", 6);
+        let context = AocContext::example(1, args);
+        assert_eq!(task.solution_bytes(b"", &context).unwrap(), ["6"]);
+    }
+}
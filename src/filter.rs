@@ -0,0 +1,69 @@
+use crate::AocTask;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub name: Option<String>,
+    pub day: Option<u8>,
+    pub phase: Option<usize>,
+}
+
+impl Filter {
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn day(day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..Self::default()
+        }
+    }
+
+    pub fn name(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn phase(mut self, phase: usize) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    pub fn matches_task(&self, task: &dyn AocTask) -> bool {
+        let name_matches = self
+            .name
+            .as_ref()
+            .is_none_or(|name| task.name().to_lowercase().contains(&name.to_lowercase()));
+        let day_matches = self.day.is_none_or(|day| task.day() == Some(day));
+        name_matches && day_matches
+    }
+
+    pub fn matches_phase(&self, phase: usize) -> bool {
+        self.phase
+            .is_none_or(|filtered_phase| filtered_phase == phase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn filter_tasks() {
+        let day_task = MockTask::new(|_, _| Ok(vec![]))
+            .with_name("Day 07")
+            .with_date(2023, 7);
+        assert!(Filter::all().matches_task(&day_task));
+        assert!(Filter::day(7).matches_task(&day_task));
+        assert!(!Filter::day(8).matches_task(&day_task));
+        assert!(Filter::name("day 07").matches_task(&day_task));
+        assert!(!Filter::name("day 08").matches_task(&day_task));
+
+        let filter = Filter::day(7).phase(2);
+        assert!(filter.matches_phase(2));
+        assert!(!filter.matches_phase(1));
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    collections::hash_map::RandomState,
+    fs,
+    hash::{BuildHasher, Hasher},
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    answers::{self, AnswerLog},
+    error::AocError,
+    AocSolution, AocTask, SharedAocTask,
+};
+
+// Expected outputs can be committed as salted hashes next to or instead of the outputs, e.g.
+// `example_1_out.sha`, so shared repositories can be checked without revealing the answers
+pub const HASHED_SUFFIX: &str = ".sha";
+// The accepted answers of the input, one line per phase
+pub const HASHED_ANSWERS_FILE: &str = "out.sha";
+// Shown in place of an expected output that is only known by its hash
+pub const HASHED_OUTPUT: &str = "<hashed output>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedOutput {
+    salt: String,
+    hash: String,
+}
+
+fn digest(salt: &str, text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(text.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Trailing whitespace is not part of the output, like when the outputs are compared
+pub fn output_text(output: &AocSolution) -> String {
+    output
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_owned()
+}
+
+impl HashedOutput {
+    // The salt only has to differ between the outputs, it does not have to be secure
+    pub fn new(text: &str) -> Self {
+        let salt = format!("{:016x}", RandomState::new().build_hasher().finish());
+        Self::with_salt(salt, text)
+    }
+
+    pub fn with_salt(salt: impl Into<String>, text: &str) -> Self {
+        let salt = salt.into();
+        let hash = digest(&salt, text);
+        Self { salt, hash }
+    }
+
+    // `<salt>:<sha256 of the salt followed by the output>`
+    pub fn parse(line: &str) -> Option<Self> {
+        let (salt, hash) = line.trim().split_once(':')?;
+        (hash.len() == 64 && hash.chars().all(|char| char.is_ascii_hexdigit())).then(|| Self {
+            salt: salt.to_owned(),
+            hash: hash.to_ascii_lowercase(),
+        })
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        digest(&self.salt, text) == self.hash
+    }
+}
+
+impl std::fmt::Display for HashedOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.salt, self.hash)
+    }
+}
+
+pub fn is_hashed(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(HASHED_SUFFIX)
+}
+
+pub fn hashed_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.as_os_str().to_owned();
+    path.push(HASHED_SUFFIX);
+    PathBuf::from(path)
+}
+
+fn read(path: &Path) -> Result<String, AocError> {
+    fs::read_to_string(path).map_err(|err| AocError::IOReadError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })
+}
+
+fn write(path: &Path, contents: String) -> Result<(), AocError> {
+    fs::write(path, contents).map_err(|err| AocError::IOWriteError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })
+}
+
+pub fn load(path: &Path) -> Result<HashedOutput, AocError> {
+    HashedOutput::parse(&read(path)?).ok_or_else(|| AocError::InvalidHashedOutput {
+        path: path.to_string_lossy().to_string(),
+    })
+}
+
+// The hash of the accepted answer of the phase, empty lines are phases without one
+pub fn answer_hash(task: &dyn AocTask, phase: usize) -> Result<Option<HashedOutput>, AocError> {
+    let path = task.data_directory().join(HASHED_ANSWERS_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    match read(&path)?.lines().nth(phase - 1).map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(line) => {
+            HashedOutput::parse(line)
+                .map(Some)
+                .ok_or_else(|| AocError::InvalidHashedOutput {
+                    path: path.to_string_lossy().to_string(),
+                })
+        }
+    }
+}
+
+// Writes the hashes of the example outputs and of the accepted answers of the tasks, returns
+// the written files
+pub fn hash_outputs(tasks: &[SharedAocTask]) -> Result<Vec<PathBuf>, AocError> {
+    let mut written = vec![];
+    for task in tasks {
+        for (_, output_path) in task.example_paths()? {
+            if is_hashed(&output_path) {
+                continue;
+            }
+            let output = output_text(&task.get_file_output(&output_path)?);
+            let path = hashed_path(&output_path);
+            // Salting the same output again would only change the committed file
+            if path.is_file() && load(&path).is_ok_and(|hash| hash.matches(&output)) {
+                continue;
+            }
+            write(&path, format!("{}\n", HashedOutput::new(&output)))?;
+            written.push(path);
+        }
+
+        let answers = AnswerLog::load(task.as_ref())?;
+        let path = task.data_directory().join(HASHED_ANSWERS_FILE);
+        let existing = if path.is_file() {
+            read(&path)?
+        } else {
+            String::new()
+        };
+        let existing_hashes = existing.lines().map(str::trim).collect::<Vec<_>>();
+        // Hashes of phases without a local answer are kept, they may come from a shared repository
+        let hashes = (1..=task.phases())
+            .map(|phase| {
+                let existing = existing_hashes.get(phase - 1).copied().unwrap_or_default();
+                match answers.accepted(phase) {
+                    Some(answer) => match HashedOutput::parse(existing) {
+                        Some(hash) if hash.matches(answer) => existing.to_owned(),
+                        _ => HashedOutput::new(answer).to_string(),
+                    },
+                    None => existing.to_owned(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let content = hashes.join("\n") + "\n";
+        if hashes.iter().any(|hash| !hash.is_empty()) && content != existing {
+            write(&path, content)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+// Whether the answer of the output matches the hash of the accepted answer
+pub fn matches_answer(hash: &HashedOutput, output: &AocSolution) -> bool {
+    hash.matches(&answers::answer_text(output))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, sync::Arc};
+
+    use super::*;
+    use crate::{testing::TempDir, AocContext, AocStringIter};
+
+    struct LineCountTask {
+        directory: PathBuf,
+    }
+
+    impl AocTask for LineCountTask {
+        fn directory(&self) -> PathBuf {
+            self.directory.clone()
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(vec![input.count().to_string()])
+        }
+    }
+
+    #[test]
+    fn hashed_outputs_match_only_their_output() {
+        let hashed = HashedOutput::with_salt("salt", "4\n10");
+        assert_eq!(
+            HashedOutput::parse(&hashed.to_string()),
+            Some(hashed.clone())
+        );
+        assert!(hashed.matches(&output_text(&vec!["4 ".to_owned(), "10".to_owned()])));
+        assert!(!hashed.matches("4\n11"));
+        assert_ne!(HashedOutput::with_salt("pepper", "4\n10"), hashed);
+        assert!(HashedOutput::new("4").matches("4"));
+        assert_eq!(HashedOutput::parse("salt:1234"), None);
+    }
+
+    #[test]
+    fn examples_are_checked_against_hashed_outputs() {
+        let directory = TempDir::new("hashed");
+        directory.write("example_in", "a\nb\n");
+        directory.write("example_out", "2\n");
+        directory.write("example_wrong_in", "a\n");
+        directory.write("example_wrong_out.sha", "salt:");
+        let task: SharedAocTask = Arc::new(LineCountTask {
+            directory: directory.path().to_owned(),
+        });
+
+        assert!(task
+            .run_example_test(&task.example_paths().unwrap()[1], 1)
+            .is_err());
+        let written = hash_outputs(std::slice::from_ref(&task)).unwrap();
+        assert_eq!(written, [directory.join("example_out.sha")]);
+        let hashed = fs::read_to_string(&written[0]).unwrap();
+        assert!(hash_outputs(std::slice::from_ref(&task))
+            .unwrap()
+            .is_empty());
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), hashed);
+        fs::remove_file(directory.join("example_out")).unwrap();
+        directory.write("example_wrong_out.sha", &HashedOutput::new("2").to_string());
+
+        let results = task
+            .example_paths()
+            .unwrap()
+            .iter()
+            .map(|io_pair| task.run_example_test(io_pair, 1).unwrap())
+            .collect::<Vec<_>>();
+        assert!(results[0].passed && !results[1].passed);
+        assert_eq!(results[1].expected_output, [HASHED_OUTPUT]);
+    }
+}
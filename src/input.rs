@@ -0,0 +1,116 @@
+use std::{
+    env,
+    fs::{self, File},
+    path::{Component, Path, PathBuf},
+};
+
+use memmap2::Mmap;
+
+use crate::{client::AocClient, error::AocError, input_files};
+
+pub const DATA_DIR_ENV_VAR: &str = "AOC_DATA_DIR";
+
+pub fn puzzle_day(directory: &Path) -> Option<u8> {
+    directory
+        .file_name()?
+        .to_string_lossy()
+        .rsplit(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+}
+
+// The first directory on the path ending with a year, e.g. `aoc_2023`
+pub fn puzzle_year(directory: &Path) -> Option<u16> {
+    directory
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .filter_map(|name| {
+            name.rsplit(|c: char| !c.is_ascii_digit())
+                .next()?
+                .parse::<u16>()
+                .ok()
+        })
+        .find(|year| *year >= 2015)
+}
+
+pub fn puzzle_date(directory: &Path) -> Option<(u16, u8)> {
+    let day = puzzle_day(directory)?;
+    Some((puzzle_year(directory)?, day))
+}
+
+pub fn data_directory(task_directory: &Path) -> PathBuf {
+    dated_data_directory(task_directory, puzzle_date(task_directory))
+}
+
+// Inputs and examples of dated tasks can be kept outside of the source tree, in
+// `<data dir>/<year>/<day>`, when the environment variable is set
+pub fn dated_data_directory(task_directory: &Path, date: Option<(u16, u8)>) -> PathBuf {
+    match (env::var_os(DATA_DIR_ENV_VAR), date) {
+        (Some(data_directory), Some((year, day))) => PathBuf::from(data_directory)
+            .join(year.to_string())
+            .join(format!("{day:02}")),
+        _ => task_directory.to_path_buf(),
+    }
+}
+
+pub fn fetch_input(year: u16, day: u8, input_path: &Path) -> Result<(), AocError> {
+    write_input(input_path, &AocClient::load()?.input(year, day)?)
+}
+
+// Downloaded inputs are kept out of git
+pub fn write_input(input_path: &Path, input: &str) -> Result<(), AocError> {
+    let write_error = |err| AocError::IOWriteError {
+        path: input_path.to_string_lossy().to_string(),
+        source: err,
+    };
+    if let Some(parent) = input_path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    fs::write(input_path, input).map_err(write_error)?;
+    input_files::ignore_input(input_path)?;
+    Ok(())
+}
+
+pub fn map_input(path: &Path) -> Result<Mmap, AocError> {
+    trace_span!("read_input", path = %path.display());
+    let file = File::open(path).map_err(|err| AocError::IOReadError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })?;
+    // SAFETY: Input files are treated as read-only while a task is running,
+    // modifying them during a run is not supported.
+    unsafe { Mmap::map(&file) }.map_err(|err| AocError::IOReadError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn puzzle_date_from_directory() {
+        assert_eq!(
+            puzzle_date(Path::new("aoc_2023/src/day_05")),
+            Some((2023, 5))
+        );
+        assert_eq!(puzzle_date(Path::new("2022/day12")), Some((2022, 12)));
+        assert_eq!(puzzle_date(Path::new("src/day_05")), None);
+        assert_eq!(puzzle_date(Path::new("tests/sum_task")), None);
+        assert_eq!(puzzle_year(Path::new("src/year_2024")), Some(2024));
+    }
+
+    #[test]
+    fn map_empty_input() {
+        let directory = TempDir::new("empty_input");
+        let path = directory.write("in", "");
+        assert!(map_input(&path).unwrap().is_empty());
+    }
+}
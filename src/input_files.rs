@@ -0,0 +1,179 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "encrypt")]
+use age::secrecy::SecretString;
+#[cfg(feature = "encrypt")]
+use dialoguer::{theme::ColorfulTheme, Password};
+use itertools::Itertools;
+
+use crate::{error::AocError, SharedAocTask};
+
+pub const INPUT_FILE: &str = "in";
+#[cfg(feature = "encrypt")]
+pub const ENCRYPTED_INPUT_FILE: &str = "in.age";
+#[cfg(feature = "encrypt")]
+pub const PASSPHRASE_ENV_VAR: &str = "AOC_INPUTS_PASSPHRASE";
+
+fn input_directories(tasks: &[SharedAocTask]) -> Vec<PathBuf> {
+    tasks
+        .iter()
+        .map(|task| task.data_directory())
+        .unique()
+        .collect()
+}
+
+// The puzzle inputs may not be redistributed, so the downloaded inputs are kept out of git
+// with a `.gitignore` next to them
+pub fn ignore_input(input_path: &Path) -> Result<bool, AocError> {
+    let (Some(directory), Some(file_name)) = (input_path.parent(), input_path.file_name()) else {
+        return Ok(false);
+    };
+    let entry = format!("/{}", file_name.to_string_lossy());
+    let gitignore_path = directory.join(".gitignore");
+    let mut gitignore = if gitignore_path.is_file() {
+        fs::read_to_string(&gitignore_path).map_err(|err| AocError::IOReadError {
+            path: gitignore_path.to_string_lossy().to_string(),
+            source: err,
+        })?
+    } else {
+        String::new()
+    };
+    if gitignore
+        .lines()
+        .any(|line| line.trim() == entry || line.trim() == &entry[1..])
+    {
+        return Ok(false);
+    }
+
+    if !gitignore.is_empty() && !gitignore.ends_with('\n') {
+        gitignore.push('\n');
+    }
+    gitignore.push_str(&entry);
+    gitignore.push('\n');
+    fs::write(&gitignore_path, gitignore).map_err(|err| AocError::IOWriteError {
+        path: gitignore_path.to_string_lossy().to_string(),
+        source: err,
+    })?;
+    Ok(true)
+}
+
+// Returns the `.gitignore` files that had to be updated
+pub fn ignore_inputs(tasks: &[SharedAocTask]) -> Result<Vec<PathBuf>, AocError> {
+    let mut updated = vec![];
+    for directory in input_directories(tasks) {
+        let input_path = directory.join(INPUT_FILE);
+        if input_path.is_file() && ignore_input(&input_path)? {
+            updated.push(directory.join(".gitignore"));
+        }
+    }
+    Ok(updated)
+}
+
+// The passphrase can be provided through the environment to avoid the prompt in scripts
+#[cfg(feature = "encrypt")]
+pub fn passphrase(confirm: bool) -> Result<SecretString, AocError> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Ok(SecretString::from(passphrase));
+    }
+    let theme = ColorfulTheme::default();
+    let mut prompt = Password::with_theme(&theme).with_prompt("Inputs passphrase");
+    if confirm {
+        prompt = prompt.with_confirmation("Repeat the passphrase", "The passphrases don't match");
+    }
+    prompt
+        .interact()
+        .map(SecretString::from)
+        .map_err(|err| AocError::UserInterractionError { source: err })
+}
+
+#[cfg(feature = "encrypt")]
+fn read_file(path: &Path) -> Result<Vec<u8>, AocError> {
+    fs::read(path).map_err(|err| AocError::IOReadError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })
+}
+
+#[cfg(feature = "encrypt")]
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), AocError> {
+    fs::write(path, contents).map_err(|err| AocError::IOWriteError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })
+}
+
+// Writes an encrypted copy of every input next to it, the copies can be committed
+#[cfg(feature = "encrypt")]
+pub fn encrypt_inputs(
+    tasks: &[SharedAocTask],
+    passphrase: &SecretString,
+) -> Result<Vec<PathBuf>, AocError> {
+    let recipient = age::scrypt::Recipient::new(passphrase.clone());
+    let mut encrypted = vec![];
+    for directory in input_directories(tasks) {
+        let input_path = directory.join(INPUT_FILE);
+        if !input_path.is_file() {
+            continue;
+        }
+        let encrypted_path = directory.join(ENCRYPTED_INPUT_FILE);
+        let contents = age::encrypt(&recipient, &read_file(&input_path)?).map_err(|err| {
+            AocError::EncryptionError {
+                path: input_path.to_string_lossy().to_string(),
+                source: Box::new(err),
+            }
+        })?;
+        write_file(&encrypted_path, &contents)?;
+        ignore_input(&input_path)?;
+        encrypted.push(encrypted_path);
+    }
+    Ok(encrypted)
+}
+
+// Restores the inputs from their encrypted copies, e.g. after cloning the repository
+#[cfg(feature = "encrypt")]
+pub fn decrypt_inputs(
+    tasks: &[SharedAocTask],
+    passphrase: &SecretString,
+) -> Result<Vec<PathBuf>, AocError> {
+    let identity = age::scrypt::Identity::new(passphrase.clone());
+    let mut decrypted = vec![];
+    for directory in input_directories(tasks) {
+        let encrypted_path = directory.join(ENCRYPTED_INPUT_FILE);
+        if !encrypted_path.is_file() {
+            continue;
+        }
+        let input_path = directory.join(INPUT_FILE);
+        let contents = age::decrypt(&identity, &read_file(&encrypted_path)?).map_err(|err| {
+            AocError::DecryptionError {
+                path: encrypted_path.to_string_lossy().to_string(),
+                source: Box::new(err),
+            }
+        })?;
+        write_file(&input_path, &contents)?;
+        ignore_input(&input_path)?;
+        decrypted.push(input_path);
+    }
+    Ok(decrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn ignore_input_once() {
+        let directory = TempDir::new("inputs");
+        directory.write(".gitignore", "target");
+
+        assert!(ignore_input(&directory.join(INPUT_FILE)).unwrap());
+        assert!(!ignore_input(&directory.join(INPUT_FILE)).unwrap());
+        assert_eq!(
+            fs::read_to_string(directory.join(".gitignore")).unwrap(),
+            "target\n/in\n"
+        );
+    }
+}
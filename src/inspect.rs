@@ -0,0 +1,208 @@
+use std::{collections::HashMap, fmt::Write};
+
+use itertools::Itertools;
+
+use crate::{style::Stylize, DOT};
+
+const HISTOGRAM_CHARS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Structure {
+    Grid { width: usize, height: usize },
+    Sections { count: usize },
+    // The number of columns of every line and which of them are all numbers
+    Columns { count: usize, numeric: Vec<usize> },
+    SingleLine { length: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStats {
+    pub lines: usize,
+    pub min_line_length: usize,
+    pub max_line_length: usize,
+    pub histogram: Vec<(char, usize)>,
+    pub structure: Vec<Structure>,
+}
+
+fn columns(line: &str) -> Vec<&str> {
+    line.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|column| !column.is_empty())
+        .collect()
+}
+
+fn detect_structure(lines: &[&str]) -> Vec<Structure> {
+    let mut structure = vec![];
+    let filled = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .collect_vec();
+    if let [line] = filled[..] {
+        structure.push(Structure::SingleLine {
+            length: line.chars().count(),
+        });
+    }
+    let sections = lines
+        .split(|line| line.trim().is_empty())
+        .filter(|section| !section.is_empty())
+        .count();
+    if sections > 1 {
+        structure.push(Structure::Sections { count: sections });
+    }
+
+    let width = filled.first().map_or(0, |line| line.chars().count());
+    if filled.len() > 1
+        && filled
+            .iter()
+            .all(|line| line.chars().count() == width && !line.contains(char::is_whitespace))
+    {
+        structure.push(Structure::Grid {
+            width,
+            height: filled.len(),
+        });
+        return structure;
+    }
+
+    let rows = filled.iter().map(|line| columns(line)).collect_vec();
+    let count = rows.first().map_or(0, Vec::len);
+    if count > 0 && rows.iter().all(|row| row.len() == count) {
+        let numeric = (0..count)
+            .filter(|&column| rows.iter().all(|row| row[column].parse::<i64>().is_ok()))
+            .collect();
+        structure.push(Structure::Columns { count, numeric });
+    }
+    structure
+}
+
+pub fn inspect(input: &str) -> InputStats {
+    let lines = input.lines().collect_vec();
+    let lengths = lines.iter().map(|line| line.chars().count());
+    let mut counts = HashMap::new();
+    for c in input.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    let histogram = counts
+        .into_iter()
+        .sorted_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)))
+        .collect();
+    InputStats {
+        lines: lines.len(),
+        min_line_length: lengths.clone().min().unwrap_or(0),
+        max_line_length: lengths.max().unwrap_or(0),
+        histogram,
+        structure: detect_structure(&lines),
+    }
+}
+
+fn describe(structure: &Structure) -> String {
+    match structure {
+        Structure::Grid { width, height } => format!("a {width}x{height} grid"),
+        Structure::Sections { count } => format!("{count} sections separated by blank lines"),
+        Structure::Columns { count, numeric } if numeric.is_empty() => {
+            format!("{count} columns on every line")
+        }
+        Structure::Columns { count, numeric } => format!(
+            "{count} columns on every line, numeric columns: {}",
+            numeric.iter().map(|column| column + 1).join(", ")
+        ),
+        Structure::SingleLine { length } => format!("a single line of {length} characters"),
+    }
+}
+
+fn escaped(c: char) -> String {
+    match c {
+        ' ' => "' '".to_owned(),
+        c => c.escape_default().to_string(),
+    }
+}
+
+// The summary of the input followed by its first and last lines
+pub fn render(input: &str, stats: &InputStats, preview_lines: usize) -> String {
+    let mut output = String::new();
+    let _ = writeln!(
+        output,
+        "{} {} lines, {} to {} characters long",
+        DOT.info(),
+        stats.lines.to_string().highlight(),
+        stats.min_line_length.to_string().highlight(),
+        stats.max_line_length.to_string().highlight()
+    );
+    for structure in &stats.structure {
+        let _ = writeln!(
+            output,
+            "{} Looks like {}",
+            DOT.info(),
+            describe(structure).bold()
+        );
+    }
+    let histogram = stats
+        .histogram
+        .iter()
+        .take(HISTOGRAM_CHARS)
+        .map(|&(c, count)| format!("{} {count}", escaped(c).accent()))
+        .join(", ");
+    let more = stats.histogram.len().saturating_sub(HISTOGRAM_CHARS);
+    let _ = write!(output, "{} Characters: {histogram}", DOT.info());
+    if more > 0 {
+        let _ = write!(output, " {}", format!("and {more} more").muted());
+    }
+    output.push('\n');
+
+    let lines = input.lines().collect_vec();
+    let (head, tail) = if lines.len() > 2 * preview_lines {
+        (
+            &lines[..preview_lines],
+            &lines[lines.len() - preview_lines..],
+        )
+    } else {
+        (&lines[..], &lines[..0])
+    };
+    output.push('\n');
+    for line in head {
+        let _ = writeln!(output, "{line}");
+    }
+    if !tail.is_empty() {
+        let skipped = lines.len() - 2 * preview_lines;
+        let _ = writeln!(
+            output,
+            "{}",
+            format!("... {skipped} more lines ...").muted()
+        );
+        for line in tail {
+            let _ = writeln!(output, "{line}");
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structure_is_detected() {
+        let grid = inspect("#..#\n.#..\n..##\n");
+        assert_eq!(grid.lines, 3);
+        assert_eq!(grid.histogram[0], ('.', 7));
+        assert_eq!(
+            grid.structure,
+            [Structure::Grid {
+                width: 4,
+                height: 3
+            }]
+        );
+
+        let seeds = inspect("seeds: 79 14\n\nmap:\n50 98 2\n52 50 48\n");
+        assert_eq!(seeds.structure, [Structure::Sections { count: 2 }]);
+        assert_eq!(
+            inspect("1 abc 3\n-4 de 6").structure,
+            [Structure::Columns {
+                count: 3,
+                numeric: vec![0, 2]
+            }]
+        );
+        assert_eq!(
+            inspect("R2, L3").structure[0],
+            Structure::SingleLine { length: 6 }
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    time::{Duration, SystemTime},
+};
+
+use itertools::Itertools;
+use serde::Deserialize;
+
+use crate::{
+    client::AocClient,
+    error::AocError,
+    style::Stylize,
+    unlock::{event_days, unlock_time},
+};
+
+// The leaderboard API asks clients not to request a leaderboard more than once every 15 minutes
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StarCompletion {
+    pub get_star_ts: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub last_star_ts: i64,
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, StarCompletion>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star_timestamp(&self, day: u8, part: usize) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .map(|completion| completion.get_star_ts)
+    }
+
+    pub fn day_stars(&self, day: u8) -> usize {
+        (1..=2)
+            .filter(|&part| self.star_timestamp(day, part).is_some())
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+impl Leaderboard {
+    pub fn standings(&self) -> Vec<&Member> {
+        self.members
+            .values()
+            .sorted_by(|a, b| {
+                b.local_score
+                    .cmp(&a.local_score)
+                    .then(b.stars.cmp(&a.stars))
+                    .then(a.last_star_ts.cmp(&b.last_star_ts))
+            })
+            .collect()
+    }
+}
+
+pub fn fetch_leaderboard(client: &AocClient, year: u16, id: u64) -> Result<Leaderboard, AocError> {
+    let path = format!("/{year}/leaderboard/private/view/{id}.json");
+    let is_fresh = client
+        .cache_path(&path)
+        .and_then(|cache_path| fs::metadata(cache_path).ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < REFRESH_INTERVAL);
+
+    let body = match client.read_cache(&path).filter(|_| is_fresh) {
+        Some(cached) => cached,
+        None => {
+            let body = client.get(&path)?;
+            client.write_cache(&path, &body)?;
+            body
+        }
+    };
+    serde_json::from_str(&body).map_err(|err| AocError::LeaderboardParseError { source: err })
+}
+
+fn format_elapsed(seconds: i64) -> String {
+    if seconds >= 100 * 3600 {
+        return ">100h".to_owned();
+    }
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn star_grid(member: &Member, days: u8) -> String {
+    (1..=days)
+        .map(|day| match member.day_stars(day) {
+            2 => "*".yellow().to_string(),
+            1 => "*".grey().to_string(),
+            _ => ".".muted().to_string(),
+        })
+        .collect()
+}
+
+pub fn render_leaderboard(leaderboard: &Leaderboard, day: Option<u8>) -> String {
+    let mut out = String::new();
+    let year = leaderboard.event.parse::<u16>().ok();
+    let days = year.map(event_days).unwrap_or(25);
+    let _ = writeln!(
+        out,
+        "{}",
+        format!("Private leaderboard {}", leaderboard.event).bold()
+    );
+
+    for (rank, member) in leaderboard.standings().into_iter().enumerate() {
+        let _ = write!(
+            out,
+            "{:>3}) {:>5} {:>2}{} {} {}",
+            rank + 1,
+            member.local_score,
+            member.stars,
+            "*".yellow(),
+            star_grid(member, days),
+            member.display_name().bold()
+        );
+        let unlock = year.zip(day).and_then(|(year, day)| unlock_time(year, day));
+        if let (Some(unlock), Some(day)) = (unlock, day) {
+            let unlock = unlock.timestamp();
+            let times = (1..=2)
+                .map(|part| {
+                    member
+                        .star_timestamp(day, part)
+                        .map(|timestamp| format_elapsed(timestamp - unlock))
+                        .unwrap_or_else(|| "--:--:--".to_owned())
+                })
+                .join("  ");
+            let _ = write!(out, "  {}", times.accent());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_private_leaderboard() {
+        let json = r#"{"event":"2023","owner_id":1,"members":{
+            "1":{"id":1,"name":"Alice","stars":3,"local_score":10,"last_star_ts":1701410400,
+                "completion_day_level":{"1":{"1":{"get_star_ts":1701407100,"star_index":0},
+                "2":{"get_star_ts":1701410400,"star_index":1}},"2":{"1":{"get_star_ts":1701493500}}}},
+            "2":{"id":2,"name":null,"stars":1,"local_score":4,"last_star_ts":1701500000,
+                "completion_day_level":{"1":{"1":{"get_star_ts":1701500000}}}}}}"#;
+        let leaderboard: Leaderboard = serde_json::from_str(json).unwrap();
+
+        let standings = leaderboard.standings();
+        assert_eq!(standings[0].display_name(), "Alice");
+        assert_eq!(standings[1].display_name(), "(anonymous user #2)");
+        assert_eq!(unlock_time(2023, 1).unwrap().timestamp(), 1701406800);
+
+        let rendered = render_leaderboard(&leaderboard, Some(1));
+        assert!(rendered.contains("00:05:00"));
+        assert!(rendered.contains("01:00:00"));
+    }
+}
@@ -1,161 +1,190 @@
+// Lets the code generated by the derive macros refer to the crate from within it
+extern crate self as aoc_framework;
+
+// Enters a tracing span until the end of the enclosing scope, when the tracing feature is enabled
+macro_rules! trace_span {
+    ($($span:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($($span)*).entered();
+    };
+}
+
+pub mod alt_inputs;
+pub mod answers;
+#[cfg(feature = "async")]
+mod async_task;
+pub mod baseline;
+pub mod bench;
+pub mod bless;
+#[cfg(feature = "browser")]
+pub mod browser;
+pub mod cancel;
+mod capture;
+pub mod cli;
+pub mod client;
+pub mod compare;
+pub mod config;
+pub mod context;
+#[cfg(feature = "criterion")]
+pub mod criterion;
+pub mod cycle;
+pub mod diff;
+pub mod differential;
 pub mod error;
+pub mod events;
+pub mod example;
+pub mod external;
+pub mod filter;
+pub mod geom;
+pub mod hashed;
+pub mod input;
+pub mod input_files;
+pub mod inspect;
+#[cfg(feature = "leaderboard")]
+pub mod leaderboard;
+pub mod math;
+#[cfg(feature = "memory")]
+pub mod memory;
+pub mod ocr;
+pub mod parse;
+#[cfg(feature = "dhat")]
+pub mod profile;
+pub mod progress;
+pub mod prompt;
+#[cfg(feature = "describe")]
+pub mod puzzle;
+pub mod race;
+pub mod ranges;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod report;
+pub mod reporter;
+mod runner;
+pub mod scaffold;
+pub mod search;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod session;
+pub mod source;
+pub mod state;
+pub mod status;
+pub mod stress;
+pub mod style;
 mod task;
+pub mod testing;
 pub mod traits;
-
-use std::path::PathBuf;
-
-use crossterm::style::Stylize;
-use itertools::Itertools;
-use prettydiff::diff_chars;
-
+#[cfg(feature = "tui")]
+pub mod tui;
+mod typed;
+pub mod unlock;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+#[cfg(feature = "derive")]
+pub use aoc_framework_derive::{AocInput, AocTask};
+#[cfg(feature = "async")]
+pub use async_task::{run_tasks_async, AsyncAocTask, AsyncTask};
+pub use cancel::CancellationToken;
+pub use compare::Comparator;
+pub use context::AocContext;
 use error::AocError;
+pub use events::{run_events, RunEvent};
+pub use example::{Example, ExampleArgs, ExampleMetadata};
+pub use external::ExternalTask;
+pub use filter::Filter;
+#[cfg(feature = "memory")]
+pub use memory::TrackingAllocator;
+pub use progress::ProgressHandle;
+#[cfg(feature = "registry")]
+pub use registry::collect_tasks;
+pub use runner::{run_tasks, AocRunner, RunOptions};
+use style::Symbol;
+pub use style::Theme;
 pub use task::{AocSolution, AocStringIter, AocTask};
-
-pub type BoxedAocTask = Box<dyn AocTask>;
-
-const CROSS: &str = "✘";
-const CHECKMARK: &str = "✔";
-const DOT: &str = "·";
-
-fn solve_task_phase(
-    task: &BoxedAocTask,
-    phase: usize,
-    phases_per_task: usize,
-) -> Result<bool, AocError> {
-    let solution_output = task.solve(phase)?;
-    println!(
-        "{} {} {}:\n{}",
-        DOT.blue(),
-        "Solution for phase".blue(),
-        phase.to_string().dark_yellow(),
-        solution_output.join("\n").blue()
-    );
-
-    let mut solved = task.phase_is_solved(phase);
-
-    if !solved {
-        solved = task.ask_if_solved(phase)?;
-    }
-
-    if !solved {
-        println!(
-            "{} Phase {}/{} of {} {}.",
-            CROSS.dark_red(),
-            phase.to_string().dark_yellow(),
-            phases_per_task.to_string().dark_yellow(),
-            task.name().bold(),
-            "failed".dark_red()
-        );
-        Ok(false)
-    } else {
-        println!(
-            "{} Phase {}/{} of {} {}!",
-            CHECKMARK.dark_green(),
-            phase.to_string().dark_yellow(),
-            phases_per_task.to_string().dark_yellow(),
-            task.name().bold(),
-            "passed".dark_green()
-        );
-        Ok(true)
+pub use typed::{TypedAocTask, TypedTask};
+
+// Tasks are `Send + Sync` and shared, so they can be solved on threads that outlive the run
+pub type SharedAocTask = std::sync::Arc<dyn AocTask>;
+
+const CROSS: Symbol = Symbol::Cross;
+const CHECKMARK: Symbol = Symbol::Checkmark;
+const DOT: Symbol = Symbol::Dot;
+
+// The task of the day in the given year, the year can be left out when only one task is for the day
+pub fn find_task(
+    tasks: &[SharedAocTask],
+    year: Option<u16>,
+    day: u8,
+) -> Result<&SharedAocTask, AocError> {
+    let mut matching = tasks.iter().filter(|task| match year {
+        Some(year) => task.date() == Some((year, day)),
+        None => task.day() == Some(day),
+    });
+    let task = matching.next().ok_or(AocError::MissingTask { day })?;
+    if matching.next().is_some() {
+        return Err(AocError::AmbiguousTask { day });
     }
+    Ok(task)
 }
 
-fn solve_example_phase(
-    task: &BoxedAocTask,
-    example: &(PathBuf, PathBuf),
-    phase: usize,
-) -> Result<bool, AocError> {
-    let example_result = task.run_example_test(example, phase)?;
-    let example_name = example
-        .0
-        .file_name()
-        .map(|name| {
-            let name_str = name.to_string_lossy();
-            name_str[..name_str.len() - 3].to_owned()
-        })
-        .unwrap_or("<failed to parse example name>".into());
-
-    if phase == 1 && !example_result.passed {
-        println!(
-            "{} {} {} the {} test in phase {}.",
-            CROSS.dark_red(),
-            task.name().bold(),
-            "failed".dark_red(),
-            example_name.bold(),
-            phase.to_string().dark_yellow(),
-        );
-        let result = example_result.output.clone().into_iter();
-        let expected = example_result.expected_output.into_iter();
-
-        println!("Diff:");
-        for lines in result.zip_longest(expected) {
-            let (res_line, exp_line) = match lines {
-                itertools::EitherOrBoth::Both(r, e) => (r, e),
-                itertools::EitherOrBoth::Left(r) => (r, Default::default()),
-                itertools::EitherOrBoth::Right(e) => (Default::default(), e),
-            };
-            println!("{}", diff_chars(&res_line, &exp_line));
-        }
-        // Exit early since we printed the diff already and there is no need to print the output
-        return Ok(false);
-    } else if phase == 1 {
-        println!(
-            "{} {} {} the {} test in phase {}!",
-            CHECKMARK.dark_green(),
-            task.name().bold(),
-            "passed".dark_green(),
-            example_name.clone().bold(),
-            phase.to_string().dark_yellow(),
-        );
+// Clears the solved markers of a day, so that its phases are run and confirmed again
+pub fn unsolve(
+    tasks: &[SharedAocTask],
+    year: Option<u16>,
+    day: u8,
+    phase: Option<usize>,
+) -> Result<(), AocError> {
+    let task = find_task(tasks, year, day)?;
+    match phase {
+        Some(phase) if !(1..=task.phases()).contains(&phase) => Err(AocError::MissingPhase {
+            task_name: task.name(),
+            phase,
+            phases: task.phases(),
+        }),
+        Some(phase) => task.mark_phase_as_unsolved(phase),
+        None => (1..=task.phases()).try_for_each(|phase| task.mark_phase_as_unsolved(phase)),
     }
-
-    println!(
-        "{} {} {} {} {}:\n{}",
-        DOT.cyan(),
-        "Output of the".cyan(),
-        example_name.bold(),
-        "test in phase".cyan(),
-        phase.to_string().dark_yellow(),
-        example_result.output.join("\n").cyan()
-    );
-
-    Ok(true)
 }
 
-pub fn check_solved_tasks(
-    tasks: Vec<BoxedAocTask>,
-    phases_per_task: usize,
-) -> Result<bool, AocError> {
-    for (i, task) in tasks.iter().enumerate() {
-        for phase in 1..=phases_per_task {
-            for example in task.example_paths()? {
-                if !solve_example_phase(task, &example, phase)? {
-                    return Ok(false);
-                }
-            }
-
-            if !solve_task_phase(task, phase, phases_per_task)? {
-                return Ok(false);
-            }
-        }
+pub fn check_solved_tasks(tasks: Vec<SharedAocTask>) -> Result<bool, AocError> {
+    let report = AocRunner::new(&tasks).run()?;
+    Ok(report.passed())
+}
 
-        println!(
-            "{}",
-            format!(
-                "{} Task {} - {}/{} done!",
-                CHECKMARK,
-                task.name(),
-                i + 1,
-                tasks.len()
-            )
-            .dark_green()
-        );
-        println!("=================================================");
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::MockTask;
+
+    fn dated_task(year: u16, day: u8) -> SharedAocTask {
+        Arc::new(
+            MockTask::new(|_, _| Ok(vec![]))
+                .with_name(format!("{year} Day {day:02}"))
+                .with_date(year, day)
+                .with_solved(1)
+                .with_solved(2),
+        )
     }
 
-    println!(
-        "{}",
-        "🚀🚀🚀✔️ All tasks have been completed! ✔️🚀🚀🚀".dark_green()
-    );
-    Ok(true)
+    #[test]
+    fn unsolve_the_task_of_the_year() {
+        let tasks = vec![dated_task(2022, 5), dated_task(2023, 5)];
+        assert!(matches!(
+            find_task(&tasks, None, 5),
+            Err(AocError::AmbiguousTask { day: 5 })
+        ));
+        assert!(matches!(
+            unsolve(&tasks, Some(2023), 5, Some(3)),
+            Err(AocError::MissingPhase { phase: 3, .. })
+        ));
+
+        unsolve(&tasks, Some(2023), 5, Some(2)).unwrap();
+        assert!(tasks[0].phase_is_solved(2).unwrap());
+        assert!(tasks[1].phase_is_solved(1).unwrap());
+        assert!(!tasks[1].phase_is_solved(2).unwrap());
+        unsolve(&tasks[..1], None, 5, None).unwrap();
+        assert!(!tasks[0].phase_is_solved(1).unwrap());
+    }
 }
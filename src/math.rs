@@ -0,0 +1,104 @@
+// Number theory for the cycle based puzzles, the results are non-negative
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Zero when either of the numbers is zero
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+// The lowest common multiple of all the numbers, e.g. when all cycles line up again
+pub fn lcm_all(numbers: impl IntoIterator<Item = i64>) -> i64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+// Returns `(gcd, x, y)` such that `a * x + b * y == gcd`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    (old_r as i64, old_x as i64, old_y as i64)
+}
+
+// The inverse of the number modulo the modulus, `None` when they are not coprime
+pub fn mod_inverse(number: i64, modulus: i64) -> Option<i64> {
+    let (gcd, x, _) = extended_gcd(number.rem_euclid(modulus), modulus);
+    (gcd == 1).then(|| x.rem_euclid(modulus))
+}
+
+pub fn mod_pow(base: i64, exponent: u64, modulus: i64) -> i64 {
+    let modulus = modulus as i128;
+    let mut base = (base as i128).rem_euclid(modulus);
+    let mut exponent = exponent;
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as i64
+}
+
+// Solves `x ≡ remainder (mod modulus)` for all the pairs, returning the smallest non-negative
+// `x` and the modulus of all the solutions. The moduli don't have to be coprime, `None` is
+// returned when the congruences contradict each other.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences.into_iter().try_fold(
+        (0i64, 1i64),
+        |(remainder, modulus), (other_remainder, other_modulus)| {
+            let (gcd, x, _) = extended_gcd(modulus, other_modulus);
+            let difference = other_remainder - remainder;
+            if difference % gcd != 0 {
+                return None;
+            }
+            let combined = (modulus as i128 / gcd as i128) * other_modulus as i128;
+            let step = (difference / gcd) as i128 * x as i128 % (other_modulus / gcd) as i128;
+            let solution = (remainder as i128 + modulus as i128 * step).rem_euclid(combined);
+            Some((solution as i64, combined as i64))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_theory() {
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(lcm_all([2, 3, 4, 6]), 12);
+        let (gcd, x, y) = extended_gcd(240, 46);
+        assert_eq!((gcd, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(2, 4), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+
+        // The bus schedule `7,13,x,x,59,x,31,19` of 2020 day 13
+        let buses = [(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
+        let congruences = buses.map(|(bus, offset)| (-offset, bus));
+        assert_eq!(crt(congruences), Some((1068781, 7 * 13 * 59 * 31 * 19)));
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+    }
+}
@@ -0,0 +1,86 @@
+use itertools::Itertools;
+
+const GLYPH_HEIGHT: usize = 6;
+
+// The letters of the font used by the puzzles, without the blank columns around them
+const GLYPHS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+fn is_lit(pixel: char) -> bool {
+    pixel == '#' || pixel == '█'
+}
+
+// Reads the letters drawn by a grid of `#` and `.`, letters are separated by blank columns
+pub fn recognize<S: AsRef<str>>(lines: &[S]) -> Option<String> {
+    if lines.len() != GLYPH_HEIGHT {
+        return None;
+    }
+    let rows = lines
+        .iter()
+        .map(|line| line.as_ref().chars().map(is_lit).collect_vec())
+        .collect_vec();
+    let width = rows.iter().map(Vec::len).max()?;
+    let lit = |row: usize, column: usize| rows[row].get(column).copied().unwrap_or(false);
+    let blank_column = |column: usize| (0..GLYPH_HEIGHT).all(|row| !lit(row, column));
+
+    let mut text = String::new();
+    let mut column = 0;
+    while column < width {
+        if blank_column(column) {
+            column += 1;
+            continue;
+        }
+        let start = column;
+        while column < width && !blank_column(column) {
+            column += 1;
+        }
+        let glyph = (0..GLYPH_HEIGHT)
+            .map(|row| {
+                (start..column)
+                    .map(|column| if lit(row, column) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .join("\n");
+        let (letter, _) = GLYPHS.iter().find(|(_, pixels)| *pixels == glyph)?;
+        text.push(*letter);
+    }
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognize_letters() {
+        let grid = [
+            "#..#.###...##..#...#",
+            "#..#.#..#.#..#.#...#",
+            "####.###..#.....#.#.",
+            "#..#.#..#.#......#..",
+            "#..#.#..#.#..#...#..",
+            "#..#.###...##....#..",
+        ];
+        assert_eq!(recognize(&grid), Some("HBCY".to_owned()));
+        assert_eq!(recognize(&grid[..5]), None);
+        assert_eq!(recognize(&["#....."; 6]), None);
+    }
+}
@@ -0,0 +1,143 @@
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct FormatError {
+    pub message: String,
+}
+
+impl FormatError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+// Splits the text into the fields between the literal parts of a format, e.g. the literals
+// `["Game ", ": ", ""]` split `Game 3: 4 red` into `["3", "4 red"]`. Every field ends at the
+// first occurrence of the literal that follows it, the last one at the end of the text.
+pub fn split_format<'t>(text: &'t str, literals: &[&str]) -> Result<Vec<&'t str>, FormatError> {
+    let mismatch = |literal: &str| FormatError::new(format!("Expected `{literal}` in `{text}`"));
+    let Some((prefix, literals)) = literals.split_first() else {
+        return Ok(vec![]);
+    };
+    let mut rest = text.strip_prefix(prefix).ok_or_else(|| mismatch(prefix))?;
+    let mut fields = Vec::with_capacity(literals.len());
+    for (index, literal) in literals.iter().enumerate() {
+        let end = match (index + 1 == literals.len(), literal.is_empty()) {
+            (true, _) => rest
+                .strip_suffix(literal)
+                .ok_or_else(|| mismatch(literal))?
+                .len(),
+            (false, true) => return Err(FormatError::new("Fields must be separated".to_owned())),
+            (false, false) => rest.find(literal).ok_or_else(|| mismatch(literal))?,
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    Ok(fields)
+}
+
+// Parses a single field, the surrounding whitespace is ignored
+pub fn parse_field<T>(text: &str, field: &str) -> Result<T, FormatError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let text = text.trim();
+    text.parse()
+        .map_err(|err| FormatError::new(format!("Invalid {field} `{text}`: {err}")))
+}
+
+// Parses a field holding a list of values, e.g. `4 red, 5 blue` split at `, `
+pub fn parse_list<C, T>(text: &str, separator: &str, field: &str) -> Result<C, FormatError>
+where
+    C: FromIterator<T>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(C::from_iter([]));
+    }
+    text.split(separator)
+        .map(|item| parse_field(item, field))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_split_into_fields() {
+        assert_eq!(
+            split_format("Game 3: 4 red, 5 blue", &["Game ", ": ", ""]).unwrap(),
+            ["3", "4 red, 5 blue"]
+        );
+        assert_eq!(
+            split_format("p=1,2 v=-3,4", &["p=", ",", " v=", ",", ""]).unwrap(),
+            ["1", "2", "-3", "4"]
+        );
+        assert!(split_format("Round 3: 4", &["Game ", ": ", ""]).is_err());
+        assert_eq!(
+            parse_list::<Vec<u8>, _>("1, 2,3", ",", "numbers").unwrap(),
+            [1, 2, 3]
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_inputs_parse_lines() {
+        use crate::AocInput;
+
+        #[derive(AocInput, Debug, PartialEq)]
+        enum Color {
+            Red,
+            Blue,
+            #[aoc_input(format = "g")]
+            Green,
+        }
+
+        #[derive(AocInput, Debug, PartialEq)]
+        #[aoc_input(format = "{count} {color}")]
+        struct Cubes {
+            count: u32,
+            color: Color,
+        }
+
+        #[derive(AocInput, Debug, PartialEq)]
+        #[aoc_input(format = "Game {id}: {cubes}")]
+        struct Game {
+            id: u32,
+            #[aoc_input(split = ", ")]
+            cubes: Vec<Cubes>,
+        }
+
+        let game: Game = "Game 3: 4 red, 5 blue, 1 g".parse().unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(
+            game.cubes,
+            [
+                Cubes {
+                    count: 4,
+                    color: Color::Red
+                },
+                Cubes {
+                    count: 5,
+                    color: Color::Blue
+                },
+                Cubes {
+                    count: 1,
+                    color: Color::Green
+                },
+            ]
+        );
+        let err = "Game 3: 4 purple".parse::<Game>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid cubes `4 purple`: Invalid color `purple`: Expected one of `red`, `blue`, `g`"
+        );
+    }
+}
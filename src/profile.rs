@@ -0,0 +1,82 @@
+use std::path::{Path, PathBuf};
+
+pub use dhat::Alloc as DhatAllocator;
+
+use crate::{
+    error::AocError,
+    filter::Filter,
+    runner::{filter_tasks, task_phases},
+    style::Stylize,
+    AocTask, SharedAocTask, CROSS, DOT,
+};
+
+pub fn profile_path(task: &(impl AocTask + ?Sized), phase: usize) -> PathBuf {
+    task.data_directory()
+        .join(format!("dhat-heap-phase-{phase}.json"))
+}
+
+// Profiling a single phase keeps the profile readable, the filter has to select exactly one
+pub fn select_phase<'t>(
+    tasks: &'t [SharedAocTask],
+    filter: &Filter,
+) -> Result<(&'t SharedAocTask, usize), AocError> {
+    let phases = filter_tasks(tasks, filter)
+        .into_iter()
+        .flat_map(|task| {
+            task_phases(task, filter)
+                .into_iter()
+                .map(move |phase| (task, phase))
+        })
+        .collect::<Vec<_>>();
+    match phases[..] {
+        [phase] => Ok(phase),
+        _ => Err(AocError::AmbiguousFilter {
+            count: phases.len(),
+        }),
+    }
+}
+
+// The heap is only profiled when `DhatAllocator` is the global allocator of the binary:
+// `#[global_allocator] static ALLOCATOR: DhatAllocator = DhatAllocator;`
+pub fn profile_phase(task: &SharedAocTask, phase: usize, path: &Path) -> Result<(), AocError> {
+    println!(
+        "{} Profiling the heap of {} phase {}",
+        DOT.info(),
+        task.name().bold(),
+        phase.to_string().highlight()
+    );
+    let profiler = dhat::Profiler::builder().file_name(path).build();
+    let result = task.solve(phase);
+    let stats = dhat::HeapStats::get();
+    // The profile is written when the profiler is dropped
+    drop(profiler);
+    result?;
+
+    if stats.total_blocks == 0 {
+        eprintln!(
+            "{} No allocations were recorded, install {} as the global allocator",
+            CROSS.failure(),
+            "aoc_framework::profile::DhatAllocator".bold()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn filter_selects_a_single_phase() {
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(MockTask::new(|_, _| Ok(vec![])))];
+        let (_, phase) = select_phase(&tasks, &Filter::all().phase(2)).unwrap();
+        assert_eq!(phase, 2);
+        assert!(matches!(
+            select_phase(&tasks, &Filter::all()),
+            Err(AocError::AmbiguousFilter { count: 2 })
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+use std::{borrow::Cow, cell::RefCell, sync::OnceLock, time::Duration};
+#[cfg(unix)]
+use std::{fs::File, io};
+
+#[cfg(unix)]
+use console::Term;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+const SPINNER_TEMPLATE: &str = "{spinner:.blue} {msg} [{elapsed_precise}]";
+const BAR_TEMPLATE: &str =
+    "{spinner:.blue} {msg} [{elapsed_precise}] {wide_bar:.blue/white} {pos}/{len} ({eta})";
+
+thread_local! {
+    static CURRENT: RefCell<Option<ProgressBar>> = const { RefCell::new(None) };
+}
+
+// Bars of phases solved on worker threads share a single set of terminal lines
+fn bars() -> &'static MultiProgress {
+    static BARS: OnceLock<MultiProgress> = OnceLock::new();
+    BARS.get_or_init(|| MultiProgress::with_draw_target(draw_target()))
+}
+
+// The output of solutions is captured by redirecting stderr, so the bars draw to a duplicate
+// of the original stderr instead
+#[cfg(unix)]
+fn draw_target() -> ProgressDrawTarget {
+    use std::os::fd::AsFd;
+
+    match io::stderr().as_fd().try_clone_to_owned() {
+        Ok(stderr) => {
+            ProgressDrawTarget::term(Term::read_write_pair(io::stdin(), File::from(stderr)), 20)
+        }
+        Err(_) => ProgressDrawTarget::stderr(),
+    }
+}
+
+#[cfg(not(unix))]
+fn draw_target() -> ProgressDrawTarget {
+    ProgressDrawTarget::stderr()
+}
+
+// Has to be called before stderr is redirected for the first time
+pub(crate) fn init() {
+    bars();
+}
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template).unwrap_or_else(|_| ProgressStyle::default_spinner())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProgressHandle {
+    bar: Option<ProgressBar>,
+}
+
+impl ProgressHandle {
+    // Returns the handle of the phase solved on the current thread, or a handle that ignores
+    // every update when no phase is being solved
+    pub fn current() -> Self {
+        Self {
+            bar: CURRENT.with(|current| current.borrow().clone()),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.bar.is_some()
+    }
+
+    pub fn set_length(&self, length: u64) {
+        if let Some(bar) = &self.bar {
+            bar.set_style(style(BAR_TEMPLATE));
+            bar.set_length(length);
+        }
+    }
+
+    pub fn set_position(&self, position: u64) {
+        if let Some(bar) = &self.bar {
+            bar.set_position(position);
+        }
+    }
+
+    pub fn inc(&self, delta: u64) {
+        if let Some(bar) = &self.bar {
+            bar.inc(delta);
+        }
+    }
+
+    pub fn set_message(&self, message: impl Into<Cow<'static, str>>) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(message);
+        }
+    }
+}
+
+pub(crate) struct PhaseProgress {
+    bar: ProgressBar,
+    previous: Option<ProgressBar>,
+}
+
+impl PhaseProgress {
+    pub(crate) fn start(task_name: &str, phase: usize) -> Self {
+        let bar = bars().add(ProgressBar::new_spinner());
+        bar.set_style(style(SPINNER_TEMPLATE));
+        bar.set_message(format!("{task_name} phase {phase}"));
+        bar.enable_steady_tick(Duration::from_millis(100));
+        let previous = CURRENT.with(|current| current.replace(Some(bar.clone())));
+        Self { bar, previous }
+    }
+}
+
+impl Drop for PhaseProgress {
+    fn drop(&mut self) {
+        self.bar.finish_and_clear();
+        bars().remove(&self.bar);
+        CURRENT.with(|current| *current.borrow_mut() = self.previous.take());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handle_follows_the_solved_phase() {
+        assert!(!ProgressHandle::current().is_active());
+        {
+            let _progress = PhaseProgress::start("Sum Task", 1);
+            let handle = ProgressHandle::current();
+            assert!(handle.is_active());
+            handle.set_length(10);
+            handle.inc(3);
+            assert_eq!(handle.bar.as_ref().map(ProgressBar::position), Some(3));
+        }
+        assert!(!ProgressHandle::current().is_active());
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use dialoguer::{theme::ColorfulTheme, Confirm, Select};
+
+use crate::error::AocError;
+
+const NO_DEFAULT: u8 = 0;
+const DEFAULT_YES: u8 = 1;
+const DEFAULT_NO: u8 = 2;
+
+static DEFAULT_ANSWER: AtomicU8 = AtomicU8::new(NO_DEFAULT);
+
+// How the runner confirms the answers of unsolved phases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfirmPolicy {
+    #[default]
+    Ask,
+    // Every answer is accepted without asking, for scripted runs that submit the answers
+    AcceptAll,
+    // Answers are never confirmed and stay unconfirmed
+    Skip,
+}
+
+impl ConfirmPolicy {
+    pub fn parse(policy: &str) -> Option<Self> {
+        match policy {
+            "ask" => Some(ConfirmPolicy::Ask),
+            "accept" => Some(ConfirmPolicy::AcceptAll),
+            "skip" => Some(ConfirmPolicy::Skip),
+            _ => None,
+        }
+    }
+
+    // There is nobody to ask without a terminal, e.g. when stdin is piped
+    pub fn resolve(self) -> Self {
+        match self {
+            ConfirmPolicy::Ask if !io::stdin().is_terminal() => ConfirmPolicy::Skip,
+            policy => policy,
+        }
+    }
+
+    // Asks only when the policy says so, the other policies answer without asking
+    pub fn confirm(self, prompt: String) -> Result<bool, AocError> {
+        match self.resolve() {
+            ConfirmPolicy::Ask => confirm(prompt),
+            ConfirmPolicy::AcceptAll => Ok(true),
+            ConfirmPolicy::Skip => Ok(false),
+        }
+    }
+}
+
+// The answer picked by pressing enter, the prompts need an explicit answer without it. The
+// default is process-wide, it applies to the prompts of the tasks as well.
+pub fn set_default_answer(default: Option<bool>) {
+    let default = match default {
+        None => NO_DEFAULT,
+        Some(true) => DEFAULT_YES,
+        Some(false) => DEFAULT_NO,
+    };
+    DEFAULT_ANSWER.store(default, Ordering::Relaxed);
+}
+
+pub fn default_answer() -> Option<bool> {
+    match DEFAULT_ANSWER.load(Ordering::Relaxed) {
+        DEFAULT_YES => Some(true),
+        DEFAULT_NO => Some(false),
+        _ => None,
+    }
+}
+
+// Without a terminal the default answer is taken, or no without one
+pub fn confirm(prompt: String) -> Result<bool, AocError> {
+    if !io::stdin().is_terminal() {
+        return Ok(default_answer().unwrap_or(false));
+    }
+    let theme = ColorfulTheme::default();
+    let mut confirm = Confirm::with_theme(&theme).with_prompt(prompt);
+    if let Some(default) = default_answer() {
+        confirm = confirm.default(default);
+    }
+    confirm
+        .interact()
+        .map_err(|dialog_err| AocError::UserInterractionError { source: dialog_err })
+}
+
+// The index of the picked item, nothing is picked without a terminal
+pub fn select(prompt: String, items: &[&str]) -> Result<Option<usize>, AocError> {
+    if !io::stdin().is_terminal() {
+        return Ok(None);
+    }
+    Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(items)
+        .default(0)
+        .interact()
+        .map(Some)
+        .map_err(|dialog_err| AocError::UserInterractionError { source: dialog_err })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_answers_and_policies() {
+        let _settings = crate::testing::lock_global_settings();
+        assert_eq!(default_answer(), None);
+        set_default_answer(Some(false));
+        assert_eq!(default_answer(), Some(false));
+        set_default_answer(None);
+        assert_eq!(default_answer(), None);
+
+        assert_eq!(
+            ConfirmPolicy::parse("accept"),
+            Some(ConfirmPolicy::AcceptAll)
+        );
+        assert_eq!(ConfirmPolicy::parse("yes"), None);
+        assert_eq!(ConfirmPolicy::Skip.resolve(), ConfirmPolicy::Skip);
+        assert_eq!(ConfirmPolicy::AcceptAll.resolve(), ConfirmPolicy::AcceptAll);
+        assert!(ConfirmPolicy::AcceptAll
+            .confirm("Accept?".to_owned())
+            .unwrap());
+        assert!(!ConfirmPolicy::Skip.confirm("Accept?".to_owned()).unwrap());
+    }
+}
@@ -0,0 +1,329 @@
+use std::{fs, path::PathBuf};
+
+use crossterm::style::StyledContent;
+use ego_tree::NodeRef;
+use itertools::Itertools;
+use scraper::{ElementRef, Html, Node, Selector};
+
+use crate::{
+    client::AocClient, error::AocError, prompt::ConfirmPolicy, style::Stylize, AocTask, CHECKMARK,
+    CROSS,
+};
+
+pub const PARTS_PER_DAY: usize = 2;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct InlineStyle {
+    emphasis: bool,
+    code: bool,
+    link: bool,
+}
+
+fn styled_text(text: &str, style: InlineStyle) -> StyledContent<&str> {
+    let mut styled = text.stylize();
+    if style.code {
+        styled = styled.yellow();
+    }
+    if style.emphasis {
+        styled = styled.white().bold();
+    }
+    if style.link {
+        styled = styled.underlined();
+    }
+    styled
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut after_whitespace = false;
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            collapsed.push(c);
+        } else if !after_whitespace {
+            collapsed.push(' ');
+        }
+        after_whitespace = c.is_whitespace();
+    }
+    collapsed
+}
+
+fn render_inline(node: NodeRef<Node>, style: InlineStyle, preformatted: bool, out: &mut String) {
+    match node.value() {
+        Node::Text(text) if preformatted => out.push_str(&styled_text(text, style).to_string()),
+        Node::Text(text) => {
+            let text = collapse_whitespace(text);
+            out.push_str(&styled_text(&text, style).to_string());
+        }
+        Node::Element(element) => {
+            let style = match element.name() {
+                "em" => InlineStyle {
+                    emphasis: true,
+                    ..style
+                },
+                "code" if !preformatted => InlineStyle {
+                    code: true,
+                    ..style
+                },
+                "a" => InlineStyle {
+                    link: true,
+                    ..style
+                },
+                "br" => {
+                    out.push('\n');
+                    return;
+                }
+                _ => style,
+            };
+            for child in node.children() {
+                render_inline(child, style, preformatted, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn inline_text(node: NodeRef<Node>, preformatted: bool) -> String {
+    let mut text = String::new();
+    render_inline(node, InlineStyle::default(), preformatted, &mut text);
+    text
+}
+
+fn render_block(node: NodeRef<Node>, out: &mut String) {
+    let Some(element) = ElementRef::wrap(node) else {
+        if node
+            .value()
+            .as_text()
+            .is_some_and(|text| !text.trim().is_empty())
+        {
+            out.push_str(inline_text(node, false).trim());
+            out.push_str("\n\n");
+        }
+        return;
+    };
+
+    match element.value().name() {
+        "html" | "body" | "article" | "div" => {
+            for child in node.children() {
+                render_block(child, out);
+            }
+        }
+        "h2" => {
+            let title = element.text().collect::<String>();
+            out.push_str(&format!("{}\n\n", title.trim().success().bold()));
+        }
+        "pre" => {
+            for line in inline_text(node, true).lines() {
+                out.push_str(&format!("    {line}\n"));
+            }
+            out.push('\n');
+        }
+        "ul" | "ol" => {
+            for item in element.children().filter(|child| {
+                ElementRef::wrap(*child).is_some_and(|item| item.value().name() == "li")
+            }) {
+                out.push_str(&format!("  - {}\n", inline_text(item, false).trim()));
+            }
+            out.push('\n');
+        }
+        _ => {
+            out.push_str(inline_text(node, false).trim());
+            out.push_str("\n\n");
+        }
+    }
+}
+
+pub fn render_description(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut out = String::new();
+    render_block(*fragment.root_element(), &mut out);
+    out.trim_end().to_owned()
+}
+
+fn part_cache_path(year: u16, day: u8, part: usize) -> String {
+    format!("/{year}/day/{day}/part_{part}.html")
+}
+
+// The description of the second part is only included in the page after solving the first one,
+// so each part is cached separately once it is available
+pub fn description_parts(
+    client: &AocClient,
+    year: u16,
+    day: u8,
+    parts: usize,
+) -> Result<Vec<String>, AocError> {
+    let cached = (1..=parts)
+        .map_while(|part| client.read_cache(&part_cache_path(year, day, part)))
+        .collect_vec();
+    if cached.len() == parts {
+        return Ok(cached);
+    }
+
+    let page = client.get(&format!("/{year}/day/{day}"))?;
+    let document = Html::parse_document(&page);
+    let selector = Selector::parse("article.day-desc").expect("the selector is valid");
+    let fetched = document
+        .select(&selector)
+        .map(|article| article.html())
+        .collect_vec();
+    for (index, part) in fetched.iter().enumerate() {
+        client.write_cache(&part_cache_path(year, day, index + 1), part)?;
+    }
+    Ok(fetched.into_iter().take(parts).collect())
+}
+
+pub fn describe(client: &AocClient, year: u16, day: u8, parts: usize) -> Result<String, AocError> {
+    Ok(description_parts(client, year, day, parts)?
+        .iter()
+        .map(|part| render_description(part))
+        .join("\n\n"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedExample {
+    pub input: String,
+    pub expected_output: Option<String>,
+}
+
+// Examples are usually the largest code block of the description, while the expected answer is
+// the last emphasized code snippet
+pub fn extract_example(part_html: &str) -> Option<ExtractedExample> {
+    let fragment = Html::parse_fragment(part_html);
+    let code_block = Selector::parse("pre > code").expect("the selector is valid");
+    let answer = Selector::parse("code > em, em > code").expect("the selector is valid");
+
+    let input = fragment
+        .select(&code_block)
+        .map(|code| code.text().collect::<String>())
+        .max_by_key(|code| code.len())?;
+    let expected_output = fragment
+        .select(&answer)
+        .map(|answer| answer.text().collect::<String>().trim().to_owned())
+        .rfind(|answer| !answer.is_empty());
+
+    Some(ExtractedExample {
+        input,
+        expected_output,
+    })
+}
+
+fn has_examples(task: &dyn AocTask) -> bool {
+    !task.inline_examples().is_empty()
+        || task.example_paths().is_ok_and(|examples| {
+            examples.iter().any(|(input_path, _)| {
+                fs::metadata(input_path).is_ok_and(|metadata| metadata.len() > 0)
+            })
+        })
+}
+
+fn write_example(
+    task: &dyn AocTask,
+    example: &ExtractedExample,
+) -> Result<(PathBuf, PathBuf), AocError> {
+    let data_directory = task.data_directory();
+    fs::create_dir_all(&data_directory).map_err(|err| AocError::IOWriteError {
+        path: data_directory.to_string_lossy().to_string(),
+        source: err,
+    })?;
+    let input_path = data_directory.join("example_1_in");
+    let output_path = data_directory.join("example_1_out");
+    for (path, content) in [
+        (&input_path, example.input.as_str()),
+        (
+            &output_path,
+            example.expected_output.as_deref().unwrap_or(""),
+        ),
+    ] {
+        fs::write(path, content).map_err(|err| AocError::IOWriteError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+    }
+    Ok((input_path, output_path))
+}
+
+// Offers to fill in the first example of a task without any, or with only the empty scaffolded ones
+pub(crate) fn offer_example_extraction(
+    task: &dyn AocTask,
+    policy: ConfirmPolicy,
+) -> Result<(), AocError> {
+    if has_examples(task) {
+        return Ok(());
+    }
+    let Some((year, day)) = task.date() else {
+        return Ok(());
+    };
+
+    let extract = policy.confirm(format!(
+        "{} has no examples, extract one from the puzzle description?",
+        task.name()
+    ))?;
+    if !extract {
+        return Ok(());
+    }
+
+    let parts = description_parts(&AocClient::load()?, year, day, 1)?;
+    let Some(example) = parts.first().and_then(|part| extract_example(part)) else {
+        println!(
+            "{} {}",
+            CROSS.failure(),
+            "Could not find an example in the puzzle description".failure()
+        );
+        return Ok(());
+    };
+
+    let (input_path, output_path) = write_example(task, &example)?;
+    println!(
+        "{} Extracted the example into {}",
+        CHECKMARK.success(),
+        input_path.to_string_lossy().bold()
+    );
+    if example.expected_output.is_none() {
+        println!(
+            "{} Could not find the expected answer, fill in {} manually",
+            CROSS.failure(),
+            output_path.to_string_lossy().bold()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_puzzle_description() {
+        let html = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+            <p>The newly-improved calibration document
+            consists of lines of text; <em>each line</em> contains a <code>value</code>.</p>
+            <pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+            <ul><li>First</li><li>Second</li></ul></article>"#;
+
+        let rendered = render_description(html);
+        assert!(rendered.contains("--- Day 1: Trebuchet?! ---"));
+        assert!(rendered.contains("The newly-improved calibration document consists of lines"));
+        assert!(rendered.contains("    1abc2\n    pqr3stu8vwx\n"));
+        assert!(rendered.contains("  - First\n  - Second"));
+    }
+
+    #[test]
+    fn extract_example_and_answer() {
+        let html = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+            <p>For example, <code>12</code> and:</p>
+            <pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+            <pre><code>ab</code></pre>
+            <p>Adding these together produces <code><em>142</em></code>.</p></article>"#;
+
+        assert_eq!(
+            extract_example(html),
+            Some(ExtractedExample {
+                input: "1abc2\npqr3stu8vwx\n".to_owned(),
+                expected_output: Some("142".to_owned()),
+            })
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::{error::AocError, state, unlock::unlock_time, AocTask};
+
+fn puzzle_unlock(task: &dyn AocTask) -> Option<DateTime<Utc>> {
+    task.date().and_then(|(year, day)| unlock_time(year, day))
+}
+
+// Races start when the puzzle unlocks, tasks without a known date, or with a date without a
+// puzzle, start on their first run
+pub fn race_start(task: &dyn AocTask) -> Option<DateTime<Utc>> {
+    if let Some(unlock) = puzzle_unlock(task) {
+        return Some(unlock);
+    }
+    let started = state::with_task_state(task, |state| state.started).ok()??;
+    DateTime::from_timestamp(started, 0)
+}
+
+pub(crate) fn record_first_run(task: &dyn AocTask) -> Result<(), AocError> {
+    if puzzle_unlock(task).is_some() {
+        return Ok(());
+    }
+    state::with_task_state(task, |state| {
+        state.started.get_or_insert(Utc::now().timestamp());
+    })
+}
+
+pub fn solve_time(task: &dyn AocTask, phase: usize) -> Option<DateTime<Utc>> {
+    let solved = state::with_task_state(task, |state| state.solved.get(&phase).copied()).ok()??;
+    DateTime::from_timestamp(solved, 0)
+}
+
+pub fn race_time(task: &dyn AocTask, phase: usize) -> Option<Duration> {
+    (solve_time(task, phase)? - race_start(task)?).to_std().ok()
+}
+
+pub fn format_race_time(race_time: Duration) -> String {
+    let seconds = race_time.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn race_time_from_first_run() {
+        let task = MockTask::new(|_, _| Ok(vec![])).with_name("Race Task");
+        record_first_run(&task).unwrap();
+        let start = race_start(&task).unwrap();
+        state::with_task_state(&task, |state| {
+            state.solved.insert(1, start.timestamp() + 3725);
+        })
+        .unwrap();
+
+        assert_eq!(race_time(&task, 1), Some(Duration::from_secs(3725)));
+        assert_eq!(race_time(&task, 2), None);
+        assert_eq!(format_race_time(Duration::from_secs(3725)), "01:02:05");
+    }
+
+    #[test]
+    fn dates_without_a_puzzle_race_from_the_first_run() {
+        let task = MockTask::new(|_, _| Ok(vec![])).with_date(2025, 20);
+        assert_eq!(race_start(&task), None);
+        record_first_run(&task).unwrap();
+        assert!(race_start(&task).is_some());
+
+        let task = MockTask::new(|_, _| Ok(vec![])).with_date(2023, 1);
+        record_first_run(&task).unwrap();
+        assert_eq!(race_start(&task), unlock_time(2023, 1));
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+// An inclusive range of integers, empty ranges cannot be represented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    // `None` when the end is before the start
+    pub fn new(start: i64, end: i64) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    // The range of `length` values from `start`, e.g. the seed ranges of 2023 day 5
+    pub fn with_length(start: i64, length: i64) -> Option<Self> {
+        Self::new(start, start + length - 1)
+    }
+
+    // The number of values in the interval
+    pub fn size(&self) -> i64 {
+        self.end - self.start + 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        (self.start..=self.end).contains(&value)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    // A single interval when the two overlap or touch, e.g. `1..=3` and `4..=6`
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        (self.start <= other.end + 1 && other.start <= self.end + 1).then(|| Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+
+    // The parts of the interval outside of the other one, at most one on each side
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+        [
+            Self::new(self.start, other.start - 1),
+            Self::new(other.end + 1, self.end),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    // Splits before the value, into the values smaller than it and the rest
+    pub fn split_at(&self, value: i64) -> (Option<Self>, Option<Self>) {
+        (
+            Self::new(self.start, self.end.min(value - 1)),
+            Self::new(self.start.max(value), self.end),
+        )
+    }
+
+    pub fn shift(&self, offset: i64) -> Self {
+        Self {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.start, self.end)
+    }
+}
+
+// Disjoint intervals kept sorted and merged, for counting how much of a line is covered
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let first = self
+            .intervals
+            .partition_point(|existing| existing.end + 1 < interval.start);
+        let mut merged = interval;
+        let mut last = first;
+        while let Some(union) = self
+            .intervals
+            .get(last)
+            .and_then(|existing| existing.union(&merged))
+        {
+            merged = union;
+            last += 1;
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|existing| existing.difference(&interval))
+            .collect();
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.intervals
+            .binary_search_by(|interval| {
+                if interval.end < value {
+                    Ordering::Less
+                } else if interval.start > value {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    // The number of values covered by the intervals
+    pub fn coverage(&self) -> i64 {
+        self.intervals.iter().map(Interval::size).sum()
+    }
+
+    // The uncovered intervals between the first and the last covered value
+    pub fn gaps(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals
+            .windows(2)
+            .filter_map(|pair| Interval::new(pair[0].end + 1, pair[1].start - 1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(intervals: T) -> Self {
+        let mut set = Self::new();
+        intervals
+            .into_iter()
+            .for_each(|interval| set.insert(interval));
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i64, end: i64) -> Interval {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn intervals_merge_and_split() {
+        let seeds = Interval::with_length(79, 14).unwrap();
+        assert_eq!(seeds, interval(79, 92));
+        assert_eq!(
+            seeds.intersection(&interval(90, 100)),
+            Some(interval(90, 92))
+        );
+        assert_eq!(seeds.intersection(&interval(93, 100)), None);
+        assert_eq!(
+            seeds.difference(&interval(80, 90)),
+            [interval(79, 79), interval(91, 92)]
+        );
+        assert_eq!(seeds.split_at(79), (None, Some(seeds)));
+        assert_eq!(interval(1, 3).union(&interval(4, 6)), Some(interval(1, 6)));
+
+        let mut covered = [
+            interval(12, 12),
+            interval(2, 14),
+            interval(16, 24),
+            interval(-2, 2),
+        ]
+        .into_iter()
+        .collect::<IntervalSet>();
+        assert_eq!(
+            covered.iter().collect::<Vec<_>>(),
+            [&interval(-2, 14), &interval(16, 24)]
+        );
+        assert_eq!(covered.coverage(), 26);
+        assert_eq!(covered.gaps().collect::<Vec<_>>(), [interval(15, 15)]);
+        covered.remove(interval(0, 20));
+        assert_eq!(covered.coverage(), 6);
+        assert!(covered.contains(-1) && !covered.contains(15) && covered.contains(24));
+    }
+}
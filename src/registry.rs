@@ -0,0 +1,78 @@
+use linkme::distributed_slice;
+
+use crate::SharedAocTask;
+
+#[doc(hidden)]
+pub use linkme;
+
+// Filled by `register_task!` at link time, from any module of the final binary
+#[distributed_slice]
+pub static TASKS: [fn() -> SharedAocTask];
+
+// Registers a task, so that it doesn't have to be listed by hand:
+// `register_task!(Day01);` or `register_task!(TypedTask::new(Day02));`
+#[macro_export]
+macro_rules! register_task {
+    ($task:expr) => {
+        const _: () = {
+            #[$crate::registry::linkme::distributed_slice($crate::registry::TASKS)]
+            #[linkme(crate = $crate::registry::linkme)]
+            static TASK: fn() -> $crate::SharedAocTask = || ::std::sync::Arc::new($task);
+        };
+    };
+}
+
+// The registered tasks, ordered by their year and day, tasks without a date come last
+pub fn collect_tasks() -> Vec<SharedAocTask> {
+    let mut tasks = TASKS.iter().map(|task| task()).collect::<Vec<_>>();
+    tasks.sort_by_cached_key(|task| {
+        let date = task.date();
+        (date.is_none(), date, task.name())
+    });
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::PathBuf};
+
+    use super::*;
+    use crate::{AocContext, AocSolution, AocStringIter, AocTask};
+
+    struct RegisteredTask(&'static str);
+
+    impl AocTask for RegisteredTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from(self.0)
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(input.collect())
+        }
+    }
+
+    register_task!(RegisteredTask("tests/registered_task"));
+    register_task!(RegisteredTask("aoc_2023/day_07"));
+    register_task!(RegisteredTask("aoc_2022/day_25"));
+
+    #[test]
+    fn registered_tasks_in_day_order() {
+        let directories = collect_tasks()
+            .iter()
+            .map(|task| task.directory())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            directories,
+            [
+                "aoc_2022/day_25",
+                "aoc_2023/day_07",
+                "tests/registered_task"
+            ]
+            .map(PathBuf::from)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::{path::PathBuf, time::Duration};
+
+use itertools::Itertools;
+
+use crate::{answers, diff, AocSolution};
+
+#[cfg(feature = "serde")]
+mod duration_secs {
+    use std::time::Duration;
+
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub mod option {
+        use std::time::Duration;
+
+        use serde::Serializer;
+
+        pub fn serialize<S: Serializer>(
+            duration: &Option<Duration>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match duration {
+                Some(duration) => serializer.serialize_some(&duration.as_secs_f64()),
+                None => serializer.serialize_none(),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExampleReport {
+    pub name: String,
+    // Inline examples have no input file
+    pub input_path: Option<PathBuf>,
+    pub checked: bool,
+    pub passed: bool,
+    pub output: AocSolution,
+    pub expected_output: AocSolution,
+    pub captured_output: String,
+    #[cfg_attr(feature = "serde", serde(with = "duration_secs"))]
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PhaseStatus {
+    Solved,
+    Unsolved,
+    Unconfirmed,
+    ExampleFailed,
+    RejectedAnswer,
+    Regressed,
+    PreviouslySolved,
+    Cancelled,
+    TimedOut,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PhaseReport {
+    pub phase: usize,
+    pub examples: Vec<ExampleReport>,
+    pub status: PhaseStatus,
+    pub output: Option<AocSolution>,
+    pub rejection: Option<String>,
+    pub accepted_answer: Option<String>,
+    pub captured_output: String,
+    #[cfg_attr(feature = "serde", serde(with = "duration_secs::option"))]
+    pub duration: Option<Duration>,
+    #[cfg_attr(feature = "serde", serde(with = "duration_secs::option"))]
+    pub race_time: Option<Duration>,
+    // Only measured when the tracking allocator is installed
+    #[cfg(feature = "memory")]
+    pub memory: Option<crate::memory::MemoryStats>,
+}
+
+impl PhaseReport {
+    // A phase without any results yet, which are filled in as they come
+    pub fn new(phase: usize, status: PhaseStatus) -> Self {
+        Self {
+            phase,
+            examples: vec![],
+            status,
+            output: None,
+            rejection: None,
+            accepted_answer: None,
+            captured_output: String::new(),
+            duration: None,
+            race_time: None,
+            #[cfg(feature = "memory")]
+            memory: None,
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        matches!(
+            self.status,
+            PhaseStatus::Solved | PhaseStatus::Unconfirmed | PhaseStatus::PreviouslySolved
+        )
+    }
+
+    // The accepted answer and the answer of the output, line by line, for diffing them
+    pub fn answer_lines(&self) -> Option<(AocSolution, AocSolution)> {
+        let lines = |answer: &str| answer.lines().map(str::to_owned).collect();
+        let accepted = self.accepted_answer.as_deref()?;
+        let answer = answers::answer_text(self.output.as_ref()?);
+        Some((lines(accepted), lines(&answer)))
+    }
+
+    // The plain diff of a regressed answer, or of the failed examples
+    pub fn diff(&self) -> Option<String> {
+        if self.status == PhaseStatus::Regressed {
+            let (accepted, answer) = self.answer_lines()?;
+            return Some(diff::render_plain(&accepted, &answer));
+        }
+        let diffs = self
+            .examples
+            .iter()
+            .filter(|example| example.checked && !example.passed)
+            .map(|example| {
+                format!(
+                    "{}:\n{}",
+                    example.name,
+                    diff::render_plain(&example.expected_output, &example.output)
+                )
+            })
+            .join("\n");
+        (!diffs.is_empty()).then_some(diffs)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TaskReport {
+    pub name: String,
+    pub directory: PathBuf,
+    pub phases: Vec<PhaseReport>,
+}
+
+impl TaskReport {
+    pub fn passed(&self) -> bool {
+        self.phases.iter().all(PhaseReport::passed)
+    }
+}
+
+pub const INTERNAL_ERROR_EXIT_CODE: u8 = 3;
+
+// The overall result of a run, example failures take precedence over other unsolved phases
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RunStatus {
+    Solved,
+    ExamplesFailed,
+    Unsolved,
+}
+
+impl RunStatus {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            RunStatus::Solved => 0,
+            RunStatus::ExamplesFailed => 1,
+            RunStatus::Unsolved => 2,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            RunStatus::Solved => "solved",
+            RunStatus::ExamplesFailed => "examples_failed",
+            RunStatus::Unsolved => "unsolved",
+        }
+    }
+}
+
+impl From<bool> for RunStatus {
+    fn from(passed: bool) -> Self {
+        if passed {
+            RunStatus::Solved
+        } else {
+            RunStatus::Unsolved
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RunReport {
+    pub tasks: Vec<TaskReport>,
+}
+
+impl RunReport {
+    pub fn passed(&self) -> bool {
+        self.tasks.iter().all(TaskReport::passed)
+    }
+
+    fn phases(&self) -> impl Iterator<Item = &PhaseReport> {
+        self.tasks.iter().flat_map(|task| &task.phases)
+    }
+
+    pub fn status(&self) -> RunStatus {
+        if self
+            .phases()
+            .any(|phase| phase.status == PhaseStatus::ExampleFailed)
+        {
+            RunStatus::ExamplesFailed
+        } else {
+            self.passed().into()
+        }
+    }
+
+    // A single line of `key=value` pairs for scripts, e.g.
+    // `status=unsolved exit_code=2 tasks=3 phases=6 passed=5 examples_failed=0 unsolved=1`
+    pub fn summary(&self) -> String {
+        let status = self.status();
+        let phases = self.phases().count();
+        let passed = self.phases().filter(|phase| phase.passed()).count();
+        let examples_failed = self
+            .phases()
+            .filter(|phase| phase.status == PhaseStatus::ExampleFailed)
+            .count();
+        format!(
+            "status={} exit_code={} tasks={} phases={phases} passed={passed} examples_failed={examples_failed} unsolved={}",
+            status.name(),
+            status.exit_code(),
+            self.tasks.len(),
+            phases - passed - examples_failed
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_failures_take_precedence() {
+        let mut report = RunReport {
+            tasks: vec![TaskReport {
+                name: "Day 01".to_owned(),
+                directory: PathBuf::from("day_01"),
+                phases: vec![
+                    PhaseReport::new(1, PhaseStatus::Solved),
+                    PhaseReport::new(2, PhaseStatus::Unsolved),
+                ],
+            }],
+        };
+        assert_eq!(report.status(), RunStatus::Unsolved);
+        assert_eq!(
+            report.summary(),
+            "status=unsolved exit_code=2 tasks=1 phases=2 passed=1 examples_failed=0 unsolved=1"
+        );
+        report.tasks[0].phases[1].status = PhaseStatus::ExampleFailed;
+        assert_eq!(report.status().exit_code(), 1);
+        report.tasks[0].phases[1].status = PhaseStatus::PreviouslySolved;
+        assert_eq!(report.status(), RunStatus::Solved);
+    }
+}
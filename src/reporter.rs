@@ -0,0 +1,658 @@
+#[cfg(feature = "browser")]
+mod browser;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+#[cfg(feature = "serde")]
+mod json;
+mod junit;
+#[cfg(feature = "notifications")]
+mod notification;
+mod webhook;
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use crossterm::style::StyledContent;
+use itertools::Itertools;
+
+use crate::{
+    capture, diff, ocr, race,
+    report::{ExampleReport, PhaseReport, PhaseStatus, RunReport, TaskReport},
+    style::{self, Glyph, Stylize},
+    AocSolution, AocTask, CHECKMARK, CROSS, DOT,
+};
+
+#[cfg(feature = "browser")]
+pub use browser::BrowserReporter;
+#[cfg(feature = "clipboard")]
+pub use clipboard::ClipboardReporter;
+#[cfg(feature = "serde")]
+pub use json::JsonReporter;
+pub use junit::JunitReporter;
+#[cfg(feature = "notifications")]
+pub use notification::NotificationReporter;
+pub use webhook::{Webhook, WebhookKind, WebhookReporter};
+
+pub trait Reporter {
+    fn on_task_start(&mut self, _task: &dyn AocTask) {}
+
+    fn on_example_result(&mut self, _task: &dyn AocTask, _phase: usize, _example: &ExampleReport) {}
+
+    // A line emitted by the solution while it is still running
+    fn on_streamed_output(&mut self, _task: &dyn AocTask, _phase: usize, _line: &str) {}
+
+    fn on_phase_output(&mut self, _task: &dyn AocTask, _phase: usize, _output: &AocSolution) {}
+
+    fn on_phase_result(&mut self, _task: &dyn AocTask, _report: &PhaseReport) {}
+
+    fn on_task_done(
+        &mut self,
+        _task: &dyn AocTask,
+        _index: usize,
+        _task_count: usize,
+        _report: &TaskReport,
+    ) {
+    }
+
+    fn on_run_done(&mut self, _report: &RunReport) {}
+}
+
+impl<R: Reporter + ?Sized> Reporter for Vec<Box<R>> {
+    fn on_task_start(&mut self, task: &dyn AocTask) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_task_start(task));
+    }
+
+    fn on_example_result(&mut self, task: &dyn AocTask, phase: usize, example: &ExampleReport) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_example_result(task, phase, example));
+    }
+
+    fn on_streamed_output(&mut self, task: &dyn AocTask, phase: usize, line: &str) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_streamed_output(task, phase, line));
+    }
+
+    fn on_phase_output(&mut self, task: &dyn AocTask, phase: usize, output: &AocSolution) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_phase_output(task, phase, output));
+    }
+
+    fn on_phase_result(&mut self, task: &dyn AocTask, report: &PhaseReport) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_phase_result(task, report));
+    }
+
+    fn on_task_done(
+        &mut self,
+        task: &dyn AocTask,
+        index: usize,
+        task_count: usize,
+        report: &TaskReport,
+    ) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_task_done(task, index, task_count, report));
+    }
+
+    fn on_run_done(&mut self, report: &RunReport) {
+        self.iter_mut()
+            .for_each(|reporter| reporter.on_run_done(report));
+    }
+}
+
+const CAPTURED_OUTPUT_LINES: usize = 20;
+
+// Only the end of the output is shown, where the prints leading up to a failure usually are
+fn print_captured_output(captured_output: &str) {
+    let lines = captured_output.lines().collect_vec();
+    if lines.is_empty() {
+        return;
+    }
+
+    println!("{} {}", DOT.muted(), "Solution output:".muted());
+    let hidden = lines.len().saturating_sub(CAPTURED_OUTPUT_LINES);
+    if hidden > 0 {
+        println!("  {}", format!("... {hidden} earlier lines hidden").muted());
+    }
+    for line in &lines[hidden..] {
+        println!("  {}", line.muted());
+    }
+}
+
+// Every line is styled on its own so that cutting off the output does not leave a style open
+fn styled_lines(output: &AocSolution, style: impl Fn(&str) -> StyledContent<&str>) -> Vec<String> {
+    output.iter().map(|line| style(line).to_string()).collect()
+}
+
+fn print_recognized_letters(output: &AocSolution) {
+    if let Some(letters) = ocr::recognize(output) {
+        println!("{} {} {}", DOT.muted(), "Reads as".muted(), letters.bold());
+    }
+}
+
+// The time it took to solve every phase since it became available, measured locally
+fn print_race_times(report: &RunReport) {
+    let race_times = report
+        .tasks
+        .iter()
+        .flat_map(|task| {
+            task.phases
+                .iter()
+                .filter_map(move |phase| Some((task, phase.phase, phase.race_time?)))
+        })
+        .collect_vec();
+    if race_times.is_empty() {
+        return;
+    }
+
+    println!("{}", "Race times:".bold());
+    for (task, phase, race_time) in race_times {
+        println!(
+            "{} {} phase {} {}",
+            DOT.accent(),
+            format!("{:<24}", task.name).bold(),
+            phase.to_string().highlight(),
+            race::format_race_time(race_time).accent()
+        );
+    }
+}
+
+const DEFAULT_MAX_LINES: usize = 100;
+const ELLIPSIS: Glyph = Glyph::new("…", "...");
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    // Only failures, the outputs of unsolved phases and the final summary
+    Quiet,
+    // The outputs of examples are only shown when they are not checked
+    #[default]
+    Normal,
+    // The outputs of all examples
+    Verbose,
+    // The outputs of all examples and how long each of them and each phase took
+    Timings,
+}
+
+impl Verbosity {
+    // `-q` and the number of `-v` flags
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Timings,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConsoleReporter {
+    max_lines: Option<usize>,
+    verbosity: Verbosity,
+    hide_answers: bool,
+    answers_copied: bool,
+}
+
+impl Default for ConsoleReporter {
+    fn default() -> Self {
+        Self {
+            max_lines: Some(DEFAULT_MAX_LINES),
+            verbosity: Verbosity::default(),
+            hide_answers: false,
+            answers_copied: false,
+        }
+    }
+}
+
+impl ConsoleReporter {
+    // Outputs and diffs longer than `max_lines` are cut off, `None` always shows them in full
+    pub fn max_lines(mut self, max_lines: Option<usize>) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    // Keeps the answers of the inputs off the screen, e.g. while streaming. This also hides the
+    // streamed lines, the rejected answers and the output of failed phases, as any of them may
+    // contain the answer. The outputs of the examples are still shown.
+    pub fn hide_answers(mut self, hide_answers: bool) -> Self {
+        self.hide_answers = hide_answers;
+        self
+    }
+
+    // Mentions where the hidden answers went, when another reporter copies them
+    pub fn answers_copied(mut self, answers_copied: bool) -> Self {
+        self.answers_copied = answers_copied;
+        self
+    }
+
+    fn timing(&self, duration: Option<Duration>) -> String {
+        match duration {
+            Some(duration) if self.verbosity >= Verbosity::Timings => {
+                format!(" ({duration:.2?})").muted().to_string()
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn rejection(&self, report: &PhaseReport) -> String {
+        if self.hide_answers {
+            "the answer was rejected before".to_owned()
+        } else {
+            report.rejection.clone().unwrap_or_default()
+        }
+    }
+
+    fn print_captured_output(&self, captured_output: &str) {
+        if !self.hide_answers {
+            print_captured_output(captured_output);
+        }
+    }
+
+    // Long outputs are written to a file next to the task instead of flooding the terminal
+    fn print_limited(&self, lines: &[String], full_output: impl FnOnce() -> String, path: PathBuf) {
+        let shown = match self.max_lines {
+            Some(max_lines) if lines.len() > max_lines => max_lines,
+            _ => lines.len(),
+        };
+        for line in &lines[..shown] {
+            println!("{line}");
+        }
+        if shown == lines.len() {
+            return;
+        }
+
+        println!(
+            "{}",
+            format!(
+                "{ELLIPSIS} {} more lines (use --full to show)",
+                lines.len() - shown
+            )
+            .muted()
+        );
+        match fs::write(&path, full_output()) {
+            Ok(()) => println!(
+                "{} {} {}",
+                DOT.muted(),
+                "The full output was written to".muted(),
+                path.to_string_lossy().muted()
+            ),
+            Err(err) => eprintln!(
+                "{} Failed to write the full output to {}: {}",
+                CROSS.failure(),
+                path.to_string_lossy().bold(),
+                err
+            ),
+        }
+    }
+}
+
+impl Reporter for ConsoleReporter {
+    fn on_example_result(&mut self, task: &dyn AocTask, phase: usize, example: &ExampleReport) {
+        if example.checked && !example.passed {
+            println!(
+                "{} {} {} the {} test in phase {}.",
+                CROSS.failure(),
+                task.name().bold(),
+                "failed".failure(),
+                example.name.clone().bold(),
+                phase.to_string().highlight(),
+            );
+            println!("Diff:");
+            let rendered_diff = diff::render(&example.expected_output, &example.output)
+                .lines()
+                .map(str::to_owned)
+                .collect_vec();
+            self.print_limited(
+                &rendered_diff,
+                || diff::render_plain(&example.expected_output, &example.output),
+                task.data_directory()
+                    .join(format!(".diff_{}", example.name)),
+            );
+            print_captured_output(&example.captured_output);
+            // Exit early since we printed the diff already and there is no need to print the output
+            return;
+        }
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
+        if example.checked {
+            println!(
+                "{} {} {} the {} test in phase {}!{}",
+                CHECKMARK.success(),
+                task.name().bold(),
+                "passed".success(),
+                example.name.clone().bold(),
+                phase.to_string().highlight(),
+                self.timing(Some(example.duration)),
+            );
+            if self.verbosity < Verbosity::Verbose {
+                return;
+            }
+        }
+
+        println!(
+            "{} {} {} {} {}:",
+            DOT.accent(),
+            "Output of the".accent(),
+            example.name.clone().bold(),
+            "test in phase".accent(),
+            phase.to_string().highlight(),
+        );
+        self.print_limited(
+            &styled_lines(&example.output, |line| line.accent()),
+            || example.output.join("\n"),
+            task.data_directory()
+                .join(format!(".output_{}", example.name)),
+        );
+        print_recognized_letters(&example.output);
+    }
+
+    fn on_streamed_output(&mut self, task: &dyn AocTask, phase: usize, line: &str) {
+        if self.hide_answers
+            || (self.verbosity == Verbosity::Quiet && task.phase_is_solved(phase).unwrap_or(false))
+        {
+            return;
+        }
+        capture::print_uncaptured(&format!(
+            "{} {} {}: {}\n",
+            DOT.info(),
+            "Streamed from phase".info(),
+            phase.to_string().highlight(),
+            line.info()
+        ));
+    }
+
+    fn on_phase_output(&mut self, task: &dyn AocTask, phase: usize, output: &AocSolution) {
+        // The answers of unsolved phases are still needed to submit them
+        if self.verbosity == Verbosity::Quiet && task.phase_is_solved(phase).unwrap_or(false) {
+            return;
+        }
+        println!(
+            "{} {} {}:",
+            DOT.info(),
+            "Solution for phase".info(),
+            phase.to_string().highlight(),
+        );
+        if self.hide_answers {
+            let copied = if self.answers_copied && !task.phase_is_solved(phase).unwrap_or(false) {
+                " (copied to clipboard)"
+            } else {
+                ""
+            };
+            println!(
+                "{} {}",
+                CHECKMARK.success(),
+                format!("answer hidden{copied}").muted()
+            );
+            return;
+        }
+        self.print_limited(
+            &styled_lines(output, |line| line.info()),
+            || output.join("\n"),
+            task.data_directory().join(format!(".output_phase_{phase}")),
+        );
+        print_recognized_letters(output);
+    }
+
+    fn on_phase_result(&mut self, task: &dyn AocTask, report: &PhaseReport) {
+        if self.verbosity == Verbosity::Quiet && report.passed() {
+            return;
+        }
+        match report.status {
+            PhaseStatus::Solved => println!(
+                "{} Phase {}/{} of {} {}!{}",
+                CHECKMARK.success(),
+                report.phase.to_string().highlight(),
+                task.phases().to_string().highlight(),
+                task.name().bold(),
+                "passed".success(),
+                self.timing(report.duration)
+            ),
+            PhaseStatus::PreviouslySolved => println!(
+                "{} Phase {}/{} of {} was {}.",
+                CHECKMARK.success(),
+                report.phase.to_string().highlight(),
+                task.phases().to_string().highlight(),
+                task.name().bold(),
+                "previously solved".success()
+            ),
+            PhaseStatus::Unsolved => {
+                println!(
+                    "{} Phase {}/{} of {} {}.",
+                    CROSS.failure(),
+                    report.phase.to_string().highlight(),
+                    task.phases().to_string().highlight(),
+                    task.name().bold(),
+                    "failed".failure()
+                );
+                self.print_captured_output(&report.captured_output);
+            }
+            PhaseStatus::RejectedAnswer => println!(
+                "{} Phase {}/{} of {} {}: {}.",
+                CROSS.failure(),
+                report.phase.to_string().highlight(),
+                task.phases().to_string().highlight(),
+                task.name().bold(),
+                "has a known wrong answer".failure(),
+                self.rejection(report)
+            ),
+            PhaseStatus::Unconfirmed => println!(
+                "{} Phase {}/{} of {} is {}.",
+                DOT.highlight(),
+                report.phase.to_string().highlight(),
+                task.phases().to_string().highlight(),
+                task.name().bold(),
+                "not marked as solved".highlight()
+            ),
+            PhaseStatus::Regressed => {
+                println!(
+                    "{} Phase {}/{} of {} {}.",
+                    CROSS.failure(),
+                    report.phase.to_string().highlight(),
+                    task.phases().to_string().highlight(),
+                    task.name().bold(),
+                    "no longer produces the accepted answer".failure()
+                );
+                if let Some((accepted, answer)) =
+                    report.answer_lines().filter(|_| !self.hide_answers)
+                {
+                    println!("Diff:");
+                    let rendered_diff = diff::render(&accepted, &answer)
+                        .lines()
+                        .map(str::to_owned)
+                        .collect_vec();
+                    self.print_limited(
+                        &rendered_diff,
+                        || diff::render_plain(&accepted, &answer),
+                        task.data_directory()
+                            .join(format!(".diff_phase_{}", report.phase)),
+                    );
+                }
+                self.print_captured_output(&report.captured_output);
+            }
+            PhaseStatus::Cancelled => println!(
+                "{} Phase {}/{} of {} was {}{}.",
+                CROSS.failure(),
+                report.phase.to_string().highlight(),
+                task.phases().to_string().highlight(),
+                task.name().bold(),
+                "cancelled".failure(),
+                self.timing(report.duration)
+            ),
+            PhaseStatus::TimedOut => println!(
+                "{} Phase {}/{} of {} {}{}.",
+                CROSS.failure(),
+                report.phase.to_string().highlight(),
+                task.phases().to_string().highlight(),
+                task.name().bold(),
+                "timed out".failure(),
+                self.timing(report.duration)
+            ),
+            // The failing example has already been reported
+            PhaseStatus::ExampleFailed => {}
+        }
+        #[cfg(feature = "memory")]
+        if let Some(memory) = report.memory {
+            println!(
+                "  {} peak heap {}, {} allocations ({} allocated)",
+                DOT.muted(),
+                crate::memory::format_bytes(memory.peak_bytes).info(),
+                memory.allocations.to_string().info(),
+                crate::memory::format_bytes(memory.allocated_bytes).muted()
+            );
+        }
+    }
+
+    fn on_task_done(
+        &mut self,
+        task: &dyn AocTask,
+        index: usize,
+        task_count: usize,
+        report: &TaskReport,
+    ) {
+        if !report.passed() || self.verbosity == Verbosity::Quiet {
+            return;
+        }
+
+        println!(
+            "{}",
+            format!(
+                "{} Task {} - {}/{} done!",
+                CHECKMARK,
+                task.name(),
+                index + 1,
+                task_count
+            )
+            .success()
+        );
+        println!("=================================================");
+    }
+
+    fn on_run_done(&mut self, report: &RunReport) {
+        print_race_times(report);
+        if report.passed() {
+            let message = if style::is_plain() {
+                "All tasks have been completed!"
+            } else {
+                "🚀🚀🚀✔️ All tasks have been completed! ✔️🚀🚀🚀"
+            };
+            println!("{}", message.success());
+            println!("{}", report.summary().muted());
+            return;
+        }
+
+        println!("=================================================");
+        println!("{}", "Failed tasks:".failure());
+        for task in report.tasks.iter().filter(|task| !task.passed()) {
+            for phase in task.phases.iter().filter(|phase| !phase.passed()) {
+                let reason = match phase.status {
+                    PhaseStatus::ExampleFailed => format!(
+                        "failed examples: {}",
+                        phase
+                            .examples
+                            .iter()
+                            .filter(|example| example.checked && !example.passed)
+                            .map(|example| example.name.as_str())
+                            .join(", ")
+                    ),
+                    PhaseStatus::RejectedAnswer => self.rejection(phase),
+                    PhaseStatus::Regressed => "the accepted answer changed".to_owned(),
+                    _ => "the solution was not accepted".to_owned(),
+                };
+                println!(
+                    "{} {} phase {} {}",
+                    CROSS.failure(),
+                    format!("{:<24}", task.name).bold(),
+                    phase.phase.to_string().highlight(),
+                    reason.failure()
+                );
+            }
+        }
+        println!("{}", report.summary().muted());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process::Command, sync::Arc};
+
+    use super::*;
+    use crate::{
+        answers::{RecordedAnswer, Verdict},
+        hashed::{HashedOutput, HASHED_ANSWERS_FILE},
+        runner::AocRunner,
+        state,
+        testing::{MockTask, TempDir},
+        Filter, SharedAocTask,
+    };
+
+    const SPOILER_RUN: &str = "AOC_SPOILER_FREE_RUN";
+    const ANSWER: &str = "8675309";
+
+    fn spoiling_task(name: &str) -> MockTask {
+        MockTask::new(|_input, context| {
+            println!("Found {ANSWER}");
+            context.emit(ANSWER);
+            Ok(vec![ANSWER.to_owned()])
+        })
+        .with_name(name)
+        .with_phases(1)
+    }
+
+    // Runs a rejected and a regressed phase, both printing their answer in every way they can
+    fn spoiler_free_run() {
+        let directory = TempDir::new("spoiler_free");
+        directory.write(
+            HASHED_ANSWERS_FILE,
+            &format!("{}\n", HashedOutput::new("1")),
+        );
+        let rejected = spoiling_task("Rejected Task").with_directory(directory.path());
+        let regressed = spoiling_task("Regressed Task").with_solved(1);
+        state::with_task_state(&regressed, |state| {
+            state.answers.push(RecordedAnswer {
+                phase: 1,
+                verdict: Verdict::Correct,
+                answer: "1".to_owned(),
+            });
+        })
+        .unwrap();
+
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(rejected), Arc::new(regressed)];
+        AocRunner::new(&tasks)
+            .interactive(false)
+            .progress(false)
+            .fail_fast(false)
+            .filter(Filter::all().phase(1))
+            .reporter(ConsoleReporter::default().hide_answers(true))
+            .run()
+            .unwrap();
+    }
+
+    // The console output is only observable from another process
+    #[test]
+    fn spoiler_free_runs_keep_the_answers_off_the_console() {
+        if env::var_os(SPOILER_RUN).is_some() {
+            spoiler_free_run();
+            return;
+        }
+        let run = Command::new(env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "reporter::tests::spoiler_free_runs_keep_the_answers_off_the_console",
+                "--nocapture",
+            ])
+            .env(SPOILER_RUN, "1")
+            .output()
+            .unwrap();
+        let console = String::from_utf8_lossy(&run.stdout) + String::from_utf8_lossy(&run.stderr);
+
+        assert!(run.status.success(), "{console}");
+        assert!(console.contains("answer hidden"), "{console}");
+        assert!(console.contains("no longer produces the accepted answer"));
+        assert!(!console.contains(ANSWER), "{console}");
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    browser,
+    report::{PhaseReport, PhaseStatus},
+    reporter::Reporter,
+    style::Stylize,
+    AocTask, CROSS,
+};
+
+// Opens the puzzle once a phase is newly solved, at the next part so its answer form is at hand
+#[derive(Default)]
+pub struct BrowserReporter;
+
+impl BrowserReporter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Reporter for BrowserReporter {
+    fn on_phase_result(&mut self, task: &dyn AocTask, report: &PhaseReport) {
+        if report.status != PhaseStatus::Solved {
+            return;
+        }
+        let next_part = (report.phase < task.phases()).then_some(report.phase + 1);
+        let Some(url) = browser::task_url(task, next_part) else {
+            return;
+        };
+        if let Err(err) = browser::open_url(&url) {
+            eprintln!("{} {err}", CROSS.failure());
+        }
+    }
+}
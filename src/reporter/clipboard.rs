@@ -0,0 +1,45 @@
+use arboard::Clipboard;
+
+use crate::{answers, reporter::Reporter, style::Stylize, AocSolution, AocTask, CROSS, DOT};
+
+// Copies the answers of the phases that are not solved yet, so they can be pasted into the
+// answer form right away. The clipboard is kept open, on X11 the copied text is gone once it is
+// closed.
+#[derive(Default)]
+pub struct ClipboardReporter {
+    clipboard: Option<Clipboard>,
+}
+
+impl ClipboardReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn copy(&mut self, text: String) -> Result<(), arboard::Error> {
+        let clipboard = match &mut self.clipboard {
+            Some(clipboard) => clipboard,
+            None => self.clipboard.insert(Clipboard::new()?),
+        };
+        clipboard.set_text(text)
+    }
+}
+
+impl Reporter for ClipboardReporter {
+    fn on_phase_output(&mut self, task: &dyn AocTask, phase: usize, output: &AocSolution) {
+        let answer = answers::answer_text(output);
+        if answer.is_empty() || task.phase_is_solved(phase).unwrap_or(false) {
+            return;
+        }
+        match self.copy(answer) {
+            Ok(()) => println!(
+                "{} {}",
+                DOT.info(),
+                format!("Copied the answer of phase {phase} to the clipboard").muted()
+            ),
+            Err(err) => eprintln!(
+                "{} Failed to copy the answer to the clipboard: {err}",
+                CROSS.failure()
+            ),
+        }
+    }
+}
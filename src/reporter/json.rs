@@ -0,0 +1,65 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{
+    error::AocError,
+    report::{RunReport, TaskReport},
+    reporter::Reporter,
+    style::Stylize,
+    CROSS,
+};
+
+#[derive(Serialize)]
+struct JsonSummary<'a> {
+    passed: bool,
+    tasks: &'a [TaskReport],
+}
+
+pub struct JsonReporter {
+    writer: Box<dyn Write + Send>,
+}
+
+impl JsonReporter {
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self { writer }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(Box::new(io::stdout()))
+    }
+
+    pub fn to_file(path: &Path) -> Result<Self, AocError> {
+        let file = File::create(path).map_err(|err| AocError::IOWriteError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        Ok(Self::new(Box::new(BufWriter::new(file))))
+    }
+
+    fn write_summary(&mut self, report: &RunReport) -> Result<(), serde_json::Error> {
+        let summary = JsonSummary {
+            passed: report.passed(),
+            tasks: &report.tasks,
+        };
+        serde_json::to_writer_pretty(&mut self.writer, &summary)?;
+        writeln!(self.writer).map_err(serde_json::Error::io)?;
+        self.writer.flush().map_err(serde_json::Error::io)
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn on_run_done(&mut self, report: &RunReport) {
+        if let Err(err) = self.write_summary(report) {
+            eprintln!(
+                "{} Failed to write the JSON report: {}",
+                CROSS.failure(),
+                err
+            );
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    diff,
+    error::AocError,
+    report::{ExampleReport, PhaseReport, PhaseStatus, RunReport, TaskReport},
+    reporter::Reporter,
+    style::Stylize,
+    AocTask, CROSS,
+};
+
+enum CaseResult {
+    Passed,
+    Failed { message: String, details: String },
+    Skipped { message: String },
+}
+
+struct TestCase {
+    class_name: String,
+    name: String,
+    time: Option<Duration>,
+    result: CaseResult,
+}
+
+pub struct JunitReporter {
+    writer: Box<dyn Write + Send>,
+    // The number of phases of every reported task, the filtered out phases are reported skipped
+    phase_counts: Vec<usize>,
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn example_case(class_name: &str, example: &ExampleReport) -> TestCase {
+    let result = if example.checked && !example.passed {
+        CaseResult::Failed {
+            message: "The output does not match the expected output".to_owned(),
+            details: diff::render_plain(&example.expected_output, &example.output),
+        }
+    } else {
+        CaseResult::Passed
+    };
+
+    TestCase {
+        class_name: class_name.to_owned(),
+        name: example.name.clone(),
+        time: Some(example.duration),
+        result,
+    }
+}
+
+fn filtered_case(class_name: &str) -> TestCase {
+    TestCase {
+        class_name: class_name.to_owned(),
+        name: "input".to_owned(),
+        time: None,
+        result: CaseResult::Skipped {
+            message: "The phase was filtered out".to_owned(),
+        },
+    }
+}
+
+fn input_case(class_name: &str, phase: &PhaseReport) -> TestCase {
+    let output = phase.output.clone().unwrap_or_default().join("\n");
+    let result = match phase.status {
+        PhaseStatus::Solved => CaseResult::Passed,
+        PhaseStatus::Unsolved => CaseResult::Failed {
+            message: "The solution was not accepted".to_owned(),
+            details: output,
+        },
+        PhaseStatus::RejectedAnswer => CaseResult::Failed {
+            message: "The answer was already rejected".to_owned(),
+            details: phase.rejection.clone().unwrap_or_default(),
+        },
+        PhaseStatus::Regressed => CaseResult::Failed {
+            message: "The output does not match the accepted answer".to_owned(),
+            details: phase
+                .answer_lines()
+                .map(|(accepted, answer)| diff::render_plain(&accepted, &answer))
+                .unwrap_or(output),
+        },
+        PhaseStatus::PreviouslySolved => CaseResult::Skipped {
+            message: "The phase was previously solved".to_owned(),
+        },
+        PhaseStatus::Unconfirmed => CaseResult::Skipped {
+            message: "The phase is not marked as solved".to_owned(),
+        },
+        PhaseStatus::ExampleFailed => CaseResult::Skipped {
+            message: "An example of the phase failed".to_owned(),
+        },
+        PhaseStatus::TimedOut => CaseResult::Failed {
+            message: "The solution did not finish within the timeout".to_owned(),
+            details: String::new(),
+        },
+        PhaseStatus::Cancelled => CaseResult::Skipped {
+            message: "The run was cancelled".to_owned(),
+        },
+    };
+
+    TestCase {
+        class_name: class_name.to_owned(),
+        name: "input".to_owned(),
+        time: phase.duration,
+        result,
+    }
+}
+
+impl JunitReporter {
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            writer,
+            phase_counts: vec![],
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(Box::new(io::stdout()))
+    }
+
+    pub fn to_file(path: &Path) -> Result<Self, AocError> {
+        let file = File::create(path).map_err(|err| AocError::IOWriteError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        Ok(Self::new(Box::new(BufWriter::new(file))))
+    }
+
+    fn render(report: &RunReport, phase_counts: &[usize]) -> String {
+        let suites = report
+            .tasks
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                let last_phase = task
+                    .phases
+                    .iter()
+                    .map(|phase| phase.phase)
+                    .chain(phase_counts.get(i).copied())
+                    .max()
+                    .unwrap_or_default();
+                let cases = (1..=last_phase)
+                    .flat_map(|phase| {
+                        let class_name = format!("{}.phase_{phase}", task.name);
+                        let Some(phase) = task.phases.iter().find(|report| report.phase == phase)
+                        else {
+                            return vec![filtered_case(&class_name)];
+                        };
+                        phase
+                            .examples
+                            .iter()
+                            .map(|example| example_case(&class_name, example))
+                            .chain([input_case(&class_name, phase)])
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                (task.name.clone(), cases)
+            })
+            .collect_vec();
+
+        let count = |cases: &[TestCase], counted: fn(&CaseResult) -> bool| {
+            cases.iter().filter(|case| counted(&case.result)).count()
+        };
+        let all = |_: &CaseResult| true;
+        let failed = |result: &CaseResult| matches!(result, CaseResult::Failed { .. });
+        let skipped = |result: &CaseResult| matches!(result, CaseResult::Skipped { .. });
+        let total = |counted: fn(&CaseResult) -> bool| -> usize {
+            suites.iter().map(|(_, cases)| count(cases, counted)).sum()
+        };
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"aoc-framework\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+            total(all),
+            total(failed),
+            total(skipped)
+        );
+        for (name, cases) in &suites {
+            let time: Duration = cases.iter().filter_map(|case| case.time).sum();
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+                escape(name),
+                count(cases, all),
+                count(cases, failed),
+                count(cases, skipped),
+                time.as_secs_f64()
+            );
+            for case in cases {
+                let _ = write!(
+                    xml,
+                    "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"",
+                    escape(&case.class_name),
+                    escape(&case.name),
+                    case.time.unwrap_or_default().as_secs_f64()
+                );
+                match &case.result {
+                    CaseResult::Passed => xml.push_str("/>\n"),
+                    CaseResult::Failed { message, details } => {
+                        let _ = writeln!(
+                            xml,
+                            ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                            escape(message),
+                            escape(details)
+                        );
+                    }
+                    CaseResult::Skipped { message } => {
+                        let _ = writeln!(
+                            xml,
+                            ">\n      <skipped message=\"{}\"/>\n    </testcase>",
+                            escape(message)
+                        );
+                    }
+                }
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn on_task_done(
+        &mut self,
+        task: &dyn AocTask,
+        _index: usize,
+        _task_count: usize,
+        _report: &TaskReport,
+    ) {
+        self.phase_counts.push(task.phases());
+    }
+
+    fn on_run_done(&mut self, report: &RunReport) {
+        let xml = Self::render(report, &self.phase_counts);
+        if let Err(err) = self
+            .writer
+            .write_all(xml.as_bytes())
+            .and_then(|_| self.writer.flush())
+        {
+            eprintln!(
+                "{} Failed to write the JUnit report: {}",
+                CROSS.failure(),
+                err
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn junit_report_contains_failure_diff() {
+        let report = RunReport {
+            tasks: vec![TaskReport {
+                name: "Day <1>".to_owned(),
+                directory: "day_01".into(),
+                phases: vec![PhaseReport {
+                    examples: vec![ExampleReport {
+                        name: "example".to_owned(),
+                        input_path: Some("day_01/example_in".into()),
+                        checked: true,
+                        passed: false,
+                        output: vec!["4".to_owned()],
+                        expected_output: vec!["5".to_owned()],
+                        captured_output: String::new(),
+                        duration: Duration::from_millis(1),
+                    }],
+                    ..PhaseReport::new(1, PhaseStatus::ExampleFailed)
+                }],
+            }],
+        };
+
+        let xml = JunitReporter::render(&report, &[1]);
+        assert!(xml.contains("tests=\"2\" failures=\"1\" skipped=\"1\""));
+        assert!(xml.contains("<testsuite name=\"Day &lt;1&gt;\""));
+        assert!(xml.contains("- 5\n+ 4\n</failure>"));
+        assert!(xml.contains("<skipped message=\"An example of the phase failed\"/>"));
+    }
+
+    #[test]
+    fn unsolved_and_filtered_phases_are_skipped() {
+        let report = RunReport {
+            tasks: vec![TaskReport {
+                name: "Day 2".to_owned(),
+                directory: "day_02".into(),
+                phases: vec![PhaseReport::new(2, PhaseStatus::Unconfirmed)],
+            }],
+        };
+
+        let xml = JunitReporter::render(&report, &[2]);
+        assert!(xml.contains(
+            "<testsuites name=\"aoc-framework\" tests=\"2\" failures=\"0\" skipped=\"2\">"
+        ));
+        let filtered = xml.find("classname=\"Day 2.phase_1\"").unwrap();
+        assert!(xml[filtered..].starts_with(
+            "classname=\"Day 2.phase_1\" name=\"input\" time=\"0.000000\">\n      \
+             <skipped message=\"The phase was filtered out\"/>"
+        ));
+        assert!(xml.contains("<skipped message=\"The phase is not marked as solved\"/>"));
+    }
+}
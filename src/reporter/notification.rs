@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use notify_rust::Notification;
+
+use crate::{report::PhaseReport, reporter::Reporter, style::Stylize, AocTask, CROSS};
+
+// Shows a desktop notification when a phase that took at least `min_duration` finishes, so slow
+// brute-force phases can be left running in the background
+pub struct NotificationReporter {
+    min_duration: Duration,
+    hide_answers: bool,
+}
+
+impl NotificationReporter {
+    pub fn new(min_duration: Duration) -> Self {
+        Self {
+            min_duration,
+            hide_answers: false,
+        }
+    }
+
+    // Leaves the answer out of the notification body
+    pub fn hide_answers(mut self, hide_answers: bool) -> Self {
+        self.hide_answers = hide_answers;
+        self
+    }
+
+    fn should_notify(&self, report: &PhaseReport) -> bool {
+        report
+            .duration
+            .is_some_and(|duration| duration >= self.min_duration)
+    }
+
+    fn body(&self, report: &PhaseReport) -> String {
+        match &report.output {
+            Some(_) if self.hide_answers => "answer hidden".to_owned(),
+            Some(output) => output.join("\n"),
+            None => String::new(),
+        }
+    }
+}
+
+impl Reporter for NotificationReporter {
+    fn on_phase_result(&mut self, task: &dyn AocTask, report: &PhaseReport) {
+        if !self.should_notify(report) {
+            return;
+        }
+        let result = if report.passed() {
+            "finished"
+        } else {
+            "failed"
+        };
+        let duration = report.duration.unwrap_or_default();
+        let shown = Notification::new()
+            .summary(&format!(
+                "{} phase {} {result} after {duration:.1?}",
+                task.name(),
+                report.phase
+            ))
+            .body(&self.body(report))
+            .show();
+        if let Err(err) = shown {
+            eprintln!(
+                "{} Failed to show a desktop notification: {err}",
+                CROSS.failure()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{PhaseReport, PhaseStatus};
+
+    #[test]
+    fn only_slow_phases_are_notified() {
+        let reporter = NotificationReporter::new(Duration::from_secs(10));
+        let mut report = PhaseReport::new(1, PhaseStatus::Solved);
+        report.duration = Some(Duration::from_secs(3));
+        assert!(!reporter.should_notify(&report));
+        report.duration = Some(Duration::from_secs(12));
+        assert!(reporter.should_notify(&report));
+        report.duration = None;
+        assert!(!reporter.should_notify(&report));
+    }
+
+    #[test]
+    fn hidden_answers_are_left_out() {
+        let mut report = PhaseReport::new(1, PhaseStatus::Solved);
+        report.output = Some(vec!["42".to_owned()]);
+        let reporter = NotificationReporter::new(Duration::ZERO);
+        assert_eq!(reporter.body(&report), "42");
+        assert_eq!(reporter.hide_answers(true).body(&report), "answer hidden");
+    }
+}
@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use crate::{
+    report::{PhaseReport, PhaseStatus, RunReport},
+    reporter::Reporter,
+    style::Stylize,
+    AocTask, DOT,
+};
+
+// A webhook that doesn't answer must not hold up the run
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WebhookKind {
+    Discord,
+    Slack,
+    // Posts the event, the message and its details as a JSON object
+    #[default]
+    Generic,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub url: String,
+    pub kind: WebhookKind,
+    pub on_solved: bool,
+    pub on_failed: bool,
+}
+
+impl Webhook {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            kind: WebhookKind::default(),
+            on_solved: true,
+            on_failed: true,
+        }
+    }
+}
+
+// The few kinds of values the payloads have, written without pulling in a JSON library
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Text(String),
+    Number(f64),
+    List(Vec<String>),
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Self {
+        Value::Text(text.to_owned())
+    }
+}
+
+impl From<usize> for Value {
+    fn from(number: usize) -> Self {
+        Value::Number(number as f64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(number: f64) -> Self {
+        Value::Number(number)
+    }
+}
+
+impl From<Vec<String>> for Value {
+    fn from(list: Vec<String>) -> Self {
+        Value::List(list)
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+impl Value {
+    fn to_json(&self) -> String {
+        match self {
+            Value::Text(text) => json_string(text),
+            Value::Number(number) => number.to_string(),
+            Value::List(list) => {
+                format!("[{}]", list.iter().map(|item| json_string(item)).join(","))
+            }
+        }
+    }
+}
+
+fn json_object<'f>(fields: impl IntoIterator<Item = (&'f str, Value)>) -> String {
+    let fields = fields
+        .into_iter()
+        .map(|(key, value)| format!("{}:{}", json_string(key), value.to_json()))
+        .join(",");
+    format!("{{{fields}}}")
+}
+
+// The details are only sent to generic webhooks, chat webhooks only show the message
+fn payload(kind: WebhookKind, event: &str, message: &str, details: &[(&str, Value)]) -> String {
+    match kind {
+        WebhookKind::Discord => json_object([("content", message.into())]),
+        WebhookKind::Slack => json_object([("text", message.into())]),
+        WebhookKind::Generic => json_object(
+            [("event", event.into()), ("message", message.into())]
+                .into_iter()
+                .chain(details.iter().cloned()),
+        ),
+    }
+}
+
+// Posts to the webhooks configured in `aoc.toml` when a phase gets solved or a run fails,
+// failed deliveries are reported as warnings without failing the run
+pub struct WebhookReporter {
+    webhooks: Vec<Webhook>,
+    agent: ureq::Agent,
+}
+
+impl WebhookReporter {
+    pub fn new(webhooks: Vec<Webhook>) -> Self {
+        Self {
+            webhooks,
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(CONNECT_TIMEOUT)
+                .timeout_read(READ_TIMEOUT)
+                .build(),
+        }
+    }
+
+    fn send(
+        &self,
+        wanted: impl Fn(&Webhook) -> bool,
+        event: &str,
+        message: &str,
+        details: &[(&str, Value)],
+    ) {
+        for webhook in self.webhooks.iter().filter(|webhook| wanted(webhook)) {
+            let result = self
+                .agent
+                .post(&webhook.url)
+                .set("Content-Type", "application/json")
+                .send_string(&payload(webhook.kind, event, message, details));
+            // The URLs of webhooks contain their secret tokens, so they are never printed
+            let reason = match result {
+                Ok(_) => continue,
+                Err(ureq::Error::Status(status, _)) => format!("status {status}"),
+                Err(ureq::Error::Transport(transport)) => transport.kind().to_string(),
+            };
+            eprintln!(
+                "{} {}",
+                DOT.highlight(),
+                format!("Warning: failed to send a webhook notification ({reason})").highlight()
+            );
+        }
+    }
+}
+
+impl Reporter for WebhookReporter {
+    fn on_phase_result(&mut self, task: &dyn AocTask, report: &PhaseReport) {
+        if report.status != PhaseStatus::Solved {
+            return;
+        }
+        let time = report
+            .duration
+            .map(|duration| format!(" in {duration:.0?}"))
+            .unwrap_or_default();
+        let message = format!("{} phase {} done{time}", task.name(), report.phase);
+        let mut details = vec![
+            ("task", Value::from(task.name())),
+            ("phase", Value::from(report.phase)),
+        ];
+        if let Some(duration) = report.duration {
+            details.push(("seconds", Value::from(duration.as_secs_f64())));
+        }
+        self.send(|webhook| webhook.on_solved, "solved", &message, &details);
+    }
+
+    fn on_run_done(&mut self, report: &RunReport) {
+        let failed = report
+            .tasks
+            .iter()
+            .flat_map(|task| {
+                task.phases
+                    .iter()
+                    .filter(|phase| !phase.passed())
+                    .map(move |phase| format!("{} phase {}", task.name, phase.phase))
+            })
+            .collect_vec();
+        if failed.is_empty() {
+            return;
+        }
+        let message = format!("{} phases failed: {}", failed.len(), failed.join(", "));
+        let details = [("failed", Value::from(failed))];
+        self.send(|webhook| webhook.on_failed, "failed", &message, &details);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payloads_match_the_webhook_kind() {
+        let details = [
+            ("phase", Value::from(2)),
+            ("task", Value::from("Day \"13\"")),
+        ];
+        assert_eq!(
+            payload(WebhookKind::Discord, "solved", "Day 13 \"done\"", &details),
+            r#"{"content":"Day 13 \"done\""}"#
+        );
+        assert_eq!(
+            payload(WebhookKind::Slack, "solved", "done\n", &details),
+            r#"{"text":"done\n"}"#
+        );
+        assert_eq!(
+            payload(WebhookKind::Generic, "solved", "done", &details),
+            r#"{"event":"solved","message":"done","phase":2,"task":"Day \"13\""}"#
+        );
+        assert_eq!(
+            payload(
+                WebhookKind::Generic,
+                "failed",
+                "1\tfailed",
+                &[("failed", Value::from(vec!["Day 1 phase 2".to_owned()]))]
+            ),
+            r#"{"event":"failed","message":"1\tfailed","failed":["Day 1 phase 2"]}"#
+        );
+    }
+}
@@ -0,0 +1,1105 @@
+use std::{
+    panic,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, RecvTimeoutError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "memory")]
+use crate::memory::MemoryTracker;
+use crate::{
+    answers::{self, AnswerLog, Verdict},
+    cancel::{self, CancellationToken},
+    capture::OutputCapture,
+    context::AocContext,
+    error::AocError,
+    example::{Example, ExampleMetadata},
+    filter::Filter,
+    hashed,
+    progress::PhaseProgress,
+    prompt::ConfirmPolicy,
+    race,
+    report::{ExampleReport, PhaseReport, PhaseStatus, RunReport, TaskReport},
+    reporter::{ConsoleReporter, Reporter},
+    source::InputSource,
+    style::{self, Theme},
+    AocSolution, SharedAocTask,
+};
+
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub interactive: bool,
+    pub parallel: bool,
+    pub fail_fast: bool,
+    pub progress: bool,
+    pub timeout: Option<Duration>,
+    pub capture_output: bool,
+    pub solved_only: bool,
+    pub skip_solved: bool,
+    pub force: bool,
+    pub filter: Filter,
+    // Replaces the inputs of all tasks
+    pub input_source: Option<Arc<dyn InputSource>>,
+    // Shows the answer when asking whether it was accepted
+    pub answer_prompt: bool,
+    pub confirm: ConfirmPolicy,
+    pub cancellation: CancellationToken,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            interactive: true,
+            parallel: false,
+            fail_fast: true,
+            progress: true,
+            timeout: None,
+            capture_output: true,
+            solved_only: false,
+            skip_solved: false,
+            force: false,
+            filter: Filter::all(),
+            input_source: None,
+            answer_prompt: false,
+            confirm: ConfirmPolicy::Ask,
+            cancellation: CancellationToken::default(),
+        }
+    }
+}
+
+struct NoopReporter;
+
+impl Reporter for NoopReporter {}
+
+// Redirecting the output is process-wide, so it is only captured when running sequentially
+fn start_capture(options: &RunOptions) -> Option<OutputCapture> {
+    (options.capture_output && !options.parallel)
+        .then(OutputCapture::start)
+        .flatten()
+}
+
+fn finish_capture<T>(
+    capture: Option<OutputCapture>,
+    result: Result<T, AocError>,
+) -> Result<(T, String), AocError> {
+    let captured_output = capture.map(OutputCapture::finish).unwrap_or_default();
+    match result {
+        Ok(value) => Ok((value, captured_output)),
+        Err(err) => {
+            // The error is reported by the caller, keep what the solution printed before it
+            print!("{captured_output}");
+            Err(err)
+        }
+    }
+}
+
+// The solution runs on its own thread, so the lines it streams through `AocContext::emit` are
+// reported while it keeps going. Threads cannot be stopped from the outside, so a solution that
+// exceeds its timeout is left running detached and the run goes on without it. Its context
+// reports it as cancelled, so solutions polling it stop right away.
+fn solve_phase(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &RunOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<(AocSolution, String), AocError> {
+    let capture = start_capture(options);
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    let (sender, receiver) = channel();
+    // Cleared once the phase is over, even when the solution keeps going
+    let _progress = options
+        .progress
+        .then(|| PhaseProgress::start(&task.name(), phase));
+    let context = AocContext::new(phase)
+        .with_deadline(deadline)
+        .with_cancellation(options.cancellation.clone())
+        .with_input_source(options.input_source.clone())
+        .with_stream(sender);
+
+    // Spans and runtimes are per thread, the solver continues the ones of the phase
+    #[cfg(feature = "tracing")]
+    let span = tracing::Span::current();
+    #[cfg(feature = "async")]
+    let runtime = tokio::runtime::Handle::try_current().ok();
+    let solver_task = Arc::clone(task);
+    let solver = thread::spawn(move || {
+        #[cfg(feature = "tracing")]
+        let _span = span.entered();
+        #[cfg(feature = "async")]
+        let _runtime = runtime.as_ref().map(tokio::runtime::Handle::enter);
+        solver_task.solve_with_context(context)
+    });
+    let timed_out = || AocError::Timeout {
+        task_name: task.name(),
+        phase,
+        timeout: options.timeout.unwrap_or_default(),
+    };
+    // The stream ends when the solver drops its context
+    let result = loop {
+        let line = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match line {
+            Ok(line) => reporter.on_streamed_output(task.as_ref(), phase, &line),
+            Err(RecvTimeoutError::Disconnected) => {
+                let result = solver
+                    .join()
+                    .unwrap_or_else(|panic| panic::resume_unwind(panic));
+                // A solution polling its context can notice the deadline before the receiver does
+                let expired = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+                break result.map_err(|err| if expired { timed_out() } else { err });
+            }
+            Err(RecvTimeoutError::Timeout) => break Err(timed_out()),
+        }
+    };
+    finish_capture(capture, result)
+}
+
+// Answers that were already rejected are never accepted, without asking again, and solved
+// phases have to keep producing their accepted answer. Forced runs ask about solved phases again.
+fn confirm_phase(
+    task: &SharedAocTask,
+    phase: usize,
+    output: &AocSolution,
+    options: &RunOptions,
+) -> Result<(PhaseStatus, Option<String>), AocError> {
+    let interactive = options.interactive;
+    let mut answers = AnswerLog::load(task.as_ref())?;
+    if task.phase_is_solved(phase)? && !(options.force && interactive) {
+        let regressed = match answers.accepted(phase) {
+            Some(accepted) => accepted != answers::answer_text(output),
+            // Shared repositories may only have the hashes of the answers
+            None => hashed::answer_hash(task.as_ref(), phase)?
+                .is_some_and(|hash| !hashed::matches_answer(&hash, output)),
+        };
+        return Ok(if regressed {
+            (PhaseStatus::Regressed, None)
+        } else {
+            (PhaseStatus::Solved, None)
+        });
+    }
+    if let Some(rejection) = answers.rejection(phase, output) {
+        return Ok((PhaseStatus::RejectedAnswer, Some(rejection)));
+    }
+    // The hashes of a shared repository settle the answers of phases that were not solved here
+    if let Some(hash) = hashed::answer_hash(task.as_ref(), phase)? {
+        if !hashed::matches_answer(&hash, output) {
+            let rejection = format!(
+                "{} does not match the hashed answer",
+                answers::answer_text(output)
+            );
+            return Ok((PhaseStatus::RejectedAnswer, Some(rejection)));
+        }
+        task.mark_phase_as_solved(phase)?;
+        answers.record(phase, output, Verdict::Correct)?;
+        return Ok((PhaseStatus::Solved, None));
+    }
+    // Accepting every answer does not need any prompts
+    let policy = match options.confirm {
+        ConfirmPolicy::Ask if !interactive => ConfirmPolicy::Skip,
+        policy => policy.resolve(),
+    };
+    let accepted = match policy {
+        ConfirmPolicy::Skip => return Ok((PhaseStatus::Unconfirmed, None)),
+        ConfirmPolicy::AcceptAll => {
+            task.mark_phase_as_solved(phase)?;
+            true
+        }
+        ConfirmPolicy::Ask if options.answer_prompt => {
+            task.confirm_answer(phase, &answers::answer_text(output))?
+        }
+        ConfirmPolicy::Ask => task.ask_if_solved(phase)?,
+    };
+    if accepted {
+        // Later runs of the solved phase are checked against the accepted answer
+        answers.record(phase, output, Verdict::Correct)?;
+        Ok((PhaseStatus::Solved, None))
+    } else {
+        // Only the unsolve command clears the solved marker of a phase
+        if let Some(verdict) = answers::ask_verdict(phase)? {
+            answers.record(phase, output, verdict)?;
+        }
+        Ok((PhaseStatus::Unsolved, None))
+    }
+}
+
+fn solved_race_time(task: &SharedAocTask, phase: usize, status: PhaseStatus) -> Option<Duration> {
+    match status {
+        PhaseStatus::Solved | PhaseStatus::PreviouslySolved => {
+            race::race_time(task.as_ref(), phase)
+        }
+        _ => None,
+    }
+}
+
+fn solve_task_phase(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &RunOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<PhaseReport, AocError> {
+    trace_span!("phase", task = %task.name(), phase);
+    #[cfg(feature = "memory")]
+    let memory_tracker = MemoryTracker::start();
+    let start = Instant::now();
+    let result = solve_phase(task, phase, options, reporter);
+    let duration = start.elapsed();
+    // A solution that finished before it noticed the cancellation keeps its answer
+    let status = match result {
+        Err(AocError::Timeout { .. }) => Some(PhaseStatus::TimedOut),
+        Err(_) if options.cancellation.is_cancelled() => Some(PhaseStatus::Cancelled),
+        _ => None,
+    };
+    if let Some(status) = status {
+        return Ok(PhaseReport {
+            duration: Some(duration),
+            ..PhaseReport::new(phase, status)
+        });
+    }
+    let (solution_output, captured_output) = result?;
+    #[cfg(feature = "memory")]
+    let memory = memory_tracker.map(MemoryTracker::finish);
+    reporter.on_phase_output(task.as_ref(), phase, &solution_output);
+
+    // The answers of other inputs are neither confirmed nor checked against the recorded ones
+    let (status, rejection, accepted_answer) = if options.input_source.is_some() {
+        (PhaseStatus::Unconfirmed, None, None)
+    } else {
+        let (status, rejection) = confirm_phase(task, phase, &solution_output, options)?;
+        let accepted_answer = AnswerLog::load(task.as_ref())?
+            .accepted(phase)
+            .map(str::to_owned);
+        (status, rejection, accepted_answer)
+    };
+    #[cfg(feature = "tracing")]
+    tracing::info!(?status, ?duration, "phase finished");
+
+    Ok(PhaseReport {
+        output: Some(solution_output),
+        rejection,
+        accepted_answer,
+        captured_output,
+        duration: Some(duration),
+        race_time: solved_race_time(task, phase, status),
+        #[cfg(feature = "memory")]
+        memory,
+        ..PhaseReport::new(phase, status)
+    })
+}
+
+enum ExampleSource<'e> {
+    Files(&'e (PathBuf, PathBuf), ExampleMetadata),
+    Inline(&'e Example),
+}
+
+impl ExampleSource<'_> {
+    fn name(&self) -> String {
+        match self {
+            ExampleSource::Files((input_path, _), metadata) => {
+                metadata.name.clone().unwrap_or_else(|| {
+                    input_path
+                        .file_name()
+                        .map(|name| {
+                            let name_str = name.to_string_lossy();
+                            name_str[..name_str.len() - 3].to_owned()
+                        })
+                        .unwrap_or("<failed to parse example name>".into())
+                })
+            }
+            ExampleSource::Inline(example) => example.name.clone(),
+        }
+    }
+
+    // Unscoped examples only have the expected outputs of the first phase
+    fn checked(&self, task: &SharedAocTask, phase: usize) -> bool {
+        let example_phase = match self {
+            ExampleSource::Files(_, metadata) if !metadata.phases.is_empty() => {
+                return metadata.phases.contains(&phase)
+            }
+            ExampleSource::Files((input_path, _), _) => task.example_phase(input_path),
+            ExampleSource::Inline(example) => example.phase,
+        };
+        phase == 1 || example_phase == Some(phase)
+    }
+}
+
+fn solve_example_phase(
+    task: &SharedAocTask,
+    example: &ExampleSource,
+    phase: usize,
+    options: &RunOptions,
+) -> Result<ExampleReport, AocError> {
+    trace_span!("example", task = %task.name(), name = %example.name(), phase);
+    let start = Instant::now();
+    let capture = start_capture(options);
+    let result = match example {
+        ExampleSource::Files(paths, _) => task.run_example_test(paths, phase),
+        ExampleSource::Inline(example) => task.run_inline_example(example, phase),
+    };
+    let (example_result, captured_output) = finish_capture(capture, result)?;
+    let checked = example.checked(task, phase);
+    let duration = start.elapsed();
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        passed = example_result.passed,
+        checked,
+        ?duration,
+        "example finished"
+    );
+
+    Ok(ExampleReport {
+        name: example.name(),
+        input_path: match example {
+            ExampleSource::Files((input_path, _), _) => Some(input_path.clone()),
+            ExampleSource::Inline(_) => None,
+        },
+        checked,
+        passed: example_result.passed,
+        output: example_result.output,
+        expected_output: example_result.expected_output,
+        captured_output,
+        duration,
+    })
+}
+
+pub(crate) fn run_examples(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &RunOptions,
+) -> Result<Vec<Result<ExampleReport, AocError>>, AocError> {
+    let example_files = task.phase_example_paths(phase)?;
+    let inline_examples = task
+        .inline_examples()
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut example)| {
+            if example.name.is_empty() {
+                example.name = format!("inline_{}", index + 1);
+            }
+            example
+        })
+        .filter(|example| example.runs_in_phase(phase))
+        .collect::<Vec<_>>();
+    let mut examples = vec![];
+    for paths in &example_files {
+        examples.push(ExampleSource::Files(
+            paths,
+            ExampleMetadata::load(&paths.0)?,
+        ));
+    }
+    examples.extend(inline_examples.iter().map(ExampleSource::Inline));
+    if !options.parallel {
+        return Ok(examples
+            .iter()
+            .map(|example| solve_example_phase(task, example, phase, options))
+            .collect());
+    }
+
+    Ok(thread::scope(|scope| {
+        let handles = examples
+            .iter()
+            .map(|example| scope.spawn(move || solve_example_phase(task, example, phase, options)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("example thread panicked"))
+            .collect()
+    }))
+}
+
+fn run_task_phase(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &RunOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<PhaseReport, AocError> {
+    if options.skip_solved && !options.force && task.phase_is_solved(phase)? {
+        let phase_report = PhaseReport {
+            race_time: solved_race_time(task, phase, PhaseStatus::PreviouslySolved),
+            ..PhaseReport::new(phase, PhaseStatus::PreviouslySolved)
+        };
+        reporter.on_phase_result(task.as_ref(), &phase_report);
+        return Ok(phase_report);
+    }
+
+    let mut examples = vec![];
+    let mut example_failed = false;
+    for example_report in run_examples(task, phase, options)? {
+        let example_report = example_report?;
+        reporter.on_example_result(task.as_ref(), phase, &example_report);
+        example_failed |= example_report.checked && !example_report.passed;
+        examples.push(example_report);
+        if example_failed && options.fail_fast {
+            break;
+        }
+    }
+
+    if example_failed {
+        let phase_report = PhaseReport {
+            examples,
+            ..PhaseReport::new(phase, PhaseStatus::ExampleFailed)
+        };
+        reporter.on_phase_result(task.as_ref(), &phase_report);
+        return Ok(phase_report);
+    }
+
+    let mut phase_report = solve_task_phase(task, phase, options, reporter)?;
+    phase_report.examples = examples;
+    reporter.on_phase_result(task.as_ref(), &phase_report);
+    Ok(phase_report)
+}
+
+pub(crate) fn task_phases(task: &SharedAocTask, filter: &Filter) -> Vec<usize> {
+    (1..=task.phases())
+        .filter(|&phase| filter.matches_phase(phase))
+        .collect()
+}
+
+// Only the solved phases are rerun when verifying them against their accepted answers
+fn phases_to_run(task: &SharedAocTask, options: &RunOptions) -> Result<Vec<usize>, AocError> {
+    let mut phases = vec![];
+    for phase in task_phases(task, &options.filter) {
+        if !options.solved_only || task.phase_is_solved(phase)? {
+            phases.push(phase);
+        }
+    }
+    Ok(phases)
+}
+
+pub(crate) fn filter_tasks<'t>(
+    tasks: &'t [SharedAocTask],
+    filter: &Filter,
+) -> Vec<&'t SharedAocTask> {
+    tasks
+        .iter()
+        .filter(|task| filter.matches_task(task.as_ref()))
+        .collect()
+}
+
+fn run_task(
+    task: &SharedAocTask,
+    options: &RunOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<TaskReport, AocError> {
+    #[cfg(feature = "describe")]
+    if options.interactive {
+        crate::puzzle::offer_example_extraction(task.as_ref(), options.confirm)?;
+    }
+
+    // Solving another input does not start the race of the puzzle
+    if options.input_source.is_none() {
+        race::record_first_run(task.as_ref())?;
+    }
+
+    let mut task_report = TaskReport {
+        name: task.name(),
+        directory: task.directory(),
+        phases: vec![],
+    };
+
+    for phase in phases_to_run(task, options)? {
+        let phase_report = run_task_phase(task, phase, options, reporter)?;
+        let passed = phase_report.passed();
+        task_report.phases.push(phase_report);
+        if (!passed && options.fail_fast) || options.cancellation.is_cancelled() {
+            break;
+        }
+    }
+
+    Ok(task_report)
+}
+
+// Reports a task that was run on a worker thread, asking for the confirmation of its
+// unconfirmed phases when running interactively
+fn replay_task(
+    task: &SharedAocTask,
+    mut task_report: TaskReport,
+    options: &RunOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<TaskReport, AocError> {
+    let mut replayed = 0;
+    for phase_report in task_report.phases.iter_mut() {
+        let mut replayed_examples = 0;
+        for example in &phase_report.examples {
+            reporter.on_example_result(task.as_ref(), phase_report.phase, example);
+            replayed_examples += 1;
+            if example.checked && !example.passed && options.fail_fast {
+                break;
+            }
+        }
+        phase_report.examples.truncate(replayed_examples);
+        if let Some(output) = &phase_report.output {
+            reporter.on_phase_output(task.as_ref(), phase_report.phase, output);
+        }
+        let unconfirmed = match phase_report.status {
+            _ if options.input_source.is_some() => false,
+            PhaseStatus::Unconfirmed => true,
+            PhaseStatus::Solved | PhaseStatus::Regressed => options.force,
+            _ => false,
+        };
+        if let (true, true, Some(output)) = (options.interactive, unconfirmed, &phase_report.output)
+        {
+            (phase_report.status, phase_report.rejection) =
+                confirm_phase(task, phase_report.phase, output, options)?;
+            phase_report.race_time =
+                solved_race_time(task, phase_report.phase, phase_report.status);
+            phase_report.accepted_answer = AnswerLog::load(task.as_ref())?
+                .accepted(phase_report.phase)
+                .map(str::to_owned);
+        }
+        reporter.on_phase_result(task.as_ref(), phase_report);
+
+        replayed += 1;
+        if !phase_report.passed() && options.fail_fast {
+            break;
+        }
+    }
+
+    task_report.phases.truncate(replayed);
+    Ok(task_report)
+}
+
+fn run_tasks_in_parallel(
+    tasks: &[&SharedAocTask],
+    options: &RunOptions,
+) -> Vec<Result<TaskReport, AocError>> {
+    // Prompts are deferred until the results are replayed on the calling thread
+    let worker_options = RunOptions {
+        interactive: false,
+        ..options.clone()
+    };
+    let next_task = AtomicUsize::new(0);
+    let results = Mutex::new((0..tasks.len()).map(|_| None).collect::<Vec<_>>());
+    let workers = thread::available_parallelism()
+        .map(|workers| workers.get())
+        .unwrap_or(1)
+        .min(tasks.len());
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next_task.fetch_add(1, Ordering::SeqCst);
+                let Some(task) = tasks.get(index) else {
+                    break;
+                };
+                let result = run_task(task, &worker_options, &mut NoopReporter);
+                results.lock().unwrap_or_else(|err| err.into_inner())[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|err| err.into_inner())
+        .into_iter()
+        .map(|result| result.expect("every task is run by a worker"))
+        .collect()
+}
+
+pub fn run_tasks(
+    tasks: &[SharedAocTask],
+    options: &RunOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<RunReport, AocError> {
+    let mut tasks = filter_tasks(tasks, &options.filter);
+    if options.solved_only {
+        let mut solved_tasks = vec![];
+        for task in tasks {
+            if !phases_to_run(task, options)?.is_empty() {
+                solved_tasks.push(task);
+            }
+        }
+        tasks = solved_tasks;
+    }
+    let mut run_report = RunReport::default();
+    let mut parallel_results = if options.parallel {
+        run_tasks_in_parallel(&tasks, options).into_iter()
+    } else {
+        vec![].into_iter()
+    };
+
+    for (i, &task) in tasks.iter().enumerate() {
+        reporter.on_task_start(task.as_ref());
+        let task_report = match parallel_results.next() {
+            Some(result) => replay_task(task, result?, options, reporter)?,
+            None => run_task(task, options, reporter)?,
+        };
+
+        let passed = task_report.passed();
+        reporter.on_task_done(task.as_ref(), i, tasks.len(), &task_report);
+        run_report.tasks.push(task_report);
+        if (!passed && options.fail_fast) || options.cancellation.is_cancelled() {
+            break;
+        }
+    }
+
+    reporter.on_run_done(&run_report);
+    Ok(run_report)
+}
+
+// Runs the tasks with the console reporter and the default options unless configured otherwise
+pub struct AocRunner<'t> {
+    tasks: &'t [SharedAocTask],
+    options: RunOptions,
+    reporters: Vec<Box<dyn Reporter + Send + 't>>,
+    theme: Option<Theme>,
+}
+
+impl<'t> AocRunner<'t> {
+    pub fn new(tasks: &'t [SharedAocTask]) -> Self {
+        Self {
+            tasks,
+            options: RunOptions::default(),
+            reporters: vec![Box::new(ConsoleReporter::default())],
+            theme: None,
+        }
+    }
+
+    pub fn options(mut self, options: RunOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.options.interactive = interactive;
+        self
+    }
+
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.options.parallel = parallel;
+        self
+    }
+
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.options.fail_fast = fail_fast;
+        self
+    }
+
+    pub fn progress(mut self, progress: bool) -> Self {
+        self.options.progress = progress;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.options.timeout = timeout;
+        self
+    }
+
+    pub fn capture_output(mut self, capture_output: bool) -> Self {
+        self.options.capture_output = capture_output;
+        self
+    }
+
+    pub fn skip_solved(mut self, skip_solved: bool) -> Self {
+        self.options.skip_solved = skip_solved;
+        self
+    }
+
+    pub fn force(mut self, force: bool) -> Self {
+        self.options.force = force;
+        self
+    }
+
+    pub fn answer_prompt(mut self, answer_prompt: bool) -> Self {
+        self.options.answer_prompt = answer_prompt;
+        self
+    }
+
+    pub fn confirm(mut self, confirm: ConfirmPolicy) -> Self {
+        self.options.confirm = confirm;
+        self
+    }
+
+    pub fn solved_only(mut self, solved_only: bool) -> Self {
+        self.options.solved_only = solved_only;
+        self
+    }
+
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.options.filter = filter;
+        self
+    }
+
+    pub fn input_source(mut self, input_source: Option<Arc<dyn InputSource>>) -> Self {
+        self.options.input_source = input_source;
+        self
+    }
+
+    // Cancelling the token, or pressing Ctrl-C during the run, stops it after the current phase
+    pub fn cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.options.cancellation = cancellation;
+        self
+    }
+
+    // Replaces all reporters, including the default console reporter
+    pub fn reporter(mut self, reporter: impl Reporter + Send + 't) -> Self {
+        self.reporters = vec![Box::new(reporter)];
+        self
+    }
+
+    pub fn add_reporter(mut self, reporter: impl Reporter + Send + 't) -> Self {
+        self.reporters.push(Box::new(reporter));
+        self
+    }
+
+    // The theme is process-wide, it also applies to the output outside of the runner
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn run(mut self) -> Result<RunReport, AocError> {
+        if let Some(theme) = self.theme {
+            style::set_theme(theme);
+        }
+        let _interrupts = cancel::cancel_on_interrupt(&self.options.cancellation);
+        run_tasks(self.tasks, &self.options, &mut self.reporters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        answers::{RecordedAnswer, Verdict},
+        hashed::{HashedOutput, HASHED_ANSWERS_FILE},
+        source::StringSource,
+        state,
+        testing::{MockTask, TempDir},
+        AocSolution, Example, SharedAocTask,
+    };
+
+    fn sum_lines(input: &str) -> AocSolution {
+        input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|num| num.parse::<i32>().unwrap_or(0))
+                    .sum::<i32>()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn sum_task() -> MockTask {
+        MockTask::new(|input, _context| Ok(sum_lines(input)))
+            .with_input("1 2\n3 4")
+            .with_example(Example::new("2 2", "4"))
+            .with_example(Example::new("3 4\n1 1", "7\n2").phase(2))
+    }
+
+    #[test]
+    fn parallel_run_matches_sequential_run() {
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(sum_task()), Arc::new(sum_task())];
+        let sequential_options = RunOptions {
+            interactive: false,
+            parallel: false,
+            fail_fast: true,
+            progress: false,
+            timeout: None,
+            capture_output: true,
+            solved_only: false,
+            skip_solved: false,
+            force: false,
+            filter: Filter::all(),
+            input_source: None,
+            answer_prompt: false,
+            confirm: ConfirmPolicy::Ask,
+            cancellation: CancellationToken::default(),
+        };
+        let parallel_options = RunOptions {
+            parallel: true,
+            ..sequential_options.clone()
+        };
+
+        let sequential = AocRunner::new(&tasks)
+            .options(sequential_options)
+            .reporter(NoopReporter)
+            .run()
+            .unwrap();
+        let parallel = run_tasks(&tasks, &parallel_options, &mut NoopReporter).unwrap();
+
+        assert!(sequential.passed());
+        assert!(parallel.passed());
+        assert_eq!(parallel.tasks.len(), 2);
+        for (sequential_task, parallel_task) in sequential.tasks.iter().zip(&parallel.tasks) {
+            assert_eq!(sequential_task.phases.len(), parallel_task.phases.len());
+            for (sequential_phase, parallel_phase) in
+                sequential_task.phases.iter().zip(&parallel_task.phases)
+            {
+                assert_eq!(sequential_phase.output, parallel_phase.output);
+                let example_names = |phase: &PhaseReport| {
+                    phase
+                        .examples
+                        .iter()
+                        .map(|example| example.name.clone())
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(
+                    example_names(sequential_phase),
+                    example_names(parallel_phase)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cancelled_phases_are_reported() {
+        let endless_task = MockTask::new(|input, context| {
+            if context.is_example() {
+                return Ok(sum_lines(input));
+            }
+            loop {
+                context.check_cancelled()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(endless_task), Arc::new(sum_task())];
+        let cancellation = CancellationToken::new();
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            fail_fast: false,
+            filter: Filter::all().phase(2),
+            cancellation: cancellation.clone(),
+            ..RunOptions::default()
+        };
+        let cancel = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            cancellation.cancel();
+        });
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+        cancel.join().unwrap();
+
+        assert_eq!(report.tasks.len(), 1);
+        assert_eq!(report.tasks[0].phases[0].status, PhaseStatus::Cancelled);
+        assert_eq!(report.status(), crate::report::RunStatus::Unsolved);
+    }
+
+    #[test]
+    fn finished_phases_keep_their_answers_when_cancelled() {
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(sum_task())];
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            filter: Filter::all().phase(1),
+            cancellation,
+            ..RunOptions::default()
+        };
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        let phase = &report.tasks[0].phases[0];
+        assert_eq!(phase.status, PhaseStatus::Unconfirmed);
+        assert_eq!(phase.output, Some(vec!["3".to_owned(), "7".to_owned()]));
+    }
+
+    #[test]
+    fn timed_out_phases_are_reported() {
+        let endless_task = MockTask::new(|input, context| {
+            if context.is_example() {
+                return Ok(sum_lines(input));
+            }
+            loop {
+                context.check_cancelled()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(endless_task), Arc::new(sum_task())];
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            fail_fast: false,
+            filter: Filter::all().phase(1),
+            timeout: Some(Duration::from_millis(50)),
+            ..RunOptions::default()
+        };
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        assert_eq!(report.tasks.len(), 2);
+        assert_eq!(report.tasks[0].phases[0].status, PhaseStatus::TimedOut);
+        assert_eq!(report.tasks[1].phases[0].status, PhaseStatus::Unconfirmed);
+        assert_eq!(report.status(), crate::report::RunStatus::Unsolved);
+    }
+
+    #[test]
+    fn runaway_solutions_do_not_hang_the_run() {
+        // Never looks at its context, so it can only be left behind
+        let runaway_task = MockTask::new(|input, context| {
+            if context.is_example() {
+                return Ok(sum_lines(input));
+            }
+            loop {
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(runaway_task)];
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            filter: Filter::all().phase(1),
+            timeout: Some(Duration::from_millis(50)),
+            ..RunOptions::default()
+        };
+        let start = Instant::now();
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        assert_eq!(report.tasks[0].phases[0].status, PhaseStatus::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn tasks_and_runners_move_between_threads() {
+        fn assert_send<T: Send>() {}
+        assert_send::<AocRunner<'static>>();
+        assert_send::<RunReport>();
+
+        let task: SharedAocTask = Arc::new(sum_task());
+        let outputs = (1..=2)
+            .map(|phase| {
+                let task = Arc::clone(&task);
+                thread::spawn(move || task.solve(phase))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(outputs[0], task.solve(1).unwrap());
+        assert_eq!(outputs[1], task.solve(2).unwrap());
+    }
+
+    #[test]
+    fn hashed_answers_settle_unsolved_phases() {
+        let directory = TempDir::new("hashed_answers");
+        directory.write(
+            HASHED_ANSWERS_FILE,
+            &format!(
+                "{}\n{}\n",
+                HashedOutput::new("3\n7"),
+                HashedOutput::new("10")
+            ),
+        );
+        let task = sum_task().with_directory(directory.path());
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(task)];
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            fail_fast: false,
+            ..RunOptions::default()
+        };
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        let statuses = report.tasks[0]
+            .phases
+            .iter()
+            .map(|phase| phase.status)
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, [PhaseStatus::Solved, PhaseStatus::RejectedAnswer]);
+        assert!(tasks[0].phase_is_solved(1).unwrap());
+        assert!(!tasks[0].phase_is_solved(2).unwrap());
+    }
+
+    #[test]
+    fn other_inputs_leave_the_answers_alone() {
+        let task = sum_task().with_name("Other Input Task").with_solved(1);
+        state::with_task_state(&task, |state| {
+            state.answers.push(RecordedAnswer {
+                phase: 1,
+                verdict: Verdict::Correct,
+                answer: "3\n7".to_owned(),
+            });
+        })
+        .unwrap();
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(task)];
+        let task_state =
+            || state::with_task_state(tasks[0].as_ref(), |state| state.clone()).unwrap();
+        let original_state = task_state();
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            confirm: ConfirmPolicy::AcceptAll,
+            input_source: Some(Arc::new(StringSource::new("5 5"))),
+            ..RunOptions::default()
+        };
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        assert!(report.tasks[0]
+            .phases
+            .iter()
+            .all(|phase| phase.status == PhaseStatus::Unconfirmed));
+        assert!(tasks[0].phase_is_solved(1).unwrap());
+        assert!(!tasks[0].phase_is_solved(2).unwrap());
+        assert_eq!(task_state(), original_state);
+    }
+
+    #[test]
+    fn verifying_runs_only_the_solved_phases() {
+        let tasks: Vec<SharedAocTask> = vec![
+            Arc::new(sum_task().with_name("Verified Task").with_solved(1)),
+            Arc::new(sum_task().with_name("Unverified Task")),
+        ];
+        for force in [false, true] {
+            let options = RunOptions {
+                interactive: false,
+                progress: false,
+                fail_fast: false,
+                solved_only: true,
+                force,
+                ..RunOptions::default()
+            };
+            let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+            assert_eq!(report.tasks.len(), 1);
+            assert_eq!(report.tasks[0].name, "Verified Task");
+            let phases = &report.tasks[0].phases;
+            assert_eq!(phases.len(), 1);
+            assert_eq!(
+                (phases[0].phase, phases[0].status),
+                (1, PhaseStatus::Solved)
+            );
+        }
+    }
+
+    #[test]
+    fn solved_phases_are_skipped_unless_forced() {
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(
+            sum_task().with_name("Skipped Task").with_solved(1),
+        )];
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            fail_fast: false,
+            skip_solved: true,
+            ..RunOptions::default()
+        };
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        let phases = &report.tasks[0].phases;
+        assert_eq!(phases[0].status, PhaseStatus::PreviouslySolved);
+        assert!(phases[0].examples.is_empty() && phases[0].output.is_none());
+        assert_eq!(phases[1].status, PhaseStatus::Unconfirmed);
+        assert!(report.passed());
+
+        let options = RunOptions {
+            force: true,
+            ..options
+        };
+        let report = run_tasks(&tasks, &options, &mut NoopReporter).unwrap();
+
+        let phases = &report.tasks[0].phases;
+        assert_eq!(phases[0].status, PhaseStatus::Solved);
+        assert_eq!(phases[0].examples.len(), 1);
+        assert_eq!(phases[0].output, Some(vec!["3".to_owned(), "7".to_owned()]));
+    }
+}
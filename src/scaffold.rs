@@ -0,0 +1,201 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+use crate::{error::AocError, input};
+
+pub const MODULES_MARKER: &str = "// aoc-framework: new task modules";
+pub const TASKS_MARKER: &str = "// aoc-framework: new tasks";
+
+const TASK_TEMPLATE: &str = r#"use std::{error::Error, path::PathBuf};
+
+use aoc_framework::{traits::*, AocContext, AocSolution, AocStringIter, AocTask};
+
+pub struct {struct_name};
+
+impl AocTask for {struct_name} {
+    fn directory(&self) -> PathBuf {
+        "{directory}".into()
+    }
+
+    fn solution(
+        &self,
+        input: AocStringIter,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        let lines = input.collect::<Vec<_>>();
+        match context.phase() {
+            1 => lines.len().solved(),
+            _ => lines.len().solved(),
+        }
+    }
+}
+"#;
+
+const TASK_LIST_TEMPLATE: &str = r#"use std::sync::Arc;
+
+use aoc_framework::SharedAocTask;
+
+{modules_marker}
+
+pub fn tasks() -> Vec<SharedAocTask> {
+    vec![
+        {tasks_marker}
+    ]
+}
+"#;
+
+fn write_file(path: &Path, contents: &str) -> Result<(), AocError> {
+    fs::write(path, contents).map_err(|err| AocError::IOWriteError {
+        path: path.to_string_lossy().to_string(),
+        source: err,
+    })
+}
+
+fn insert_before_marker(contents: &mut String, marker: &str, line: &str) -> Option<()> {
+    let marker_start = contents.find(marker)?;
+    let line_start = contents[..marker_start]
+        .rfind('\n')
+        .map(|newline| newline + 1)
+        .unwrap_or(0);
+    let indent = contents[line_start..marker_start].to_owned();
+    contents.insert_str(line_start, &format!("{indent}{line}\n"));
+    Some(())
+}
+
+fn register_task(
+    task_list_path: &Path,
+    module_name: &str,
+    struct_name: &str,
+) -> Result<(), AocError> {
+    let mut task_list = if task_list_path.is_file() {
+        fs::read_to_string(task_list_path).map_err(|err| AocError::IOReadError {
+            path: task_list_path.to_string_lossy().to_string(),
+            source: err,
+        })?
+    } else {
+        TASK_LIST_TEMPLATE
+            .replace("{modules_marker}", MODULES_MARKER)
+            .replace("{tasks_marker}", TASKS_MARKER)
+    };
+
+    let module_line = format!("pub mod {module_name};");
+    if task_list.lines().any(|line| line.trim() == module_line) {
+        return Ok(());
+    }
+
+    for (marker, line) in [
+        (MODULES_MARKER, module_line),
+        (
+            TASKS_MARKER,
+            format!("Arc::new({module_name}::{struct_name}),"),
+        ),
+    ] {
+        insert_before_marker(&mut task_list, marker, &line).ok_or_else(|| {
+            AocError::MissingScaffoldMarker {
+                path: task_list_path.to_string_lossy().to_string(),
+                marker: marker.to_owned(),
+            }
+        })?;
+    }
+
+    write_file(task_list_path, &task_list)
+}
+
+// Tasks of a year are kept in a directory of the year, unless the tasks directory already names
+// one, so that the tasks know their year
+pub fn year_directory(tasks_directory: &Path, year: u16) -> PathBuf {
+    match input::puzzle_year(tasks_directory) {
+        Some(_) => tasks_directory.to_path_buf(),
+        None => tasks_directory.join(format!("year_{year}")),
+    }
+}
+
+pub fn scaffold_task(day: u8, tasks_directory: &Path) -> Result<PathBuf, AocError> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::InvalidDay { day });
+    }
+    let module_name = format!("day_{day:02}");
+    let struct_name = format!("Day{day:02}");
+    let task_directory = tasks_directory.join(&module_name);
+    let data_directory = input::data_directory(&task_directory);
+
+    for directory in [&task_directory, &data_directory] {
+        fs::create_dir_all(directory).map_err(|err| AocError::IOWriteError {
+            path: directory.to_string_lossy().to_string(),
+            source: err,
+        })?;
+    }
+
+    for file_name in ["example_1_in", "example_1_out"] {
+        let path = data_directory.join(file_name);
+        if !path.exists() {
+            File::create(&path).map_err(|err| AocError::IOWriteError {
+                path: path.to_string_lossy().to_string(),
+                source: err,
+            })?;
+        }
+    }
+
+    let module_path = task_directory.join("mod.rs");
+    if !module_path.exists() {
+        let module = TASK_TEMPLATE
+            .replace("{struct_name}", &struct_name)
+            .replace(
+                "{directory}",
+                &task_directory.to_string_lossy().replace('\\', "/"),
+            );
+        write_file(&module_path, &module)?;
+    }
+
+    register_task(&tasks_directory.join("mod.rs"), &module_name, &struct_name)?;
+
+    Ok(task_directory)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn scaffold_registers_tasks() {
+        let directory = TempDir::new("scaffold");
+        let tasks_directory = directory.join("tasks");
+
+        let task_directory = scaffold_task(5, &tasks_directory).unwrap();
+        scaffold_task(6, &tasks_directory).unwrap();
+        scaffold_task(5, &tasks_directory).unwrap();
+        assert!(matches!(
+            scaffold_task(26, &tasks_directory),
+            Err(AocError::InvalidDay { day: 26 })
+        ));
+
+        assert!(task_directory.join("example_1_in").is_file());
+        assert!(task_directory.join("example_1_out").is_file());
+        let module = fs::read_to_string(task_directory.join("mod.rs")).unwrap();
+        assert!(module.contains("impl AocTask for Day05"));
+
+        let task_list = fs::read_to_string(tasks_directory.join("mod.rs")).unwrap();
+        assert_eq!(task_list.matches("pub mod day_05;").count(), 1);
+        assert!(task_list.contains("pub mod day_06;\n"));
+        assert!(task_list
+            .contains("        Arc::new(day_05::Day05),\n        Arc::new(day_06::Day06),\n"));
+    }
+
+    #[test]
+    fn dated_tasks_are_scaffolded_by_year() {
+        let directory = TempDir::new("scaffold_year");
+        let tasks_directory = year_directory(&directory.join("tasks"), 2024);
+        assert_eq!(tasks_directory, directory.join("tasks/year_2024"));
+        assert_eq!(
+            year_directory(&directory.join("aoc_2023/src"), 2024),
+            directory.join("aoc_2023/src")
+        );
+
+        let task_directory = scaffold_task(5, &tasks_directory).unwrap();
+        assert_eq!(input::puzzle_date(&task_directory), Some((2024, 5)));
+        assert!(tasks_directory.join("mod.rs").is_file());
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+// Nodes are stored once and referred to by their index, so they only have to be hashable
+struct Visited<N> {
+    nodes: Vec<N>,
+    parents: Vec<usize>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new(start: N) -> Self {
+        Self {
+            nodes: vec![start.clone()],
+            parents: vec![0],
+            indices: HashMap::from([(start, 0)]),
+        }
+    }
+
+    // Returns the index of the node, unless it was already visited
+    fn visit(&mut self, node: N, parent: usize) -> Option<usize> {
+        let Entry::Vacant(entry) = self.indices.entry(node) else {
+            return None;
+        };
+        let index = self.nodes.len();
+        self.nodes.push(entry.key().clone());
+        self.parents.push(parent);
+        entry.insert(index);
+        Some(index)
+    }
+
+    // The nodes from the start to the node at the index, both included
+    fn path(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].clone()];
+        while index != 0 {
+            index = self.parents[index];
+            path.push(self.nodes[index].clone());
+        }
+        path.reverse();
+        path
+    }
+}
+
+// The shortest path from the start to the first node that is a goal, the start and the goal
+// included, when every step costs the same
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&visited.nodes[index]) {
+            return Some(visited.path(index));
+        }
+        for neighbor in neighbors(&visited.nodes[index]) {
+            queue.extend(visited.visit(neighbor, index));
+        }
+    }
+    None
+}
+
+// The number of steps to every node reachable from the start, e.g. for flood fills
+pub fn bfs_distances<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for neighbor in neighbors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(neighbor.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((neighbor, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+// Some path from the start to a goal, searching depth first
+pub fn dfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new(start);
+    let mut stack = vec![0];
+    while let Some(index) = stack.pop() {
+        if is_goal(&visited.nodes[index]) {
+            return Some(visited.path(index));
+        }
+        for neighbor in neighbors(&visited.nodes[index]) {
+            stack.extend(visited.visit(neighbor, index));
+        }
+    }
+    None
+}
+
+// The cheapest path from the start to a goal and its cost, the neighbors come with the cost of
+// the step to them
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+// Dijkstra guided by a heuristic, which must never overestimate the remaining cost for the
+// path to be the cheapest one, e.g. the manhattan distance to the goal on a grid
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut visited = Visited::new(start);
+    let mut costs = vec![C::default()];
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // Cheaper paths to the node were found after this entry was queued
+        if cost > costs[index] {
+            continue;
+        }
+        if is_goal(&visited.nodes[index]) {
+            return Some((visited.path(index), cost));
+        }
+        for (neighbor, step) in neighbors(&visited.nodes[index]) {
+            let neighbor_cost = cost + step;
+            let estimate = heuristic(&neighbor);
+            let neighbor_index = match visited.indices.get(&neighbor) {
+                Some(&known) if costs[known] <= neighbor_cost => continue,
+                Some(&known) => {
+                    costs[known] = neighbor_cost;
+                    visited.parents[known] = index;
+                    known
+                }
+                None => {
+                    costs.push(neighbor_cost);
+                    visited.nodes.len()
+                }
+            };
+            visited.visit(neighbor, index);
+            queue.push(Reverse((
+                neighbor_cost + estimate,
+                neighbor_cost,
+                neighbor_index,
+            )));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::{Bounds, Point};
+
+    #[test]
+    fn searches_find_the_shortest_paths() {
+        let grid = ["S.#...", ".##.#.", "...#E.", ".#...#"];
+        let cell = |point: Point| grid[point.y as usize].as_bytes()[point.x as usize];
+        let bounds = Bounds::from_size(6, 4);
+        let open = move |point: &Point| {
+            point
+                .neighbors()
+                .filter(|&neighbor| bounds.contains(neighbor) && cell(neighbor) != b'#')
+                .collect::<Vec<_>>()
+        };
+        let goal = Point::new(4, 2);
+        let is_goal = |point: &Point| *point == goal;
+
+        let path = bfs(Point::ZERO, open, is_goal).unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (Point::ZERO, goal));
+        assert_eq!(path.len(), 9);
+        assert_eq!(bfs_distances(Point::ZERO, open)[&goal], 8);
+        assert!(dfs(Point::ZERO, open, is_goal).is_some_and(|path| path.len() >= 9));
+
+        // Moving down costs 5, the heuristic still never overestimates
+        let weighted = |point: &Point| {
+            open(point)
+                .into_iter()
+                .map(|neighbor| (neighbor, if neighbor.y > point.y { 5 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let (path, cost) = dijkstra(Point::ZERO, weighted, is_goal).unwrap();
+        let (astar_path, astar_cost) = astar(
+            Point::ZERO,
+            weighted,
+            |point| point.manhattan(goal),
+            is_goal,
+        )
+        .unwrap();
+        assert_eq!(cost, astar_cost);
+        assert_eq!(path.len(), astar_path.len());
+        assert_eq!(cost, 20);
+        assert_eq!(bfs(Point::ZERO, open, |point| cell(*point) == b'X'), None);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::VecDeque,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+    thread,
+};
+
+use axum::{extract::State, response::Html, routing::get, Json, Router};
+use serde::Serialize;
+
+use crate::{
+    baseline::{baselines_path, BaselineStore},
+    error::AocError,
+    events::{run_events, RunEvent},
+    report::{PhaseReport, PhaseStatus},
+    style::Stylize,
+    RunOptions, SharedAocTask, DOT,
+};
+
+const INDEX: &str = include_str!("serve/index.html");
+const LOG_LINES: usize = 200;
+
+type SharedDashboard = Arc<Mutex<Dashboard>>;
+
+#[derive(Debug, Clone, Serialize)]
+struct PhaseResult {
+    #[serde(flatten)]
+    report: PhaseReport,
+    diff: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DayState {
+    name: String,
+    day: Option<u8>,
+    solved: Vec<bool>,
+    results: Vec<Option<PhaseResult>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BaselineEntry {
+    name: String,
+    task: String,
+    phase: usize,
+    mean: f64,
+    median: f64,
+}
+
+// Everything the page shows, the run updates it through its events
+#[derive(Debug, Clone, Serialize)]
+struct Dashboard {
+    running: Option<String>,
+    finished: bool,
+    days: Vec<DayState>,
+    log: VecDeque<String>,
+    baselines: Vec<BaselineEntry>,
+}
+
+impl Dashboard {
+    fn new(tasks: &[SharedAocTask]) -> Result<Self, AocError> {
+        let days = tasks
+            .iter()
+            .map(|task| {
+                Ok(DayState {
+                    name: task.name(),
+                    day: task.day(),
+                    solved: (1..=task.phases())
+                        .map(|phase| task.phase_is_solved(phase))
+                        .collect::<Result<_, AocError>>()?,
+                    results: vec![None; task.phases()],
+                })
+            })
+            .collect::<Result<_, AocError>>()?;
+        let baselines = BaselineStore::load(&baselines_path())?
+            .iter()
+            .map(|(name, task, phase, baseline)| BaselineEntry {
+                name: name.to_owned(),
+                task: task.to_owned(),
+                phase,
+                mean: baseline.mean.as_secs_f64(),
+                median: baseline.median.as_secs_f64(),
+            })
+            .collect();
+        Ok(Self {
+            running: None,
+            finished: false,
+            days,
+            log: VecDeque::new(),
+            baselines,
+        })
+    }
+
+    fn log(&mut self, line: String) {
+        if self.log.len() == LOG_LINES {
+            self.log.pop_front();
+        }
+        self.log.push_back(line);
+    }
+
+    fn apply(&mut self, event: RunEvent) {
+        match event {
+            RunEvent::TaskStarted { task } => {
+                self.log(format!("Started {task}"));
+                self.running = Some(task);
+            }
+            RunEvent::ExampleFinished {
+                task,
+                phase,
+                example,
+            } if example.checked && !example.passed => {
+                self.log(format!(
+                    "{task} phase {phase}: example {} failed",
+                    example.name
+                ));
+            }
+            RunEvent::PhaseFinished { task, phase } => {
+                let duration = phase
+                    .duration
+                    .map(|duration| format!(" in {duration:.2?}"))
+                    .unwrap_or_default();
+                self.log(format!(
+                    "{task} phase {}: {:?}{duration}",
+                    phase.phase, phase.status
+                ));
+                let Some(day) = self.days.iter_mut().find(|day| day.name == task) else {
+                    return;
+                };
+                let index = phase.phase - 1;
+                if matches!(
+                    phase.status,
+                    PhaseStatus::Solved | PhaseStatus::PreviouslySolved
+                ) {
+                    day.solved[index] = true;
+                }
+                day.results[index] = Some(PhaseResult {
+                    diff: phase.diff(),
+                    report: phase,
+                });
+            }
+            RunEvent::RunFinished { report } => {
+                let passed = report.tasks.iter().filter(|task| task.passed()).count();
+                self.log(format!(
+                    "Finished, {passed}/{} tasks passed",
+                    report.tasks.len()
+                ));
+                self.running = None;
+                self.finished = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn lock(dashboard: &SharedDashboard) -> MutexGuard<'_, Dashboard> {
+    dashboard.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+async fn index() -> Html<&'static str> {
+    Html(INDEX)
+}
+
+async fn state(State(dashboard): State<SharedDashboard>) -> Json<Dashboard> {
+    Json(lock(&dashboard).clone())
+}
+
+fn server_error(source: std::io::Error) -> AocError {
+    AocError::ServerError { source }
+}
+
+// Runs the tasks in the background and serves their progress until the process is stopped
+pub fn serve(
+    tasks: Vec<SharedAocTask>,
+    options: RunOptions,
+    address: SocketAddr,
+) -> Result<(), AocError> {
+    let dashboard = Arc::new(Mutex::new(Dashboard::new(&tasks)?));
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(server_error)?;
+    let listener = runtime
+        .block_on(tokio::net::TcpListener::bind(address))
+        .map_err(server_error)?;
+    println!(
+        "{} Serving the dashboard on {}",
+        DOT.info(),
+        format!("http://{address}").bold()
+    );
+
+    let updated = Arc::clone(&dashboard);
+    thread::spawn(move || {
+        let mut events = run_events(tasks, options);
+        for event in events.by_ref() {
+            lock(&updated).apply(event);
+        }
+        if let Err(err) = events.finish() {
+            let mut dashboard = lock(&updated);
+            dashboard.log(format!("The run failed: {err}"));
+            dashboard.running = None;
+            dashboard.finished = true;
+        }
+    });
+
+    let app = Router::new()
+        .route("/", get(index))
+        .route("/api/state", get(state))
+        .with_state(dashboard);
+    runtime
+        .block_on(async { axum::serve(listener, app).await })
+        .map_err(server_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn events_update_the_dashboard() {
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(MockTask::new(|_, _| Ok(vec![])))];
+        let mut dashboard = Dashboard::new(&tasks).unwrap();
+        let name = tasks[0].name();
+        dashboard.apply(RunEvent::TaskStarted { task: name.clone() });
+        assert_eq!(dashboard.running.as_deref(), Some(name.as_str()));
+
+        dashboard.apply(RunEvent::PhaseFinished {
+            task: name,
+            phase: PhaseReport {
+                output: Some(vec!["6".to_owned()]),
+                ..PhaseReport::new(2, PhaseStatus::Solved)
+            },
+        });
+        assert!(dashboard.days[0].solved[1]);
+        assert!(dashboard.days[0].results[1].is_some());
+        assert_eq!(dashboard.log.len(), 2);
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::error::AocError;
+
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+const CONFIG_DIRECTORY: &str = "aoc-framework";
+const CONFIG_FILE: &str = "session";
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "aoc-framework";
+#[cfg(feature = "keyring")]
+const KEYRING_USER: &str = "session";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSource {
+    Environment,
+    ConfigFile,
+    Keyring,
+}
+
+impl Display for SessionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionSource::Environment => write!(f, "the {SESSION_ENV_VAR} environment variable"),
+            SessionSource::ConfigFile => write!(f, "the session config file"),
+            SessionSource::Keyring => write!(f, "the OS keyring"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    token: String,
+    source: SessionSource,
+}
+
+// Accepts both the bare cookie value and a copied `session=...` cookie
+fn normalize_token(token: &str) -> Option<String> {
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token).trim();
+    (!token.is_empty()).then(|| token.to_owned())
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|directory| directory.join(CONFIG_DIRECTORY).join(CONFIG_FILE))
+}
+
+pub(crate) fn missing_session_error() -> AocError {
+    AocError::MissingSession {
+        variable: SESSION_ENV_VAR.to_owned(),
+        config_path: config_path()
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|| "<config directory>".to_owned()),
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_entry() -> Result<keyring::Entry, AocError> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|err| AocError::KeyringError { source: err })
+}
+
+impl Session {
+    pub fn new(token: &str, source: SessionSource) -> Option<Self> {
+        normalize_token(token).map(|token| Self { token, source })
+    }
+
+    // Looks for the session in the environment, the config file and the OS keyring, in that order
+    pub fn load() -> Result<Self, AocError> {
+        if let Some(session) = Self::from_env() {
+            return Ok(session);
+        }
+        if let Some(session) = Self::from_config_file()? {
+            return Ok(session);
+        }
+        #[cfg(feature = "keyring")]
+        if let Some(session) = Self::from_keyring()? {
+            return Ok(session);
+        }
+
+        Err(missing_session_error())
+    }
+
+    pub fn from_env() -> Option<Self> {
+        std::env::var(SESSION_ENV_VAR)
+            .ok()
+            .and_then(|token| Self::new(&token, SessionSource::Environment))
+    }
+
+    pub fn from_config_file() -> Result<Option<Self>, AocError> {
+        let Some(path) = config_path().filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+        let token = fs::read_to_string(&path).map_err(|err| AocError::IOReadError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        Ok(Self::new(&token, SessionSource::ConfigFile))
+    }
+
+    #[cfg(feature = "keyring")]
+    pub fn from_keyring() -> Result<Option<Self>, AocError> {
+        match keyring_entry()?.get_password() {
+            Ok(token) => Ok(Self::new(&token, SessionSource::Keyring)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(AocError::KeyringError { source: err }),
+        }
+    }
+
+    pub fn save_to_config_file(&self) -> Result<PathBuf, AocError> {
+        let path = config_path().ok_or(AocError::MissingConfigDirectory)?;
+        let write_error = |err| AocError::IOWriteError {
+            path: path.to_string_lossy().to_string(),
+            source: err,
+        };
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(write_error)?;
+        }
+        write_private_file(&path, &self.token).map_err(write_error)?;
+        Ok(path)
+    }
+
+    #[cfg(feature = "keyring")]
+    pub fn save_to_keyring(&self) -> Result<(), AocError> {
+        keyring_entry()?
+            .set_password(&self.token)
+            .map_err(|err| AocError::KeyringError { source: err })
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn source(&self) -> SessionSource {
+        self.source
+    }
+
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.token)
+    }
+
+    // Tells the sessions apart without revealing them, e.g. in the paths of cached inputs
+    pub fn id(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        Sha256::digest(self.token.as_bytes())
+            .iter()
+            .take(8)
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    pub fn expired_error(&self) -> AocError {
+        AocError::ExpiredSession {
+            origin: self.source.to_string(),
+        }
+    }
+}
+
+// Only the owner may read the session, even when the file was created with wider permissions
+fn write_private_file(path: &Path, contents: &str) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::{
+            io::Write,
+            os::unix::fs::{OpenOptionsExt, PermissionsExt},
+        };
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(contents.as_bytes())
+    }
+    #[cfg(not(unix))]
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_session_tokens() {
+        assert_eq!(
+            normalize_token(" 53616c7465640a \n"),
+            Some("53616c7465640a".to_owned())
+        );
+        assert_eq!(
+            normalize_token("session=53616c7465640a"),
+            Some("53616c7465640a".to_owned())
+        );
+        assert_eq!(normalize_token("session= "), None);
+        assert_eq!(normalize_token(""), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn session_files_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        use crate::testing::TempDir;
+
+        let directory = TempDir::new("session");
+        let path = directory.write("session", "");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        write_private_file(&path, "53616c7465640a").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "53616c7465640a");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
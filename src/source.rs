@@ -0,0 +1,236 @@
+use std::{
+    fmt::Debug,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Arc, Mutex},
+};
+
+use crate::error::AocError;
+
+// Where the input of a task comes from, the input file of the task by default. Tasks can
+// override `AocTask::input_source`, and a run can replace the source of all of its tasks.
+pub trait InputSource: Debug + Send + Sync {
+    // Shown in place of the input path, e.g. in errors
+    fn describe(&self) -> String;
+
+    // Sources backed by a file are solved from the file directly, which keeps it memory mapped
+    // and lets typed tasks cache its parsed form
+    fn path(&self) -> Option<&Path> {
+        None
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AocError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl InputSource for FileSource {
+    fn describe(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    fn path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AocError> {
+        fs::read(&self.path).map_err(|err| AocError::IOReadError {
+            path: self.describe(),
+            source: err,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StringSource {
+    input: String,
+}
+
+impl StringSource {
+    pub fn new(input: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+        }
+    }
+}
+
+impl InputSource for StringSource {
+    fn describe(&self) -> String {
+        "<string input>".to_owned()
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AocError> {
+        Ok(self.input.clone().into_bytes())
+    }
+}
+
+// Stdin can only be read once, the input is kept for the following phases
+#[derive(Debug, Default)]
+pub struct StdinSource {
+    input: Mutex<Option<Vec<u8>>>,
+}
+
+impl StdinSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InputSource for StdinSource {
+    fn describe(&self) -> String {
+        "<stdin>".to_owned()
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AocError> {
+        let mut input = self.input.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(input) = input.as_ref() {
+            return Ok(input.clone());
+        }
+        let mut read = vec![];
+        io::stdin()
+            .read_to_end(&mut read)
+            .map_err(|err| AocError::IOReadError {
+                path: self.describe(),
+                source: err,
+            })?;
+        Ok(input.insert(read).clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UrlSource {
+    url: String,
+}
+
+impl UrlSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+impl InputSource for UrlSource {
+    fn describe(&self) -> String {
+        self.url.clone()
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AocError> {
+        let response = ureq::get(&self.url)
+            .call()
+            .map_err(|err| AocError::RequestError {
+                url: self.url.clone(),
+                source: Box::new(err),
+            })?;
+        let mut input = vec![];
+        response
+            .into_reader()
+            .read_to_end(&mut input)
+            .map_err(|err| AocError::IOReadError {
+                path: self.describe(),
+                source: err,
+            })?;
+        Ok(input)
+    }
+}
+
+// The stdout of a program, e.g. a generator or a command decrypting the input
+#[derive(Debug, Clone)]
+pub struct CommandSource {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandSource {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: vec![],
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
+
+impl InputSource for CommandSource {
+    fn describe(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn read(&self) -> Result<Vec<u8>, AocError> {
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|err| AocError::CommandError {
+                command: self.describe(),
+                source: err,
+            })?;
+        if !output.status.success() {
+            return Err(AocError::CommandFailed {
+                command: self.describe(),
+                status: output.status.to_string(),
+            });
+        }
+        Ok(output.stdout)
+    }
+}
+
+// `-` reads stdin, URLs are downloaded, `cmd:` runs the whitespace separated command after it
+// and anything else is a path
+pub fn parse_source(spec: &str) -> Arc<dyn InputSource> {
+    if spec == "-" {
+        Arc::new(StdinSource::new())
+    } else if spec.starts_with("http://") || spec.starts_with("https://") {
+        Arc::new(UrlSource::new(spec))
+    } else if let Some(command) = spec.strip_prefix("cmd:") {
+        let mut words = command.split_whitespace();
+        let program = words.next().unwrap_or_default();
+        Arc::new(words.fold(CommandSource::new(program), CommandSource::arg))
+    } else {
+        Arc::new(FileSource::new(spec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_are_parsed_from_specs() {
+        assert_eq!(parse_source("-").describe(), "<stdin>");
+        assert_eq!(
+            parse_source("https://example.com/input").describe(),
+            "https://example.com/input"
+        );
+        let file = parse_source("tests/sum_task/example_in");
+        assert_eq!(file.path(), Some(Path::new("tests/sum_task/example_in")));
+        assert_eq!(file.read().unwrap(), b"2 2\n");
+        assert_eq!(StringSource::new("1\n2").read().unwrap(), b"1\n2");
+
+        #[cfg(unix)]
+        {
+            let command = parse_source("cmd:printf  3\\n4");
+            assert_eq!(command.describe(), "printf 3\\n4");
+            assert_eq!(command.path(), None);
+            assert_eq!(command.read().unwrap(), b"3\n4");
+        }
+    }
+}
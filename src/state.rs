@@ -0,0 +1,431 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Utc};
+use toml::{Table, Value};
+
+use crate::{
+    answers::{self, RecordedAnswer, Verdict},
+    error::AocError,
+    input, AocTask,
+};
+
+pub const STATE_ENV_VAR: &str = "AOC_STATE_FILE";
+const DEFAULT_STATE_FILE: &str = ".aoc-state.toml";
+
+static STATE: Mutex<Option<StateStore>> = Mutex::new(None);
+
+// Everything the framework remembers about a task, timestamps are in seconds since the epoch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskState {
+    pub started: Option<i64>,
+    pub solved: BTreeMap<usize, i64>,
+    pub answers: Vec<RecordedAnswer>,
+}
+
+impl TaskState {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// The state file has a `[[task]]` table per task, with a `[[task.solved]]` table per solved
+// phase since TOML tables cannot have the phases as keys, and a `[[task.answers]]` table per
+// recorded answer
+fn integer(table: &Table, key: &str) -> Result<Option<i64>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Integer(value)) => Ok(Some(*value)),
+        Some(_) => Err(format!("`{key}` must be an integer")),
+    }
+}
+
+fn required_integer(table: &Table, key: &str) -> Result<i64, String> {
+    integer(table, key)?.ok_or_else(|| format!("missing `{key}`"))
+}
+
+fn phase(table: &Table) -> Result<usize, String> {
+    usize::try_from(required_integer(table, "phase")?)
+        .map_err(|_| "`phase` must not be negative".to_owned())
+}
+
+fn string<'t>(table: &'t Table, key: &str) -> Result<&'t str, String> {
+    match table.get(key) {
+        Some(Value::String(value)) => Ok(value),
+        Some(_) => Err(format!("`{key}` must be a string")),
+        None => Err(format!("missing `{key}`")),
+    }
+}
+
+fn tables<'t>(table: &'t Table, key: &str) -> Result<Vec<&'t Table>, String> {
+    let list_error = || format!("`{key}` must be a list of tables");
+    match table.get(key) {
+        None => Ok(vec![]),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| value.as_table().ok_or_else(list_error))
+            .collect(),
+        Some(_) => Err(list_error()),
+    }
+}
+
+fn parse_answer(table: &Table) -> Result<RecordedAnswer, String> {
+    let verdict = string(table, "verdict")?;
+    Ok(RecordedAnswer {
+        phase: phase(table)?,
+        verdict: Verdict::parse(verdict).ok_or_else(|| format!("unknown verdict `{verdict}`"))?,
+        answer: string(table, "answer")?.to_owned(),
+    })
+}
+
+fn parse_task(table: &Table) -> Result<(String, TaskState), String> {
+    let state = TaskState {
+        started: integer(table, "started")?,
+        solved: tables(table, "solved")?
+            .into_iter()
+            .map(|solved| Ok((phase(solved)?, required_integer(solved, "timestamp")?)))
+            .collect::<Result<_, String>>()?,
+        answers: tables(table, "answers")?
+            .into_iter()
+            .map(parse_answer)
+            .collect::<Result<_, _>>()?,
+    };
+    Ok((string(table, "directory")?.to_owned(), state))
+}
+
+fn parse_state(content: &str) -> Result<BTreeMap<String, TaskState>, String> {
+    let table = content
+        .parse::<Table>()
+        .map_err(|err| err.message().to_owned())?;
+    tables(&table, "task")?
+        .into_iter()
+        .map(parse_task)
+        .collect()
+}
+
+fn task_record(directory: &str, state: &TaskState) -> Value {
+    let mut record = Table::new();
+    record.insert("directory".to_owned(), directory.into());
+    if let Some(started) = state.started {
+        record.insert("started".to_owned(), started.into());
+    }
+    let solved = state
+        .solved
+        .iter()
+        .map(|(&phase, &timestamp)| {
+            Table::from_iter([
+                ("phase".to_owned(), Value::from(phase as i64)),
+                ("timestamp".to_owned(), Value::from(timestamp)),
+            ])
+        })
+        .map(Value::Table)
+        .collect::<Vec<_>>();
+    if !solved.is_empty() {
+        record.insert("solved".to_owned(), Value::Array(solved));
+    }
+    let answers = state
+        .answers
+        .iter()
+        .map(|answer| {
+            Table::from_iter([
+                ("phase".to_owned(), Value::from(answer.phase as i64)),
+                ("verdict".to_owned(), Value::from(answer.verdict.as_str())),
+                ("answer".to_owned(), Value::from(answer.answer.as_str())),
+            ])
+        })
+        .map(Value::Table)
+        .collect::<Vec<_>>();
+    if !answers.is_empty() {
+        record.insert("answers".to_owned(), Value::Array(answers));
+    }
+    Value::Table(record)
+}
+
+// The state of all tasks is kept in a single file, which is read again when it changes on disk
+#[derive(Debug, Clone, Default)]
+pub struct StateStore {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    tasks: BTreeMap<String, TaskState>,
+}
+
+// The nearest directory with a manifest, so the state does not depend on where the tasks are
+// run from within the project
+fn project_root() -> PathBuf {
+    let current_dir = env::current_dir().unwrap_or_default();
+    current_dir
+        .ancestors()
+        .find(|directory| directory.join("Cargo.toml").is_file())
+        .map_or_else(|| current_dir.clone(), Path::to_path_buf)
+}
+
+// The state file is kept next to the inputs when they are kept outside of the source tree and
+// in the project root otherwise, unless the environment variable moves it
+pub fn state_path() -> PathBuf {
+    if let Some(path) = env::var_os(STATE_ENV_VAR) {
+        return PathBuf::from(path);
+    }
+    env::var_os(input::DATA_DIR_ENV_VAR)
+        .map_or_else(project_root, PathBuf::from)
+        .join(DEFAULT_STATE_FILE)
+}
+
+fn task_key(task: &(impl AocTask + ?Sized)) -> String {
+    task.directory().to_string_lossy().to_string()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl StateStore {
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let mut tasks = BTreeMap::new();
+        if path.is_file() {
+            let content = fs::read_to_string(path).map_err(|err| AocError::IOReadError {
+                path: path.to_string_lossy().to_string(),
+                source: err,
+            })?;
+            tasks = parse_state(&content).map_err(|message| AocError::InvalidState {
+                path: path.to_string_lossy().to_string(),
+                message,
+            })?;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            modified: modified(path),
+            tasks,
+        })
+    }
+
+    pub fn save(&mut self) -> Result<(), AocError> {
+        let records = self
+            .tasks
+            .iter()
+            .filter(|(_, state)| !state.is_empty())
+            .map(|(directory, state)| task_record(directory, state))
+            .collect::<Vec<_>>();
+        let content = Table::from_iter([("task".to_owned(), Value::Array(records))]).to_string();
+
+        let write_error = |err| AocError::IOWriteError {
+            path: self.path.to_string_lossy().to_string(),
+            source: err,
+        };
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+        fs::write(&self.path, content).map_err(write_error)?;
+        self.modified = modified(&self.path);
+        Ok(())
+    }
+
+    // Another process, or the user, changed the file since it was read
+    fn is_stale(&self, path: &Path) -> bool {
+        self.path != path || self.modified != modified(path)
+    }
+
+    pub fn task(&self, key: &str) -> Option<&TaskState> {
+        self.tasks.get(key).filter(|state| !state.is_empty())
+    }
+
+    pub fn task_mut(&mut self, key: &str) -> &mut TaskState {
+        self.tasks.entry(key.to_owned()).or_default()
+    }
+}
+
+fn read_timestamp(path: &Path) -> Option<i64> {
+    match fs::read_to_string(path).ok()?.trim().parse() {
+        Ok(timestamp) => Some(timestamp),
+        Err(_) => {
+            let modified = fs::metadata(path).ok()?.modified().ok()?;
+            Some(DateTime::<Utc>::from(modified).timestamp())
+        }
+    }
+}
+
+// The state used to be kept in hidden files next to the task, which are moved into the store
+fn read_marker_files(task: &(impl AocTask + ?Sized)) -> (TaskState, Vec<PathBuf>) {
+    let directory = task.directory();
+    let mut state = TaskState::default();
+    let mut marker_files = vec![];
+
+    for phase in 1..=task.phases() {
+        let solved_path = directory.join(format!(".solved_phase_{phase}"));
+        if let Some(timestamp) = read_timestamp(&solved_path) {
+            state.solved.insert(phase, timestamp);
+            marker_files.push(solved_path);
+        }
+    }
+    let answers_path = directory.join(".answers");
+    if let Ok(answers) = fs::read_to_string(&answers_path) {
+        state.answers = answers.lines().filter_map(answers::parse_line).collect();
+        marker_files.push(answers_path);
+    }
+    let started_path = directory.join(".race_start");
+    if let Some(timestamp) = read_timestamp(&started_path) {
+        state.started = Some(timestamp);
+        marker_files.push(started_path);
+    }
+    (state, marker_files)
+}
+
+fn migrate_marker_files(
+    store: &mut StateStore,
+    task: &(impl AocTask + ?Sized),
+) -> Result<(), AocError> {
+    let (state, marker_files) = read_marker_files(task);
+    if marker_files.is_empty() {
+        return Ok(());
+    }
+
+    *store.task_mut(&task_key(task)) = state;
+    store.save()?;
+    for marker_file in marker_files {
+        fs::remove_file(&marker_file).map_err(|err| AocError::IOWriteError {
+            path: marker_file.to_string_lossy().to_string(),
+            source: err,
+        })?;
+    }
+    Ok(())
+}
+
+// Gives access to the state of the task, wherever the task keeps it
+pub fn with_task_state<T>(
+    task: &(impl AocTask + ?Sized),
+    update: impl FnOnce(&mut TaskState) -> T,
+) -> Result<T, AocError> {
+    let mut update = Some(update);
+    let mut result = None;
+    task.update_state(&mut |state| {
+        if let Some(update) = update.take() {
+            result = Some(update(state));
+        }
+    })?;
+    Ok(result.expect("the task state was not updated"))
+}
+
+// Gives access to the state of the task in the state file, saving the store when it was changed
+pub fn with_stored_state<T>(
+    task: &(impl AocTask + ?Sized),
+    update: impl FnOnce(&mut TaskState) -> T,
+) -> Result<T, AocError> {
+    let mut store = STATE.lock().unwrap_or_else(|err| err.into_inner());
+    let path = state_path();
+    if store.as_ref().is_none_or(|store| store.is_stale(&path)) {
+        *store = Some(StateStore::load(&path)?);
+    }
+    let store = store.as_mut().expect("the state store was just loaded");
+
+    let key = task_key(task);
+    if store.task(&key).is_none() {
+        migrate_marker_files(store, task)?;
+    }
+    let state = store.task_mut(&key);
+    let original = state.clone();
+    let result = update(state);
+    if *state != original {
+        store.save()?;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+    use crate::{answers::Verdict, testing::TempDir, AocContext, AocSolution, AocStringIter};
+
+    struct StateTask {
+        directory: PathBuf,
+    }
+
+    impl AocTask for StateTask {
+        fn directory(&self) -> PathBuf {
+            self.directory.clone()
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(input.collect())
+        }
+    }
+
+    #[test]
+    fn marker_files_are_migrated() {
+        let directory = TempDir::new("state");
+        directory.write(".solved_phase_1", "1701406800");
+        directory.write(".answers", "2\ttoo_high\t100\n");
+        let task = StateTask {
+            directory: directory.path().to_owned(),
+        };
+
+        let mut store = StateStore::load(&directory.join("state.toml")).unwrap();
+        migrate_marker_files(&mut store, &task).unwrap();
+        assert!(!directory.join(".solved_phase_1").exists());
+        assert!(!directory.join(".answers").exists());
+
+        let store = StateStore::load(&directory.join("state.toml")).unwrap();
+        let state = store.task(&task_key(&task)).unwrap();
+        assert_eq!(state.solved.get(&1), Some(&1701406800));
+        assert_eq!(state.started, None);
+        assert_eq!(
+            state.answers,
+            vec![RecordedAnswer {
+                phase: 2,
+                verdict: Verdict::TooHigh,
+                answer: "100".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn state_is_saved_and_loaded() {
+        let directory = TempDir::new("saved_state");
+        let path = directory.join("state.toml");
+        let state = TaskState {
+            started: Some(1701406000),
+            solved: BTreeMap::from([(1, 1701406800), (2, 1701407400)]),
+            answers: vec![RecordedAnswer {
+                phase: 2,
+                verdict: Verdict::TooLow,
+                answer: "1\n2 \"3\"".to_owned(),
+            }],
+        };
+        let mut store = StateStore::load(&path).unwrap();
+        *store.task_mut("day_01") = state.clone();
+        store.task_mut("day_02");
+        store.save().unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("[[task.solved]]"));
+        assert!(!content.contains("day_02"));
+        let store = StateStore::load(&path).unwrap();
+        assert_eq!(store.task("day_01"), Some(&state));
+    }
+
+    #[test]
+    fn invalid_state_is_reported() {
+        let directory = TempDir::new("invalid_state");
+        let path = directory.write("state.toml", "[[task]]\nstarted = 1\n");
+        assert!(matches!(
+            StateStore::load(&path),
+            Err(AocError::InvalidState { .. })
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use crate::{
+    client::AocClient, error::AocError, style::Stylize, unlock::event_days, SharedAocTask,
+};
+
+const DAYS: usize = 25;
+const COLUMNS: usize = 5;
+
+pub type Calendar = [Option<usize>; DAYS];
+
+// Collects the solved phases of the registered tasks per year, days without a task are `None`
+pub fn local_calendars(
+    tasks: &[SharedAocTask],
+) -> Result<BTreeMap<Option<u16>, Calendar>, AocError> {
+    let mut calendars = BTreeMap::new();
+    for task in tasks {
+        let Some(day) = task.day() else {
+            continue;
+        };
+        let year = task.year();
+        let mut solved = 0;
+        for phase in 1..=task.phases() {
+            if task.phase_is_solved(phase)? {
+                solved += 1;
+            }
+        }
+        calendars.entry(year).or_insert([None; DAYS])[day as usize - 1] = Some(solved);
+    }
+    Ok(calendars)
+}
+
+// The calendar page labels every day with the number of collected stars
+fn parse_calendar_stars(page: &str) -> [usize; DAYS] {
+    let mut stars = [0; DAYS];
+    for (day, day_stars) in stars.iter_mut().enumerate() {
+        let label = format!("aria-label=\"Day {}, ", day + 1);
+        if let Some(start) = page.find(&label) {
+            let rest = &page[start + label.len()..];
+            if rest.starts_with("two stars") {
+                *day_stars = 2;
+            } else if rest.starts_with("one star") {
+                *day_stars = 1;
+            }
+        }
+    }
+    stars
+}
+
+pub fn fetch_stars(client: &AocClient, year: u16) -> Result<[usize; DAYS], AocError> {
+    Ok(parse_calendar_stars(&client.get(&format!("/{year}"))?))
+}
+
+// Marks the phases that were solved on the website as solved locally
+pub fn sync_solved_phases(client: &AocClient, tasks: &[SharedAocTask]) -> Result<(), AocError> {
+    let years = local_calendars(tasks)?
+        .into_keys()
+        .flatten()
+        .collect::<Vec<_>>();
+    for year in years {
+        let stars = fetch_stars(client, year)?;
+        for task in tasks {
+            let Some((task_year, day)) = task.date() else {
+                continue;
+            };
+            if task_year != year {
+                continue;
+            }
+            for phase in 1..=stars[day as usize - 1].min(task.phases()) {
+                if !task.phase_is_solved(phase)? {
+                    task.mark_phase_as_solved(phase)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn render_calendar(year: Option<u16>, calendar: &Calendar) -> String {
+    let mut out = String::new();
+    let title = year
+        .map(|year| format!("Advent of Code {year}"))
+        .unwrap_or_else(|| "Advent of Code".to_owned());
+    let _ = writeln!(out, "{}", title.bold());
+
+    let days = year.map(event_days).unwrap_or(DAYS as u8) as usize;
+    for (row, days) in calendar[..days].chunks(COLUMNS).enumerate() {
+        for (column, solved) in days.iter().enumerate() {
+            let day = row * COLUMNS + column + 1;
+            let stars = match solved {
+                Some(2..) => "**".yellow(),
+                Some(1) => "* ".grey(),
+                Some(_) => "  ".stylize(),
+                None => "..".muted(),
+            };
+            let _ = write!(out, "{} {}", format!("{day:>4}").muted(), stars);
+        }
+        out.push('\n');
+    }
+
+    let total: usize = calendar.iter().flatten().map(|solved| solved.min(&2)).sum();
+    let _ = writeln!(out, "{} {}/{}", "Stars:".bold(), total, days * 2);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_calendar_page() {
+        let page = r#"<a aria-label="Day 1, two stars" href="/2023/day/1" class="calendar-day1 calendar-verycomplete">
+            <a aria-label="Day 2, one star" href="/2023/day/2" class="calendar-day2 calendar-complete">
+            <a aria-label="Day 3" href="/2023/day/3" class="calendar-day3">"#;
+        let stars = parse_calendar_stars(page);
+        assert_eq!(stars[..4], [2, 1, 0, 0]);
+    }
+}
@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use crate::{
+    bench::{measure, BenchOptions},
+    context::AocContext,
+    error::AocError,
+    filter::Filter,
+    runner::{filter_tasks, task_phases},
+    style::Stylize,
+    SharedAocTask, DOT,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct StressOptions {
+    pub start_scale: usize,
+    // The scale is multiplied by the factor after every step
+    pub factor: usize,
+    pub steps: u32,
+    pub iterations: u32,
+    // No larger scales are tried once a single solve takes longer than this
+    pub time_limit: Duration,
+}
+
+impl Default for StressOptions {
+    fn default() -> Self {
+        Self {
+            start_scale: 1,
+            factor: 2,
+            steps: 8,
+            iterations: 3,
+            time_limit: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StressSample {
+    pub scale: usize,
+    pub input_bytes: usize,
+    pub median: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct StressResult {
+    pub task_name: String,
+    pub phase: usize,
+    pub samples: Vec<StressSample>,
+}
+
+impl StressResult {
+    pub fn exponent(&self) -> Option<f64> {
+        fit_exponent(&self.samples)
+    }
+
+    // The expected time to solve the phase at a larger scale, following the fitted curve
+    pub fn extrapolate(&self, scale: usize) -> Option<Duration> {
+        let exponent = self.exponent()?;
+        let last = self.samples.last()?;
+        let ratio = scale as f64 / last.scale as f64;
+        Duration::try_from_secs_f64(last.median.as_secs_f64() * ratio.powf(exponent)).ok()
+    }
+}
+
+// The slope of the least squares line through the samples on a log-log scale, i.e. `k` when
+// the time grows like `scale^k`
+pub fn fit_exponent(samples: &[StressSample]) -> Option<f64> {
+    let points = samples
+        .iter()
+        .filter(|sample| sample.scale > 0 && !sample.median.is_zero())
+        .map(|sample| ((sample.scale as f64).ln(), sample.median.as_secs_f64().ln()))
+        .collect::<Vec<_>>();
+    if points.len() < 2 {
+        return None;
+    }
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+    let covariance = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+fn describe_exponent(exponent: f64) -> &'static str {
+    match exponent {
+        e if e < 0.5 => "constant or logarithmic",
+        e if e < 1.3 => "linear",
+        e if e < 1.7 => "n log n or n^1.5",
+        e if e < 2.5 => "quadratic",
+        e if e < 3.5 => "cubic",
+        _ => "worse than cubic",
+    }
+}
+
+// `None` when the task does not generate inputs
+pub fn stress_task(
+    task: &SharedAocTask,
+    phase: usize,
+    options: &StressOptions,
+) -> Result<Option<StressResult>, AocError> {
+    let bench_options = BenchOptions {
+        warmup_iterations: 0,
+        iterations: options.iterations,
+    };
+    let mut samples = vec![];
+    let mut scale = options.start_scale.max(1);
+    for _ in 0..options.steps {
+        let input = task.generate_input(scale);
+        if input.is_empty() {
+            break;
+        }
+        let context = AocContext::new(phase);
+        let stats = measure(&bench_options, || {
+            task.solution_bytes(input.as_bytes(), &context)
+                .map(|_| ())
+                .map_err(|err| AocError::SolutionExecutionError {
+                    input_path: format!("generated input at scale {scale}"),
+                    source: err,
+                })
+        })?;
+        samples.push(StressSample {
+            scale,
+            input_bytes: input.len(),
+            median: stats.median,
+        });
+        if stats.min > options.time_limit {
+            break;
+        }
+        scale = scale.saturating_mul(options.factor.max(2));
+    }
+
+    Ok((!samples.is_empty()).then(|| StressResult {
+        task_name: task.name(),
+        phase,
+        samples,
+    }))
+}
+
+pub fn stress_tasks(
+    tasks: &[SharedAocTask],
+    filter: &Filter,
+    options: &StressOptions,
+) -> Result<Vec<StressResult>, AocError> {
+    let mut results = vec![];
+    for task in filter_tasks(tasks, filter) {
+        for phase in task_phases(task, filter) {
+            let Some(result) = stress_task(task, phase, options)? else {
+                continue;
+            };
+            println!(
+                "{} {} phase {}:",
+                DOT.info(),
+                result.task_name.clone().bold(),
+                phase.to_string().highlight(),
+            );
+            for sample in &result.samples {
+                println!(
+                    "  scale {:<8} {:>10} bytes  median {}",
+                    sample.scale.to_string().accent(),
+                    sample.input_bytes,
+                    format!("{:?}", sample.median).info(),
+                );
+            }
+            match result.exponent() {
+                Some(exponent) => {
+                    println!(
+                        "  grows like {} ({})",
+                        format!("n^{exponent:.2}").highlight(),
+                        describe_exponent(exponent).bold()
+                    );
+                    let last = result.samples[result.samples.len() - 1].scale;
+                    if let Some(estimate) = result.extrapolate(last.saturating_mul(10)) {
+                        println!(
+                            "  {}",
+                            format!("10x the largest scale would take about {estimate:?}").muted()
+                        );
+                    }
+                }
+                None => println!("  {}", "too few samples to fit a curve".muted()),
+            }
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn stress_grows_the_scale() {
+        let task = MockTask::new(|input, _context| Ok(vec![input.lines().count().to_string()]))
+            .with_generator(|scale| "1\n".repeat(scale));
+        let task: SharedAocTask = Arc::new(task);
+        let options = StressOptions {
+            start_scale: 10,
+            steps: 4,
+            iterations: 1,
+            ..StressOptions::default()
+        };
+        let result = stress_task(&task, 1, &options).unwrap().unwrap();
+        let scales = result
+            .samples
+            .iter()
+            .map(|sample| (sample.scale, sample.input_bytes))
+            .collect::<Vec<_>>();
+        assert_eq!(scales, [(10, 20), (20, 40), (40, 80), (80, 160)]);
+
+        let quadratic = [1, 2, 4, 8].map(|scale| StressSample {
+            scale,
+            input_bytes: 0,
+            median: Duration::from_micros(scale as u64 * scale as u64 * 100),
+        });
+        assert!((fit_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(fit_exponent(&quadratic[..1]), None);
+    }
+}
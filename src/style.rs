@@ -0,0 +1,243 @@
+use std::{
+    env,
+    fmt::{self, Display},
+    io::{self, IsTerminal},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        RwLock,
+    },
+};
+
+use crossterm::style::{Color, ContentStyle, StyledContent};
+
+const UNDETECTED: u8 = 0;
+const STYLED: u8 = 1;
+const PLAIN: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(UNDETECTED);
+static THEME: RwLock<Theme> = RwLock::new(Theme::DEFAULT);
+
+// The symbols and the colors of the console output, the colors are picked by meaning instead of
+// by name, e.g. `success` for passed phases
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub checkmark: &'static str,
+    pub cross: &'static str,
+    pub dot: &'static str,
+    pub success: Color,
+    pub failure: Color,
+    pub highlight: Color,
+    pub info: Color,
+    pub muted: Color,
+    pub accent: Color,
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme {
+        checkmark: "✔",
+        cross: "✘",
+        dot: "·",
+        success: Color::DarkGreen,
+        failure: Color::DarkRed,
+        highlight: Color::DarkYellow,
+        info: Color::Blue,
+        muted: Color::DarkGrey,
+        accent: Color::Cyan,
+    };
+
+    // For fonts without the unicode symbols, its symbols are also used in plain mode
+    pub const ASCII: Theme = Theme {
+        checkmark: "v",
+        cross: "x",
+        dot: "-",
+        ..Theme::DEFAULT
+    };
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DEFAULT
+    }
+}
+
+pub fn theme() -> Theme {
+    *THEME.read().unwrap_or_else(|err| err.into_inner())
+}
+
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(|err| err.into_inner()) = theme;
+}
+
+// Plain output is used when NO_COLOR is set or stdout is piped, unless it was set explicitly
+pub fn is_plain() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        UNDETECTED => {
+            let plain = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+                || !io::stdout().is_terminal();
+            set_plain(plain);
+            plain
+        }
+        mode => mode == PLAIN,
+    }
+}
+
+// Plain output drops all styling, unicode symbols and emoji
+pub fn set_plain(plain: bool) {
+    MODE.store(if plain { PLAIN } else { STYLED }, Ordering::Relaxed);
+    console::set_colors_enabled(!plain);
+    console::set_colors_enabled_stderr(!plain);
+}
+
+// A symbol with an ASCII replacement for plain output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    pub unicode: &'static str,
+    pub ascii: &'static str,
+}
+
+impl Glyph {
+    pub const fn new(unicode: &'static str, ascii: &'static str) -> Self {
+        Self { unicode, ascii }
+    }
+
+    pub fn text(&self) -> &'static str {
+        if is_plain() {
+            self.ascii
+        } else {
+            self.unicode
+        }
+    }
+}
+
+impl Display for Glyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text())
+    }
+}
+
+impl crossterm::style::Stylize for Glyph {
+    type Styled = StyledContent<Glyph>;
+
+    fn stylize(self) -> Self::Styled {
+        StyledContent::new(ContentStyle::new(), self)
+    }
+}
+
+// The symbols of the current theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Checkmark,
+    Cross,
+    Dot,
+}
+
+impl Symbol {
+    pub fn text(&self) -> &'static str {
+        let theme = if is_plain() { Theme::ASCII } else { theme() };
+        match self {
+            Symbol::Checkmark => theme.checkmark,
+            Symbol::Cross => theme.cross,
+            Symbol::Dot => theme.dot,
+        }
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text())
+    }
+}
+
+impl crossterm::style::Stylize for Symbol {
+    type Styled = StyledContent<Symbol>;
+
+    fn stylize(self) -> Self::Styled {
+        StyledContent::new(ContentStyle::new(), self)
+    }
+}
+
+fn unstyled<S: AsMut<ContentStyle>>(mut styled: S) -> S {
+    if is_plain() {
+        *styled.as_mut() = ContentStyle::new();
+    }
+    styled
+}
+
+macro_rules! styles {
+    ($($method:ident),* $(,)?) => {
+        $(
+            fn $method(self) -> Self::Styled {
+                unstyled(crossterm::style::Stylize::$method(self))
+            }
+        )*
+    };
+}
+
+macro_rules! theme_colors {
+    ($($color:ident),* $(,)?) => {
+        $(
+            fn $color(self) -> Self::Styled {
+                unstyled(crossterm::style::Stylize::with(self, theme().$color))
+            }
+        )*
+    };
+}
+
+// Drop-in replacement of crossterm's `Stylize` which leaves the content unstyled in plain mode
+pub trait Stylize: crossterm::style::Stylize {
+    fn stylize(self) -> Self::Styled {
+        crossterm::style::Stylize::stylize(self)
+    }
+
+    styles!(
+        bold,
+        dim,
+        italic,
+        underlined,
+        reverse,
+        black,
+        dark_grey,
+        red,
+        dark_red,
+        green,
+        dark_green,
+        yellow,
+        dark_yellow,
+        blue,
+        dark_blue,
+        magenta,
+        dark_magenta,
+        cyan,
+        dark_cyan,
+        white,
+        grey,
+    );
+
+    theme_colors!(success, failure, highlight, info, muted, accent);
+}
+
+impl<T: crossterm::style::Stylize> Stylize for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_output_is_unstyled() {
+        let _settings = crate::testing::lock_global_settings();
+        let was_plain = is_plain();
+        let glyph = Glyph::new("✔", "v");
+        set_plain(true);
+        assert_eq!(format!("{}", glyph.dark_green()), "v");
+        assert_eq!("text".bold().red().to_string(), "text");
+        assert_eq!(Symbol::Checkmark.to_string(), "v");
+        set_plain(false);
+        assert_eq!(glyph.to_string(), "✔");
+        assert_ne!("text".bold().to_string(), "text");
+        assert_eq!(
+            "text".failure().to_string(),
+            crossterm::style::Stylize::with("text", Theme::DEFAULT.failure).to_string()
+        );
+        set_plain(was_plain);
+    }
+}
@@ -1,19 +1,32 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     error::Error,
     fs::File,
-    io::{BufRead, BufReader, Lines},
-    path::PathBuf,
+    io::{BufRead, BufReader, Cursor, Lines},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use dialoguer::{theme::ColorfulTheme, Confirm};
+use chrono::Utc;
 use itertools::{Itertools, ProcessResults};
 
-use crate::error::AocError;
+use crate::{
+    compare::Comparator,
+    context::AocContext,
+    error::AocError,
+    example::{Example, ExampleMetadata},
+    hashed, input, prompt,
+    source::InputSource,
+    state::{self, TaskState},
+    style::Stylize,
+    CROSS,
+};
 
 pub type AocSolution = Vec<String>;
-pub type AocStringIter<'src> = ProcessResults<'src, Lines<BufReader<File>>, std::io::Error>;
-pub type AocResultStringIter = Lines<BufReader<File>>;
+pub type AocStringIter<'src> = ProcessResults<'src, AocResultStringIter, std::io::Error>;
+pub type AocResultStringIter = Lines<Box<dyn BufRead>>;
 
 #[derive(Debug)]
 pub struct AocTestResult {
@@ -22,9 +35,45 @@ pub struct AocTestResult {
     pub expected_output: AocSolution,
 }
 
-pub trait AocTask {
+fn catch_solution_panic(
+    input_path: &Path,
+    solve: impl FnOnce() -> Result<AocSolution, AocError>,
+) -> Result<AocSolution, AocError> {
+    panic::catch_unwind(AssertUnwindSafe(solve)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<unknown panic payload>".to_owned());
+        Err(AocError::SolutionPanicked {
+            input_path: input_path.to_string_lossy().to_string(),
+            message,
+        })
+    })
+}
+
+// Tasks are solved from worker threads when running in parallel or with a timeout
+pub trait AocTask: Send + Sync {
     fn directory(&self) -> PathBuf;
 
+    // Parsed out of the directory by default, e.g. `aoc_2023/day_05`
+    fn year(&self) -> Option<u16> {
+        input::puzzle_date(&self.directory()).map(|(year, _)| year)
+    }
+
+    fn day(&self) -> Option<u8> {
+        input::puzzle_day(&self.directory())
+    }
+
+    fn date(&self) -> Option<(u16, u8)> {
+        self.year().zip(self.day())
+    }
+
+    // Holds the input and the examples, which is the task directory unless configured otherwise
+    fn data_directory(&self) -> PathBuf {
+        input::dated_data_directory(&self.directory(), self.date())
+    }
+
     fn title_case(&self, string: String) -> String {
         string
             .split_whitespace()
@@ -49,7 +98,7 @@ pub trait AocTask {
     }
 
     fn example_paths(&self) -> Result<Vec<(PathBuf, PathBuf)>, AocError> {
-        let example_directory = self.directory();
+        let example_directory = self.data_directory();
         let task_files = example_directory
             .read_dir()
             .map_err(|err| AocError::MissingExample {
@@ -80,6 +129,11 @@ pub trait AocTask {
             let mut output_filename = input_filename.clone();
             output_filename.replace_range(output_filename.len() - 3.., "_out");
             let output_path = example_directory.join(output_filename);
+            let output_path = if output_path.is_file() {
+                output_path
+            } else {
+                hashed::hashed_path(&output_path)
+            };
 
             if output_path.is_file() && input_file.path().is_file() {
                 example_pairs.push((input_file.path().to_owned(), output_path));
@@ -89,40 +143,127 @@ pub trait AocTask {
         Ok(example_pairs)
     }
 
+    fn example_phase(&self, input_path: &Path) -> Option<usize> {
+        let filename = input_path.file_name()?.to_string_lossy().to_string();
+        let name = filename.strip_suffix("_in")?;
+        let (_, phase) = name.rsplit_once("_phase")?;
+        phase.parse().ok()
+    }
+
+    // The phases listed in the metadata sidecar of an example take precedence over its name
+    fn phase_example_paths(&self, phase: usize) -> Result<Vec<(PathBuf, PathBuf)>, AocError> {
+        let mut examples = vec![];
+        for (input_path, output_path) in self.example_paths()? {
+            let metadata = ExampleMetadata::load(&input_path)?;
+            let in_phase = if metadata.phases.is_empty() {
+                self.example_phase(&input_path)
+                    .is_none_or(|example_phase| example_phase == phase)
+            } else {
+                metadata.phases.contains(&phase)
+            };
+            if in_phase {
+                examples.push((input_path, output_path));
+            }
+        }
+        Ok(examples)
+    }
+
+    // Examples declared in code, which are run after the example files
+    fn inline_examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    fn phases(&self) -> usize {
+        2
+    }
+
     fn input_path(&self) -> PathBuf {
-        self.directory().join("in")
+        let input_path = self.data_directory().join("in");
+        if !input_path.exists() {
+            if let Some((year, day)) = self.date() {
+                if let Err(err) = input::fetch_input(year, day, &input_path) {
+                    eprintln!(
+                        "{} Failed to download the input of {}: {}",
+                        CROSS.failure(),
+                        self.name().bold(),
+                        err
+                    );
+                }
+            }
+        }
+        input_path
+    }
+
+    // Replaces the input file, e.g. with stdin or the output of a command. A source passed to
+    // the runner takes precedence.
+    fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        None
     }
 
-    fn solved_phase_path(&self, phase: usize) -> PathBuf {
-        self.directory().join(format!(".solved_phase_{phase}"))
+    // Everything the framework remembers about the task, kept in the state file unless the task
+    // keeps it elsewhere, e.g. in memory in tests
+    fn update_state(&self, update: &mut dyn FnMut(&mut TaskState)) -> Result<(), AocError> {
+        state::with_stored_state(self, update)
     }
 
-    fn phase_is_solved(&self, phase: usize) -> bool {
-        self.solved_phase_path(phase).is_file()
+    fn phase_is_solved(&self, phase: usize) -> Result<bool, AocError> {
+        state::with_task_state(self, |state| state.solved.contains_key(&phase))
     }
 
+    // The time of the solve is kept for the race timer, so marking a phase again keeps it
     fn mark_phase_as_solved(&self, phase: usize) -> Result<(), AocError> {
-        let solved_path = self.solved_phase_path(phase);
-        File::create(&solved_path).map_err(|io_err| AocError::MarkSolvedError {
-            task_name: self.name(),
-            solved_path: solved_path.to_string_lossy().to_string(),
-            source: io_err,
-        })?;
-        Ok(())
+        state::with_task_state(self, |state| {
+            state.solved.entry(phase).or_insert(Utc::now().timestamp());
+        })
+    }
+
+    fn mark_phase_as_unsolved(&self, phase: usize) -> Result<(), AocError> {
+        state::with_task_state(self, |state| {
+            state.solved.remove(&phase);
+        })
     }
 
     fn solution(
         &self,
         input: AocStringIter,
-        phase: usize,
+        context: &AocContext,
     ) -> Result<AocSolution, Box<dyn Error + Send + Sync>>;
 
+    fn solution_raw(
+        &self,
+        input: &str,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        let reader: Box<dyn BufRead> = Box::new(Cursor::new(input.to_owned()));
+        reader
+            .lines()
+            .process_results(|lines| self.solution(lines, context))?
+    }
+
+    fn solution_bytes(
+        &self,
+        input: &[u8],
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        let input = self.preprocess(Cow::Borrowed(std::str::from_utf8(input)?));
+        self.solution_raw(&input, context)
+    }
+
+    // Transforms the examples and the input before they reach `solution` or `solution_raw`,
+    // e.g. to strip comments or normalize whitespace. Tasks implementing `solution_bytes` get
+    // the input unchanged. Borrowed inputs are views of the memory mapped input files, which
+    // `solution_raw` gets without copying them when they are returned unchanged.
+    fn preprocess<'i>(&self, raw: Cow<'i, str>) -> Cow<'i, str> {
+        raw
+    }
+
     fn get_file_iterator(&self, path: &PathBuf) -> Result<AocResultStringIter, AocError> {
         let file = File::open(path).map_err(|io_err| AocError::IOReadError {
             path: path.to_string_lossy().to_string(),
             source: io_err,
         })?;
-        Ok(BufReader::new(file).lines())
+        let reader: Box<dyn BufRead> = Box::new(BufReader::new(file));
+        Ok(reader.lines())
     }
 
     fn get_file_output(&self, path: &PathBuf) -> Result<AocSolution, AocError> {
@@ -136,39 +277,70 @@ pub trait AocTask {
 
     fn solve_from_input_path(
         &self,
-        input_path: &PathBuf,
-        phase: usize,
+        input_path: &Path,
+        context: &AocContext,
     ) -> Result<AocSolution, AocError> {
-        let input = self.get_file_iterator(input_path)?;
-        let output = input
-            .process_results(|lines| {
-                self.solution(lines, phase)
-                    .map_err(|err| AocError::SolutionExecutionError {
-                        input_path: input_path.to_string_lossy().to_string(),
-                        source: err,
-                    })
+        let input = input::map_input(input_path)?;
+        self.solution_bytes(&input, context)
+            .map_err(|err| AocError::SolutionExecutionError {
+                input_path: input_path.to_string_lossy().to_string(),
+                source: err,
             })
-            .map_err(|line_read_error| AocError::IOReadError {
-                path: input_path.to_string_lossy().to_string(),
-                source: line_read_error,
-            })??;
-        Ok(output)
     }
 
     fn solve(&self, phase: usize) -> Result<AocSolution, AocError> {
-        let input_path = self.input_path();
-        let output = self.solve_from_input_path(&input_path, phase)?;
-        Ok(output)
+        self.solve_with_context(AocContext::new(phase))
     }
 
-    fn solutions_match(&self, s1: &AocSolution, s2: &AocSolution) -> bool {
-        let matches = s1
-            .iter()
-            .zip(s2.iter())
-            .filter(|&(a, b)| a.trim() == b.trim())
-            .count();
+    fn solve_with_context(&self, context: AocContext) -> Result<AocSolution, AocError> {
+        trace_span!(
+            "solve",
+            phase = context.phase(),
+            example = context.is_example(),
+            variant = context.variant()
+        );
+        let source = context.input_source().or_else(|| self.input_source());
+        let input_path = match source.as_ref() {
+            Some(source) => source
+                .path()
+                .map_or_else(|| PathBuf::from(source.describe()), Path::to_path_buf),
+            None => self.input_path(),
+        };
+        // Only file inputs are memory mapped and known to the solution by their path
+        let reader = source.filter(|source| source.path().is_none());
+        let context = match reader {
+            Some(_) => context,
+            None => context.with_input_path(&input_path),
+        };
+        let output = catch_solution_panic(&input_path, || match &reader {
+            Some(source) => {
+                let input = source.read()?;
+                self.solution_bytes(&input, &context).map_err(|err| {
+                    AocError::SolutionExecutionError {
+                        input_path: input_path.to_string_lossy().to_string(),
+                        source: err,
+                    }
+                })
+            }
+            None => self.solve_from_input_path(&input_path, &context),
+        })?;
+        Ok(context.finish_output(output))
+    }
+
+    fn comparator(&self, _phase: usize) -> Comparator {
+        Comparator::default()
+    }
+
+    // Names of alternate implementations of the phase, e.g. a naive and an optimized one. The
+    // solution picks the implementation based on `AocContext::variant`.
+    fn variants(&self, _phase: usize) -> Vec<&'static str> {
+        vec![]
+    }
 
-        matches == s1.len() && matches == s2.len()
+    // A synthetic input that grows with the scale, used by the stress mode to estimate how the
+    // solution scales. Tasks without a generator return an empty input.
+    fn generate_input(&self, _scale: usize) -> String {
+        String::new()
     }
 
     fn run_example_test(
@@ -176,20 +348,60 @@ pub trait AocTask {
         io_pair: &(PathBuf, PathBuf),
         phase: usize,
     ) -> Result<AocTestResult, AocError> {
-        let example_output = self.get_file_output(&io_pair.1)?;
-        let output = self.solve_from_input_path(&io_pair.0, phase)?;
+        let hashed_output = hashed::is_hashed(&io_pair.1)
+            .then(|| hashed::load(&io_pair.1))
+            .transpose()?;
+        let example_output = match hashed_output {
+            Some(_) => vec![hashed::HASHED_OUTPUT.to_owned()],
+            None => self.get_file_output(&io_pair.1)?,
+        };
+        let context = AocContext::example(phase, ExampleMetadata::load(&io_pair.0)?.args)
+            .with_input_path(&io_pair.0);
+        let output = catch_solution_panic(&io_pair.0, || {
+            self.solve_from_input_path(&io_pair.0, &context)
+        })?;
+        let output = context.finish_output(output);
+        // Hashed outputs can only be compared exactly
+        let passed = match hashed_output {
+            Some(hashed_output) => hashed_output.matches(&hashed::output_text(&output)),
+            None => self.comparator(phase).matches(&output, &example_output),
+        };
         Ok(AocTestResult {
-            passed: self.solutions_match(&example_output, &output),
+            passed,
             output,
             expected_output: example_output,
         })
     }
 
+    fn run_inline_example(
+        &self,
+        example: &Example,
+        phase: usize,
+    ) -> Result<AocTestResult, AocError> {
+        let example_path = PathBuf::from(format!("<inline example {}>", example.name));
+        let expected_output = example
+            .expected_output
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let context = AocContext::example(phase, example.args.clone());
+        let output = catch_solution_panic(&example_path, || {
+            self.solution_bytes(example.input.as_bytes(), &context)
+                .map_err(|err| AocError::SolutionExecutionError {
+                    input_path: example_path.to_string_lossy().to_string(),
+                    source: err,
+                })
+        })?;
+        let output = context.finish_output(output);
+        Ok(AocTestResult {
+            passed: self.comparator(phase).matches(&output, &expected_output),
+            output,
+            expected_output,
+        })
+    }
+
     fn ask_if_solved(&self, phase: usize) -> Result<bool, AocError> {
-        let solved = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt(format!("Is phase {phase} of the task solved?"))
-            .interact()
-            .map_err(|dialog_err| AocError::UserInterractionError { source: dialog_err })?;
+        let solved = prompt::confirm(format!("Is phase {phase} of the task solved?"))?;
 
         if solved {
             self.mark_phase_as_solved(phase)?;
@@ -198,6 +410,22 @@ pub trait AocTask {
             Ok(false)
         }
     }
+
+    // Shows the answer before asking whether it was accepted, multiline answers below the prompt
+    fn confirm_answer(&self, phase: usize, answer: &str) -> Result<bool, AocError> {
+        let question = if answer.contains('\n') {
+            println!("{answer}");
+            format!("Was the answer above accepted for phase {phase}?")
+        } else {
+            format!("Was {answer} accepted as the answer of phase {phase}?")
+        };
+        let accepted = prompt::confirm(question)?;
+
+        if accepted {
+            self.mark_phase_as_solved(phase)?;
+        }
+        Ok(accepted)
+    }
 }
 
 #[cfg(test)]
@@ -214,7 +442,7 @@ mod tests {
         fn solution(
             &self,
             input: AocStringIter,
-            _phase: usize,
+            _context: &AocContext,
         ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
             let mut answers = vec![];
             for line in input {
@@ -229,12 +457,142 @@ mod tests {
         }
     }
 
+    struct RawSumTask;
+
+    impl AocTask for RawSumTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn solution(
+            &self,
+            mut input: AocStringIter,
+            context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            self.solution_raw(&input.join("\n"), context)
+        }
+
+        fn solution_raw(
+            &self,
+            input: &str,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            let sum = input
+                .split_whitespace()
+                .map(|num| num.parse::<i32>())
+                .sum::<Result<i32, _>>()?;
+            Ok(vec![sum.to_string()])
+        }
+    }
+
+    struct BytesSumTask;
+
+    impl AocTask for BytesSumTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn solution(
+            &self,
+            mut input: AocStringIter,
+            context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            self.solution_bytes(input.join("\n").as_bytes(), context)
+        }
+
+        fn solution_bytes(
+            &self,
+            input: &[u8],
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            let mut sum = 0;
+            let mut number = 0;
+            let mut sign = 1;
+            for &byte in input.iter().chain(b"\n") {
+                match byte {
+                    b'-' => sign = -1,
+                    b'0'..=b'9' => number = number * 10 + (byte - b'0') as i32,
+                    _ => {
+                        sum += sign * number;
+                        number = 0;
+                        sign = 1;
+                    }
+                }
+            }
+            Ok(vec![sum.to_string()])
+        }
+    }
+
+    struct PanickingTask;
+
+    impl AocTask for PanickingTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn solution(
+            &self,
+            _input: AocStringIter,
+            context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            panic!("phase {} is not done yet", context.phase())
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(crate::AocTask)]
+    #[aoc(
+        dir = "tests/sum_task",
+        name = "Derived Sum",
+        day = 3,
+        phases = 1,
+        input = "str"
+    )]
+    struct DerivedSumTask;
+
+    #[cfg(feature = "derive")]
+    impl DerivedSumTask {
+        fn solve_input(
+            &self,
+            input: &str,
+            context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            RawSumTask.solution_raw(input, context)
+        }
+    }
+
     #[test]
     fn sum_task_name() {
         let task = SumTask;
         assert_eq!(task.name(), String::from("Sum Task"));
     }
 
+    #[test]
+    fn task_date() {
+        struct DatedTask;
+
+        impl AocTask for DatedTask {
+            fn directory(&self) -> PathBuf {
+                PathBuf::from("solutions/2021/day_09")
+            }
+
+            fn day(&self) -> Option<u8> {
+                Some(10)
+            }
+
+            fn solution(
+                &self,
+                input: AocStringIter,
+                _context: &AocContext,
+            ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+                Ok(input.collect())
+            }
+        }
+
+        assert_eq!(SumTask.date(), None);
+        assert_eq!(DatedTask.date(), Some((2021, 10)));
+    }
+
     #[test]
     fn sum_task_example_solutions() {
         let task = SumTask;
@@ -245,25 +603,185 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sum_task_inline_example() {
+        let example = Example::new("1 2\n3 4", "3\n7");
+        assert!(SumTask.run_inline_example(&example, 1).unwrap().passed);
+        let example = Example::new("1 2", "4");
+        assert!(!SumTask.run_inline_example(&example, 1).unwrap().passed);
+    }
+
+    #[test]
+    fn preprocessing_applies_to_examples_and_input() {
+        struct CommentedSumTask;
+
+        impl AocTask for CommentedSumTask {
+            fn directory(&self) -> PathBuf {
+                PathBuf::from("tests/sum_task")
+            }
+
+            fn preprocess<'i>(&self, raw: Cow<'i, str>) -> Cow<'i, str> {
+                raw.lines()
+                    .map(|line| line.split('#').next().unwrap_or_default().trim())
+                    .filter(|line| !line.is_empty())
+                    .join("\n")
+                    .into()
+            }
+
+            fn solution(
+                &self,
+                input: AocStringIter,
+                context: &AocContext,
+            ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+                SumTask.solution(input, context)
+            }
+        }
+
+        // Inputs that are not preprocessed stay views of the input files
+        assert!(matches!(
+            SumTask.preprocess(Cow::Borrowed("1 2")),
+            Cow::Borrowed(_)
+        ));
+        let example = Example::new("# pairs\n1 2 # first\n\n3 4", "3\n7");
+        assert!(
+            CommentedSumTask
+                .run_inline_example(&example, 1)
+                .unwrap()
+                .passed
+        );
+        assert_eq!(
+            CommentedSumTask.solve(1).unwrap(),
+            SumTask.solve(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn context_reaches_the_solution() {
+        struct StepsTask;
+
+        impl AocTask for StepsTask {
+            fn directory(&self) -> PathBuf {
+                PathBuf::from("tests/sum_task")
+            }
+
+            fn solution(
+                &self,
+                _input: AocStringIter,
+                context: &AocContext,
+            ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+                assert_eq!(context.is_example(), context.input_path().is_none());
+                Ok(vec![context.args().get_or("steps", 64).to_string()])
+            }
+        }
+
+        let example = Example::new("", "6").arg("steps", 6);
+        assert!(StepsTask.run_inline_example(&example, 1).unwrap().passed);
+        assert_eq!(StepsTask.solve(1).unwrap(), ["64"]);
+    }
+
+    #[test]
+    fn sum_task_phase_examples() {
+        let task = SumTask;
+        let phase_example = PathBuf::from("tests/sum_task/example_03_phase2_in");
+        assert_eq!(task.example_phase(&phase_example), Some(2));
+        assert_eq!(
+            task.example_phase(Path::new("tests/sum_task/example_02_in")),
+            None
+        );
+
+        let phase_1_examples = task.phase_example_paths(1).unwrap();
+        let phase_2_examples = task.phase_example_paths(2).unwrap();
+        assert!(!phase_1_examples
+            .iter()
+            .any(|(input, _)| *input == phase_example));
+        assert_eq!(phase_2_examples.len(), phase_1_examples.len() + 1);
+        assert!(phase_2_examples
+            .iter()
+            .any(|(input, _)| *input == phase_example));
+    }
+
     #[test]
     fn sum_task_solution() {
         let task = SumTask;
         let solution = task.solve(1).unwrap();
         let expected_output = vec![7.to_string(), 12.to_string(), 289197.to_string()];
-        assert!(task.solutions_match(&solution, &expected_output))
+        assert!(task.comparator(1).matches(&solution, &expected_output))
     }
 
     #[test]
-    fn sum_task_solved() {
-        let task = SumTask;
-        let phase = 1usize;
-        let solved_path = task.solved_phase_path(phase);
-        if solved_path.exists() {
-            std::fs::remove_file(solved_path).unwrap();
+    fn solved_phases_are_kept_in_the_task_state() {
+        // Keeps its state in memory, the state file of the project is left alone
+        #[derive(Default)]
+        struct StatefulTask {
+            state: std::sync::Mutex<TaskState>,
+        }
+
+        impl AocTask for StatefulTask {
+            fn directory(&self) -> PathBuf {
+                PathBuf::from("tests/stateful_task")
+            }
+
+            fn update_state(&self, update: &mut dyn FnMut(&mut TaskState)) -> Result<(), AocError> {
+                update(&mut self.state.lock().unwrap());
+                Ok(())
+            }
+
+            fn solution(
+                &self,
+                input: AocStringIter,
+                _context: &AocContext,
+            ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+                Ok(input.collect())
+            }
         }
-        assert!(!task.phase_is_solved(phase));
+
+        let task = StatefulTask::default();
+        let phase = 1usize;
+        task.mark_phase_as_unsolved(phase).unwrap();
+        assert!(!task.phase_is_solved(phase).unwrap());
 
         task.mark_phase_as_solved(phase).unwrap();
-        assert!(task.phase_is_solved(phase));
+        assert!(task.phase_is_solved(phase).unwrap());
+    }
+
+    #[test]
+    fn raw_sum_task_solution() {
+        let task = RawSumTask;
+        let solution = task.solve(1).unwrap();
+        assert_eq!(solution, vec![289216.to_string()]);
+    }
+
+    #[test]
+    fn bytes_sum_task_solution() {
+        let task = BytesSumTask;
+        let solution = task.solve(1).unwrap();
+        assert_eq!(solution, vec![289216.to_string()]);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derived_task_solution() {
+        let task = DerivedSumTask;
+        assert_eq!(task.name(), "Derived Sum");
+        assert_eq!(task.phases(), 1);
+        assert_eq!(DerivedSumTask::DAY, 3);
+        assert_eq!(task.day(), Some(3));
+        assert_eq!(task.year(), None);
+        assert_eq!(task.solve(1).unwrap(), vec![289216.to_string()]);
+    }
+
+    #[test]
+    fn panicking_task_fails() {
+        let task = PanickingTask;
+        match task.solve(2) {
+            Err(AocError::SolutionPanicked {
+                input_path,
+                message,
+            }) => {
+                assert_eq!(input_path, "tests/sum_task/in");
+                assert_eq!(message, "phase 2 is not done yet");
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
     }
 }
@@ -0,0 +1,487 @@
+use std::{
+    error::Error,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use itertools::Itertools;
+
+use crate::{
+    answers::{self, AnswerLog},
+    diff,
+    error::AocError,
+    example, hashed,
+    runner::{run_examples, RunOptions},
+    source::{InputSource, StringSource},
+    state::TaskState,
+    AocContext, AocSolution, AocStringIter, AocTask, Example, SharedAocTask,
+};
+
+type MockSolution =
+    dyn Fn(&str, &AocContext) -> Result<AocSolution, Box<dyn Error + Send + Sync>> + Send + Sync;
+type MockGenerator = dyn Fn(usize) -> String + Send + Sync;
+
+// A task that never touches the filesystem, its input and examples are strings and its state
+// is only remembered by the task itself
+pub struct MockTask {
+    name: String,
+    directory: Option<PathBuf>,
+    date: Option<(u16, u8)>,
+    input: String,
+    examples: Vec<Example>,
+    phases: usize,
+    variants: Vec<&'static str>,
+    state: Mutex<TaskState>,
+    solution: Box<MockSolution>,
+    generator: Option<Box<MockGenerator>>,
+}
+
+impl MockTask {
+    pub fn new(
+        solution: impl Fn(&str, &AocContext) -> Result<AocSolution, Box<dyn Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self {
+            name: "Mock Task".to_owned(),
+            directory: None,
+            date: None,
+            input: String::new(),
+            examples: vec![],
+            phases: 2,
+            variants: vec![],
+            state: Mutex::new(TaskState::default()),
+            solution: Box::new(solution),
+            generator: None,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    // The input is still only solved from the string, real directories hold the other files
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub fn with_date(mut self, year: u16, day: u8) -> Self {
+        self.date = Some((year, day));
+        self
+    }
+
+    // Dedented like the inputs of inline examples
+    pub fn with_input(mut self, input: &str) -> Self {
+        self.input = example::dedent(input);
+        self
+    }
+
+    pub fn with_example(mut self, example: Example) -> Self {
+        self.examples.push(example);
+        self
+    }
+
+    pub fn with_phases(mut self, phases: usize) -> Self {
+        self.phases = phases;
+        self
+    }
+
+    // The same variants in every phase
+    pub fn with_variants(mut self, variants: &[&'static str]) -> Self {
+        self.variants = variants.to_vec();
+        self
+    }
+
+    pub fn with_generator(
+        mut self,
+        generator: impl Fn(usize) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.generator = Some(Box::new(generator));
+        self
+    }
+
+    pub fn with_solved(self, phase: usize) -> Self {
+        self.mark_phase_as_solved(phase)
+            .expect("the state of mock tasks is kept in memory");
+        self
+    }
+}
+
+impl AocTask for MockTask {
+    fn directory(&self) -> PathBuf {
+        self.directory
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("mock").join(&self.name))
+    }
+
+    fn year(&self) -> Option<u16> {
+        self.date.map(|(year, _)| year)
+    }
+
+    fn day(&self) -> Option<u8> {
+        self.date.map(|(_, day)| day)
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn example_paths(&self) -> Result<Vec<(PathBuf, PathBuf)>, AocError> {
+        Ok(vec![])
+    }
+
+    // Never downloaded, the input is only solved from the string
+    fn input_path(&self) -> PathBuf {
+        self.data_directory().join("in")
+    }
+
+    fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        Some(Arc::new(StringSource::new(self.input.clone())))
+    }
+
+    fn inline_examples(&self) -> Vec<Example> {
+        self.examples.clone()
+    }
+
+    fn phases(&self) -> usize {
+        self.phases
+    }
+
+    fn variants(&self, _phase: usize) -> Vec<&'static str> {
+        self.variants.clone()
+    }
+
+    fn generate_input(&self, scale: usize) -> String {
+        self.generator
+            .as_ref()
+            .map_or_else(String::new, |generator| generator(scale))
+    }
+
+    fn update_state(&self, update: &mut dyn FnMut(&mut TaskState)) -> Result<(), AocError> {
+        update(&mut self.state.lock().unwrap_or_else(|err| err.into_inner()));
+        Ok(())
+    }
+
+    fn solution(
+        &self,
+        mut input: AocStringIter,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        self.solution_raw(&input.join("\n"), context)
+    }
+
+    fn solution_raw(
+        &self,
+        input: &str,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        (self.solution)(input, context)
+    }
+
+    // There is nobody to ask in tests
+    fn ask_if_solved(&self, _phase: usize) -> Result<bool, AocError> {
+        Ok(false)
+    }
+
+    fn confirm_answer(&self, _phase: usize, _answer: &str) -> Result<bool, AocError> {
+        Ok(false)
+    }
+}
+
+// A fresh directory in the system temp directory, removed with its contents when dropped
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "aoc_framework_{name}_{}_{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)
+            .unwrap_or_else(|err| panic!("failed to create {}: {err}", path.to_string_lossy()));
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.path.join(path)
+    }
+
+    // Creates the parent directories of the file as well
+    pub fn write(&self, path: impl AsRef<Path>, contents: &str) -> PathBuf {
+        let path = self.join(path);
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        fs::write(&path, contents)
+            .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.to_string_lossy()));
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+// The report of a phase with nothing but its status, for testing reporters
+fn test_options() -> RunOptions {
+    // Capturing redirects the stdout of the whole process, which the other tests share
+    RunOptions {
+        interactive: false,
+        progress: false,
+        capture_output: false,
+        ..RunOptions::default()
+    }
+}
+
+// Panics with the diffs of the failing examples of every phase. Unscoped examples are only
+// checked in the first phase, like in the runner.
+pub fn assert_examples(task: &SharedAocTask) {
+    let mut failures = String::new();
+    for phase in 1..=task.phases() {
+        let examples = run_examples(task, phase, &test_options())
+            .unwrap_or_else(|err| panic!("{} phase {phase}: {err}", task.name()));
+        for example in examples {
+            match example {
+                Ok(example) if example.passed || !example.checked => {}
+                Ok(example) => {
+                    let _ = writeln!(
+                        failures,
+                        "{} phase {phase}, example {} failed:\n{}",
+                        task.name(),
+                        example.name,
+                        diff::render_plain(&example.expected_output, &example.output)
+                    );
+                }
+                Err(err) => {
+                    let _ = writeln!(failures, "{} phase {phase}: {err}", task.name());
+                }
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{failures}");
+}
+
+// Panics when a solved phase no longer produces its accepted answer, or the answer of its hash.
+// Phases without either and tasks without an input, e.g. on CI, are skipped.
+pub fn assert_answers(task: &SharedAocTask) {
+    if !task.input_path().is_file() {
+        return;
+    }
+    let answers =
+        AnswerLog::load(task.as_ref()).unwrap_or_else(|err| panic!("{}: {err}", task.name()));
+    for phase in 1..=task.phases() {
+        let hash = hashed::answer_hash(task.as_ref(), phase)
+            .unwrap_or_else(|err| panic!("{}: {err}", task.name()));
+        if answers.accepted(phase).is_none() && hash.is_none() {
+            continue;
+        }
+        let output = task
+            .solve(phase)
+            .unwrap_or_else(|err| panic!("{} phase {phase}: {err}", task.name()));
+        match answers.accepted(phase) {
+            Some(accepted) => assert_eq!(
+                answers::answer_text(&output),
+                accepted,
+                "{} phase {phase} no longer produces its accepted answer",
+                task.name()
+            ),
+            None => assert!(
+                hash.is_some_and(|hash| hashed::matches_answer(&hash, &output)),
+                "{} phase {phase} no longer produces the answer of its hash",
+                task.name()
+            ),
+        }
+    }
+}
+
+// Serializes the tests that change the process-wide settings, i.e. the plain output mode and the
+// default answer of the prompts
+#[cfg(test)]
+pub(crate) fn lock_global_settings() -> std::sync::MutexGuard<'static, ()> {
+    static SETTINGS: Mutex<()> = Mutex::new(());
+    SETTINGS.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+// Generates a module of tests per task running its examples, `aoc_tests!(Day01, Day02);` adds
+// `aoc_tests::Day01::examples` and `aoc_tests::Day02::examples`. Tasks that are not unit structs
+// are given as `Day03 = TypedTask::new(Day03)`, and a leading `answers;` adds an `answers` test
+// checking the solved phases against their accepted answers.
+#[macro_export]
+macro_rules! aoc_tests {
+    (@task $name:ident) => {
+        $name
+    };
+    (@task $name:ident = $task:expr) => {
+        $task
+    };
+    (@answers true) => {
+        #[test]
+        fn answers() {
+            $crate::testing::assert_answers(&task());
+        }
+    };
+    (@answers false) => {};
+    (@tests $answers:tt; $($name:ident $(= $task:expr)?),+) => {
+        #[cfg(test)]
+        mod aoc_tests {
+            $(
+                #[allow(non_snake_case)]
+                mod $name {
+                    #[allow(unused_imports)]
+                    use super::super::*;
+
+                    fn task() -> $crate::SharedAocTask {
+                        ::std::sync::Arc::new($crate::aoc_tests!(@task $name $(= $task)?))
+                    }
+
+                    #[test]
+                    fn examples() {
+                        $crate::testing::assert_examples(&task());
+                    }
+
+                    $crate::aoc_tests!(@answers $answers);
+                }
+            )+
+        }
+    };
+    (answers; $($name:ident $(= $task:expr)?),+ $(,)?) => {
+        $crate::aoc_tests!(@tests true; $($name $(= $task)?),+);
+    };
+    ($($name:ident $(= $task:expr)?),+ $(,)?) => {
+        $crate::aoc_tests!(@tests false; $($name $(= $task)?),+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AocStringIter, TypedAocTask, TypedTask};
+
+    struct SumTask;
+
+    impl AocTask for SumTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(input
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|num| num.parse::<i32>().unwrap_or(0))
+                        .sum::<i32>()
+                        .to_string()
+                })
+                .collect())
+        }
+    }
+
+    struct TypedSumTask;
+
+    impl TypedAocTask for TypedSumTask {
+        type Parsed = Vec<Vec<i32>>;
+
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn parse(&self, input: &str) -> Result<Self::Parsed, Box<dyn Error + Send + Sync>> {
+            Ok(input
+                .lines()
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|num| num.parse().unwrap_or(0))
+                        .collect()
+                })
+                .collect())
+        }
+
+        fn solve_parsed(
+            &self,
+            parsed: &Self::Parsed,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(parsed
+                .iter()
+                .map(|line| line.iter().sum::<i32>().to_string())
+                .collect())
+        }
+    }
+
+    struct BrokenTask;
+
+    impl AocTask for BrokenTask {
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn solution(
+            &self,
+            input: AocStringIter,
+            _context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            Ok(vec![input.count().to_string()])
+        }
+    }
+
+    aoc_tests!(answers; SumTask, TypedSumTask = TypedTask::new(TypedSumTask));
+
+    #[test]
+    fn mock_tasks_stay_in_memory() {
+        let task = MockTask::new(|input, context| {
+            let sum = input
+                .lines()
+                .map(|line| line.parse::<i64>())
+                .sum::<Result<i64, _>>()?;
+            Ok(vec![(sum * context.phase() as i64).to_string()])
+        })
+        .with_name("Mock Sum")
+        .with_date(2023, 1)
+        .with_input(
+            "
+            1
+            2
+            3",
+        )
+        .with_example(Example::new("4\n5", "9"))
+        .with_example(Example::new("4\n5", "18").phase(2))
+        .with_solved(1);
+        assert_eq!((task.name(), task.day()), ("Mock Sum".to_owned(), Some(1)));
+        assert_eq!(task.solve(2).unwrap(), ["12"]);
+        assert!(task.phase_is_solved(1).unwrap() && !task.phase_is_solved(2).unwrap());
+        task.mark_phase_as_solved(2).unwrap();
+        assert!(task.phase_is_solved(2).unwrap());
+
+        let task: SharedAocTask = Arc::new(task);
+        assert_examples(&task);
+        assert!(!task.input_path().exists());
+    }
+
+    #[test]
+    #[should_panic(expected = "example example_02 failed")]
+    fn failing_examples_panic() {
+        assert_examples(&(Arc::new(BrokenTask) as SharedAocTask));
+    }
+}
@@ -1,6 +1,10 @@
 use itertools::Itertools;
 use std::error::Error;
 use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use thiserror::Error;
 
 use crate::AocSolution;
 
@@ -67,3 +71,132 @@ where
         }
     }
 }
+
+// Groups of consecutive lines separated by blank lines, as yielded by `SplitSections::sections`
+pub struct Sections<I> {
+    lines: I,
+}
+
+impl<I, S> Iterator for Sections<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str> + Into<String>,
+{
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut section = vec![];
+        for line in self.lines.by_ref() {
+            if !line.as_ref().trim().is_empty() {
+                section.push(line.into());
+            } else if !section.is_empty() {
+                break;
+            }
+        }
+        (!section.is_empty()).then_some(section)
+    }
+}
+
+// Splits lines into sections at blank lines, e.g. `input.sections()` for inputs made of a few
+// blocks. Repeated blank lines never produce empty sections.
+pub trait SplitSections: Iterator + Sized {
+    fn sections(self) -> Sections<Self> {
+        Sections { lines: self }
+    }
+}
+
+impl<I, S> SplitSections for I
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str> + Into<String>,
+{
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Failed to parse line {line} `{text}`: {message}")]
+pub struct ParseError {
+    // Numbered from 1, like in an editor
+    pub line: usize,
+    pub text: String,
+    pub message: String,
+}
+
+// Lines parsed with `FromStr`, as yielded by `ParseLines::parse_lines`
+pub struct ParsedLines<I, T> {
+    lines: I,
+    line: usize,
+    parsed: PhantomData<fn() -> T>,
+}
+
+impl<I, S, T> Iterator for ParsedLines<I, T>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+    T: FromStr,
+    T::Err: Display,
+{
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line += 1;
+        let text = line.as_ref().trim();
+        Some(text.parse().map_err(|err: T::Err| ParseError {
+            line: self.line,
+            text: text.to_owned(),
+            message: err.to_string(),
+        }))
+    }
+}
+
+// Parses every line into a value, e.g. `input.parse_lines::<i64>().try_collect()?` for inputs
+// with a number per line. Errors point at the line that failed to parse.
+pub trait ParseLines: Iterator + Sized {
+    fn parse_lines<T>(self) -> ParsedLines<Self, T> {
+        ParsedLines {
+            lines: self,
+            line: 0,
+            parsed: PhantomData,
+        }
+    }
+}
+
+impl<I, S> ParseLines for I
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_are_split_at_blank_lines() {
+        let sections = "\n1000\n2000\n\n3000\n\n\n \n4000\n5000\n"
+            .lines()
+            .sections();
+        assert_eq!(
+            sections.collect_vec(),
+            [vec!["1000", "2000"], vec!["3000"], vec!["4000", "5000"]]
+        );
+    }
+
+    #[test]
+    fn lines_are_parsed_with_their_line_numbers() {
+        let numbers: Vec<i64> = "12\n-5 \n7".lines().parse_lines().try_collect().unwrap();
+        assert_eq!(numbers, [12, -5, 7]);
+
+        let err = "1\n2\nthree"
+            .lines()
+            .parse_lines::<u8>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(
+            err.to_string(),
+            "Failed to parse line 3 `three`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::{
+    io::{self, Stdout},
+    time::Duration,
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use itertools::Itertools;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use crate::{
+    error::AocError,
+    filter::Filter,
+    report::{PhaseReport, PhaseStatus, TaskReport},
+    reporter::Reporter,
+    run_tasks,
+    runner::filter_tasks,
+    style::{self, Symbol},
+    RunOptions, SharedAocTask,
+};
+
+type TuiTerminal = Terminal<CrosstermBackend<Stdout>>;
+
+const HELP: &str =
+    "↑/↓ task  ←/→ phase  r run  a run all  s mark solved  u unsolve  digits jump to day  q quit";
+
+// The runner reports to the dashboard instead of the terminal
+struct SilentReporter;
+
+impl Reporter for SilentReporter {}
+
+struct App<'t> {
+    tasks: Vec<&'t SharedAocTask>,
+    reports: Vec<Option<TaskReport>>,
+    list_state: ListState,
+    phase: usize,
+    day_input: String,
+    message: String,
+}
+
+fn terminal_error(source: io::Error) -> AocError {
+    AocError::TerminalError { source }
+}
+
+fn color(color: crossterm::style::Color) -> ratatui::style::Color {
+    ratatui::style::Color::from(color)
+}
+
+fn status_symbol(status: Option<PhaseStatus>, solved: bool) -> Span<'static> {
+    let theme = style::theme();
+    match status {
+        Some(PhaseStatus::Solved | PhaseStatus::PreviouslySolved) => Span::styled(
+            Symbol::Checkmark.text(),
+            Style::default().fg(color(theme.success)),
+        ),
+        Some(PhaseStatus::Unconfirmed) => Span::styled(
+            Symbol::Dot.text(),
+            Style::default().fg(color(theme.highlight)),
+        ),
+        Some(_) => Span::styled(
+            Symbol::Cross.text(),
+            Style::default().fg(color(theme.failure)),
+        ),
+        None if solved => Span::styled(
+            Symbol::Checkmark.text(),
+            Style::default().fg(color(theme.muted)),
+        ),
+        None => Span::styled(Symbol::Dot.text(), Style::default().fg(color(theme.muted))),
+    }
+}
+
+impl<'t> App<'t> {
+    fn new(tasks: Vec<&'t SharedAocTask>) -> Self {
+        let mut list_state = ListState::default();
+        list_state.select((!tasks.is_empty()).then_some(0));
+        Self {
+            reports: vec![None; tasks.len()],
+            tasks,
+            list_state,
+            phase: 1,
+            day_input: String::new(),
+            message: HELP.to_owned(),
+        }
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.list_state.selected()
+    }
+
+    fn select(&mut self, index: usize) {
+        if index < self.tasks.len() {
+            self.list_state.select(Some(index));
+            self.phase = self.phase.min(self.tasks[index].phases());
+        }
+    }
+
+    fn phase_report(&self, index: usize, phase: usize) -> Option<&PhaseReport> {
+        self.reports[index]
+            .as_ref()?
+            .phases
+            .iter()
+            .find(|report| report.phase == phase)
+    }
+
+    // Prompts and progress bars would draw over the dashboard, the output is always captured
+    fn run_task(&mut self, index: usize) {
+        let options = RunOptions {
+            interactive: false,
+            progress: false,
+            capture_output: true,
+            fail_fast: false,
+            ..RunOptions::default()
+        };
+        let task = self.tasks[index];
+        match run_tasks(std::slice::from_ref(task), &options, &mut SilentReporter) {
+            Ok(mut report) => {
+                self.reports[index] = report.tasks.pop();
+                self.message = format!("Ran {}", task.name());
+            }
+            Err(err) => self.message = format!("{} failed: {err}", task.name()),
+        }
+    }
+
+    fn set_solved(&mut self, solved: bool) {
+        let Some(index) = self.selected() else {
+            return;
+        };
+        let task = self.tasks[index];
+        let result = if solved {
+            task.mark_phase_as_solved(self.phase)
+        } else {
+            task.mark_phase_as_unsolved(self.phase)
+        };
+        self.message = match result {
+            Ok(()) if solved => format!("Marked phase {} of {} as solved", self.phase, task.name()),
+            Ok(()) => format!("Marked phase {} of {} as unsolved", self.phase, task.name()),
+            Err(err) => err.to_string(),
+        };
+    }
+
+    // Days are typed as two digits, e.g. `0` `7` for day 7
+    fn jump_to_day(&mut self, digit: char) {
+        self.day_input.push(digit);
+        if self.day_input.len() < 2 {
+            self.message = format!("Jump to day {}_", self.day_input);
+            return;
+        }
+        let day = self.day_input.parse::<u8>().ok();
+        self.day_input.clear();
+        match self.tasks.iter().position(|task| task.day() == day) {
+            Some(index) => {
+                self.select(index);
+                self.message = HELP.to_owned();
+            }
+            None => self.message = "No task registered for that day".to_owned(),
+        }
+    }
+
+    // Returns false when the dashboard should be closed
+    fn handle_key(&mut self, terminal: &mut TuiTerminal, code: KeyCode) -> Result<bool, AocError> {
+        let selected = self.selected().unwrap_or(0);
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Up | KeyCode::Char('k') => self.select(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select(selected + 1),
+            KeyCode::Left | KeyCode::Char('h') => self.phase = self.phase.saturating_sub(1).max(1),
+            KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(task) = self.tasks.get(selected) {
+                    self.phase = (self.phase + 1).min(task.phases());
+                }
+            }
+            KeyCode::Char('r') | KeyCode::Enter if !self.tasks.is_empty() => {
+                self.message = format!("Running {}...", self.tasks[selected].name());
+                self.draw(terminal)?;
+                self.run_task(selected);
+            }
+            KeyCode::Char('a') => {
+                for index in 0..self.tasks.len() {
+                    self.select(index);
+                    self.message = format!("Running {}...", self.tasks[index].name());
+                    self.draw(terminal)?;
+                    self.run_task(index);
+                }
+                self.message = format!("Ran {} tasks", self.tasks.len());
+            }
+            KeyCode::Char('s') => self.set_solved(true),
+            KeyCode::Char('u') => self.set_solved(false),
+            KeyCode::Char(digit) if digit.is_ascii_digit() => self.jump_to_day(digit),
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    fn draw(&mut self, terminal: &mut TuiTerminal) -> Result<(), AocError> {
+        terminal
+            .draw(|frame| self.render(frame))
+            .map(|_| ())
+            .map_err(terminal_error)
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [main, footer] = split(
+            frame.size(),
+            Direction::Vertical,
+            [Constraint::Min(0), Constraint::Length(1)],
+        );
+        let [list, details] = split(
+            main,
+            Direction::Horizontal,
+            [Constraint::Length(32), Constraint::Min(0)],
+        );
+        let [output, diff, timings] = split(
+            details,
+            Direction::Vertical,
+            [
+                Constraint::Percentage(45),
+                Constraint::Percentage(35),
+                Constraint::Percentage(20),
+            ],
+        );
+
+        self.render_task_list(frame, list);
+        if let Some(index) = self.selected() {
+            let title = format!(" {} phase {} ", self.tasks[index].name(), self.phase);
+            frame.render_widget(
+                Paragraph::new(self.output_lines(index))
+                    .block(Block::default().borders(Borders::ALL).title(title))
+                    .wrap(Wrap { trim: false }),
+                output,
+            );
+            frame.render_widget(
+                Paragraph::new(self.diff_lines(index))
+                    .block(Block::default().borders(Borders::ALL).title(" Diff ")),
+                diff,
+            );
+            frame.render_widget(
+                Paragraph::new(self.timing_lines(index))
+                    .block(Block::default().borders(Borders::ALL).title(" Timings ")),
+                timings,
+            );
+        }
+        frame.render_widget(
+            Paragraph::new(self.message.as_str())
+                .style(Style::default().fg(color(style::theme().muted))),
+            footer,
+        );
+    }
+
+    fn render_task_list(&mut self, frame: &mut Frame, area: Rect) {
+        let items = self
+            .tasks
+            .iter()
+            .enumerate()
+            .map(|(index, task)| {
+                let mut spans = (1..=task.phases())
+                    .map(|phase| {
+                        let status = self.phase_report(index, phase).map(|report| report.status);
+                        status_symbol(status, task.phase_is_solved(phase).unwrap_or(false))
+                    })
+                    .collect_vec();
+                spans.push(Span::raw(format!(" {}", task.name())));
+                ListItem::new(Line::from(spans))
+            })
+            .collect_vec();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Tasks "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn output_lines(&self, index: usize) -> Vec<Line<'static>> {
+        let Some(report) = self.phase_report(index, self.phase) else {
+            return vec![Line::raw("Not run yet, press r to run the task")];
+        };
+        let theme = style::theme();
+        let mut lines = report
+            .output
+            .iter()
+            .flatten()
+            .map(|line| Line::styled(line.clone(), Style::default().fg(color(theme.info))))
+            .collect_vec();
+        for example in &report.examples {
+            lines.push(Line::styled(
+                format!("Example {}:", example.name),
+                Style::default().fg(color(theme.accent)),
+            ));
+            lines.extend(
+                example
+                    .output
+                    .iter()
+                    .map(|line| Line::raw(format!("  {line}"))),
+            );
+        }
+        let captured = report
+            .examples
+            .iter()
+            .map(|example| example.captured_output.as_str())
+            .chain([report.captured_output.as_str()]);
+        for line in captured.flat_map(str::lines) {
+            lines.push(Line::styled(
+                line.to_owned(),
+                Style::default().fg(color(theme.muted)),
+            ));
+        }
+        lines
+    }
+
+    fn diff_lines(&self, index: usize) -> Vec<Line<'static>> {
+        let Some(report) = self.phase_report(index, self.phase) else {
+            return vec![];
+        };
+        let rendered = report.diff().unwrap_or_default();
+        let theme = style::theme();
+        rendered
+            .lines()
+            .map(|line| {
+                let style = match line.chars().next() {
+                    Some('-') => Style::default().fg(color(theme.failure)),
+                    Some('+') => Style::default().fg(color(theme.success)),
+                    Some('@') => Style::default().fg(color(theme.accent)),
+                    _ => Style::default(),
+                };
+                Line::styled(line.to_owned(), style)
+            })
+            .collect()
+    }
+
+    fn timing_lines(&self, index: usize) -> Vec<Line<'static>> {
+        let Some(report) = &self.reports[index] else {
+            return vec![];
+        };
+        let format_duration = |duration: Option<Duration>| {
+            duration.map_or_else(|| "-".to_owned(), |duration| format!("{duration:.2?}"))
+        };
+        report
+            .phases
+            .iter()
+            .flat_map(|phase| {
+                let examples = phase.examples.iter().map(move |example| {
+                    Line::raw(format!(
+                        "  phase {} {:<20} {}",
+                        phase.phase,
+                        example.name,
+                        format_duration(Some(example.duration))
+                    ))
+                });
+                std::iter::once(Line::raw(format!(
+                    "phase {} {:<22} {}",
+                    phase.phase,
+                    "input",
+                    format_duration(phase.duration)
+                )))
+                .chain(examples)
+            })
+            .collect()
+    }
+}
+
+fn split<const N: usize>(
+    area: Rect,
+    direction: Direction,
+    constraints: [Constraint; N],
+) -> [Rect; N] {
+    let areas = Layout::default()
+        .direction(direction)
+        .constraints(constraints)
+        .split(area);
+    std::array::from_fn(|index| areas[index])
+}
+
+fn event_loop(app: &mut App, terminal: &mut TuiTerminal) -> Result<(), AocError> {
+    loop {
+        app.draw(terminal)?;
+        if let Event::Key(key) = event::read().map_err(terminal_error)? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(terminal, key.code)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+// Full-screen dashboard of the tasks, which are only run on request
+pub fn run_dashboard(tasks: &[SharedAocTask], filter: &Filter) -> Result<(), AocError> {
+    let mut app = App::new(filter_tasks(tasks, filter));
+    enable_raw_mode().map_err(terminal_error)?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen).map_err(terminal_error)?;
+    let result = Terminal::new(CrosstermBackend::new(stdout))
+        .map_err(terminal_error)
+        .and_then(|mut terminal| event_loop(&mut app, &mut terminal));
+
+    // The terminal is restored even when the dashboard failed
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    let _ = disable_raw_mode();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::testing::MockTask;
+
+    #[test]
+    fn dashboard_lists_the_tasks() {
+        let task = MockTask::new(|_, _| Ok(vec![])).with_name("Sum Task");
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(task)];
+        let mut app = App::new(filter_tasks(&tasks, &Filter::all()));
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let screen = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(screen.contains("Sum Task"));
+        assert!(screen.contains("Not run yet"));
+    }
+}
@@ -0,0 +1,424 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    compare::Comparator, context::AocContext, error::AocError, example::Example, input,
+    source::InputSource, AocSolution, AocStringIter, AocTask,
+};
+#[cfg(feature = "serde")]
+use crate::{input_files, SharedAocTask};
+
+pub trait TypedAocTask: Send + Sync {
+    // Cached parsed inputs are shared between the threads solving the phases
+    type Parsed: Send + Sync;
+
+    fn directory(&self) -> PathBuf;
+
+    fn year(&self) -> Option<u16> {
+        input::puzzle_date(&self.directory()).map(|(year, _)| year)
+    }
+
+    fn day(&self) -> Option<u8> {
+        input::puzzle_day(&self.directory())
+    }
+
+    fn data_directory(&self) -> PathBuf {
+        input::dated_data_directory(&self.directory(), self.year().zip(self.day()))
+    }
+
+    fn phases(&self) -> usize {
+        2
+    }
+
+    fn comparator(&self, _phase: usize) -> Comparator {
+        Comparator::default()
+    }
+
+    fn inline_examples(&self) -> Vec<Example> {
+        vec![]
+    }
+
+    fn variants(&self, _phase: usize) -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn generate_input(&self, _scale: usize) -> String {
+        String::new()
+    }
+
+    fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        None
+    }
+
+    fn preprocess<'i>(&self, raw: Cow<'i, str>) -> Cow<'i, str> {
+        raw
+    }
+
+    // Part of the key of the parsed inputs cached on disk, bump it when the parser changes so
+    // that the inputs are parsed again
+    fn parse_version(&self) -> u32 {
+        0
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, Box<dyn Error + Send + Sync>>;
+
+    fn solve_parsed(
+        &self,
+        input: &Self::Parsed,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>>;
+}
+
+type ParsedCache<P> = HashMap<PathBuf, (Option<SystemTime>, Arc<P>)>;
+
+#[cfg(feature = "serde")]
+const DISK_CACHE_FILE: &str = ".parsed_input";
+
+// Stores the parsed input next to the input, the first line holds its cache key
+#[cfg(feature = "serde")]
+struct DiskCache<P> {
+    save: fn(&P) -> Option<String>,
+    load: fn(&str) -> Option<P>,
+}
+
+pub struct TypedTask<T: TypedAocTask> {
+    task: T,
+    cache: Mutex<ParsedCache<T::Parsed>>,
+    #[cfg(feature = "serde")]
+    disk_cache: Option<DiskCache<T::Parsed>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TypedTask<T>
+where
+    T: TypedAocTask,
+    T::Parsed: serde::Serialize + serde::de::DeserializeOwned,
+{
+    // Also keeps the parsed input on disk, so that later runs skip parsing the same input again
+    pub fn with_disk_cache(task: T) -> Self {
+        Self {
+            disk_cache: Some(DiskCache {
+                save: |parsed| serde_json::to_string(parsed).ok(),
+                load: |cached| serde_json::from_str(cached).ok(),
+            }),
+            ..Self::new(task)
+        }
+    }
+}
+
+// The parsed type and the parse version are hashed with the input, so a changed parser never
+// loads what an older one stored
+#[cfg(feature = "serde")]
+fn cache_key<P>(input: &[u8], parse_version: u32) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(std::any::type_name::<P>().as_bytes());
+    hasher.update(parse_version.to_le_bytes());
+    hasher.update(input);
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+// Returns the removed cache files
+#[cfg(feature = "serde")]
+pub fn clear_disk_caches(tasks: &[SharedAocTask]) -> Result<Vec<PathBuf>, AocError> {
+    let mut removed = vec![];
+    for directory in tasks.iter().map(|task| task.data_directory()).unique() {
+        let cache_path = directory.join(DISK_CACHE_FILE);
+        if !cache_path.is_file() {
+            continue;
+        }
+        fs::remove_file(&cache_path).map_err(|err| AocError::IOWriteError {
+            path: cache_path.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        removed.push(cache_path);
+    }
+    Ok(removed)
+}
+
+impl<T: TypedAocTask> TypedTask<T> {
+    pub fn new(task: T) -> Self {
+        Self {
+            task,
+            cache: Mutex::new(HashMap::new()),
+            #[cfg(feature = "serde")]
+            disk_cache: None,
+        }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.task
+    }
+
+    #[cfg(feature = "serde")]
+    fn load_from_disk(&self, key: &str) -> Option<T::Parsed> {
+        let disk_cache = self.disk_cache.as_ref()?;
+        let cached = fs::read_to_string(self.task.data_directory().join(DISK_CACHE_FILE)).ok()?;
+        let (cached_key, parsed) = cached.split_once('\n')?;
+        (cached_key == key).then(|| (disk_cache.load)(parsed))?
+    }
+
+    // The cache is only an optimization, failing to write it doesn't fail the task
+    #[cfg(feature = "serde")]
+    fn save_to_disk(&self, key: &str, parsed: &T::Parsed) {
+        let Some(serialized) = self
+            .disk_cache
+            .as_ref()
+            .and_then(|cache| (cache.save)(parsed))
+        else {
+            return;
+        };
+        let cache_path = self.task.data_directory().join(DISK_CACHE_FILE);
+        if fs::write(&cache_path, format!("{key}\n{serialized}")).is_ok() {
+            let _ = input_files::ignore_input(&cache_path);
+        }
+    }
+
+    // Examples are quick to parse, only the parsed input is kept on disk
+    fn parsed_input(
+        &self,
+        input_path: &Path,
+        persistent: bool,
+    ) -> Result<Arc<T::Parsed>, AocError> {
+        let execution_error = |err| AocError::SolutionExecutionError {
+            input_path: input_path.to_string_lossy().to_string(),
+            source: err,
+        };
+        let modified = fs::metadata(input_path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+
+        let mut cache = self.cache.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some((cached_modified, parsed)) = cache.get(input_path) {
+            if *cached_modified == modified {
+                return Ok(parsed.clone());
+            }
+        }
+
+        let input = input::map_input(input_path)?;
+        #[cfg(feature = "serde")]
+        let key = persistent.then(|| cache_key::<T::Parsed>(&input, self.task.parse_version()));
+        #[cfg(feature = "serde")]
+        if let Some(parsed) = key.as_deref().and_then(|key| self.load_from_disk(key)) {
+            let parsed = Arc::new(parsed);
+            cache.insert(input_path.to_path_buf(), (modified, parsed.clone()));
+            return Ok(parsed);
+        }
+        #[cfg(not(feature = "serde"))]
+        let _ = persistent;
+
+        let input = std::str::from_utf8(&input).map_err(|err| execution_error(err.into()))?;
+        let input = self.task.preprocess(Cow::Borrowed(input));
+        let parsed = {
+            trace_span!("parse", path = %input_path.display());
+            Arc::new(self.task.parse(&input).map_err(execution_error)?)
+        };
+        #[cfg(feature = "serde")]
+        if let Some(key) = key {
+            self.save_to_disk(&key, &parsed);
+        }
+        cache.insert(input_path.to_path_buf(), (modified, parsed.clone()));
+        Ok(parsed)
+    }
+}
+
+impl<T: TypedAocTask> From<T> for TypedTask<T> {
+    fn from(task: T) -> Self {
+        Self::new(task)
+    }
+}
+
+impl<T: TypedAocTask> AocTask for TypedTask<T> {
+    fn directory(&self) -> PathBuf {
+        self.task.directory()
+    }
+
+    fn year(&self) -> Option<u16> {
+        self.task.year()
+    }
+
+    fn day(&self) -> Option<u8> {
+        self.task.day()
+    }
+
+    fn data_directory(&self) -> PathBuf {
+        self.task.data_directory()
+    }
+
+    fn phases(&self) -> usize {
+        self.task.phases()
+    }
+
+    fn comparator(&self, phase: usize) -> Comparator {
+        self.task.comparator(phase)
+    }
+
+    fn inline_examples(&self) -> Vec<Example> {
+        self.task.inline_examples()
+    }
+
+    fn variants(&self, phase: usize) -> Vec<&'static str> {
+        self.task.variants(phase)
+    }
+
+    fn generate_input(&self, scale: usize) -> String {
+        self.task.generate_input(scale)
+    }
+
+    fn input_source(&self) -> Option<Arc<dyn InputSource>> {
+        self.task.input_source()
+    }
+
+    fn preprocess<'i>(&self, raw: Cow<'i, str>) -> Cow<'i, str> {
+        self.task.preprocess(raw)
+    }
+
+    fn solution(
+        &self,
+        mut input: AocStringIter,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        self.solution_raw(&input.join("\n"), context)
+    }
+
+    fn solution_raw(
+        &self,
+        input: &str,
+        context: &AocContext,
+    ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+        let parsed = self.task.parse(input)?;
+        self.task.solve_parsed(&parsed, context)
+    }
+
+    fn solve_from_input_path(
+        &self,
+        input_path: &Path,
+        context: &AocContext,
+    ) -> Result<AocSolution, AocError> {
+        let parsed = self.parsed_input(input_path, !context.is_example())?;
+        self.task
+            .solve_parsed(&parsed, context)
+            .map_err(|err| AocError::SolutionExecutionError {
+                input_path: input_path.to_string_lossy().to_string(),
+                source: err,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use itertools::Itertools;
+
+    use super::*;
+    #[cfg(feature = "serde")]
+    use crate::testing::TempDir;
+
+    #[derive(Default)]
+    struct TypedSumTask {
+        parse_count: AtomicUsize,
+        data_directory: Option<PathBuf>,
+        parse_version: u32,
+    }
+
+    impl TypedAocTask for TypedSumTask {
+        type Parsed = Vec<Vec<i32>>;
+
+        fn directory(&self) -> PathBuf {
+            PathBuf::from("tests/sum_task")
+        }
+
+        fn data_directory(&self) -> PathBuf {
+            self.data_directory
+                .clone()
+                .unwrap_or_else(|| self.directory())
+        }
+
+        fn parse_version(&self) -> u32 {
+            self.parse_version
+        }
+
+        fn parse(&self, input: &str) -> Result<Self::Parsed, Box<dyn Error + Send + Sync>> {
+            self.parse_count.fetch_add(1, Ordering::SeqCst);
+            Ok(input
+                .lines()
+                .map(|line| line.split_whitespace().map(str::parse).try_collect())
+                .try_collect()?)
+        }
+
+        fn solve_parsed(
+            &self,
+            input: &Self::Parsed,
+            context: &AocContext,
+        ) -> Result<AocSolution, Box<dyn Error + Send + Sync>> {
+            let factor = context.phase() as i32;
+            Ok(input
+                .iter()
+                .map(|numbers| (numbers.iter().sum::<i32>() * factor).to_string())
+                .collect())
+        }
+    }
+
+    #[test]
+    fn typed_task_parses_once() {
+        let task = TypedTask::new(TypedSumTask::default());
+        assert_eq!(task.name(), "Sum Task");
+        assert_eq!(task.solve(1).unwrap(), vec!["7", "12", "289197"]);
+        assert_eq!(task.solve(2).unwrap(), vec!["14", "24", "578394"]);
+        assert_eq!(task.inner().parse_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parsed_inputs_are_cached_on_disk() {
+        let directory = TempDir::new("parsed");
+        let data_directory = directory.path().to_owned();
+        fs::copy("tests/sum_task/in", data_directory.join("in")).unwrap();
+        let new_task = |parse_version| {
+            TypedTask::with_disk_cache(TypedSumTask {
+                data_directory: Some(data_directory.clone()),
+                parse_version,
+                ..TypedSumTask::default()
+            })
+        };
+
+        let task = new_task(0);
+        assert_eq!(task.solve(1).unwrap(), vec!["7", "12", "289197"]);
+        assert_eq!(task.inner().parse_count.load(Ordering::SeqCst), 1);
+        let task = new_task(0);
+        assert_eq!(task.solve(2).unwrap(), vec!["14", "24", "578394"]);
+        assert_eq!(task.inner().parse_count.load(Ordering::SeqCst), 0);
+        let task = new_task(1);
+        assert_eq!(task.solve(1).unwrap(), vec!["7", "12", "289197"]);
+        assert_eq!(task.inner().parse_count.load(Ordering::SeqCst), 1);
+
+        fs::write(data_directory.join("in"), "1 2").unwrap();
+        let task = new_task(1);
+        assert_eq!(task.solve(1).unwrap(), vec!["3"]);
+        assert_eq!(task.inner().parse_count.load(Ordering::SeqCst), 1);
+        assert!(fs::read_to_string(data_directory.join(".gitignore"))
+            .unwrap()
+            .contains("/.parsed_input"));
+
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(new_task(1))];
+        let cache_path = data_directory.join(DISK_CACHE_FILE);
+        assert_eq!(clear_disk_caches(&tasks).unwrap(), [cache_path]);
+        assert!(!data_directory.join(DISK_CACHE_FILE).exists());
+        assert!(clear_disk_caches(&tasks).unwrap().is_empty());
+    }
+}
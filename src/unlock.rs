@@ -0,0 +1,345 @@
+use std::{
+    env,
+    io::{stdout, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, Local, TimeZone, Utc};
+use crossterm::{
+    execute,
+    terminal::{Clear, ClearType},
+};
+
+use crate::{
+    client::AocClient, error::AocError, input, scaffold, style::Stylize, SharedAocTask, CHECKMARK,
+    CROSS, DOT,
+};
+
+const DOWNLOAD_ATTEMPTS: usize = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+pub const FIRST_EVENT_YEAR: u16 = 2015;
+
+// Events since 2025 only have 12 puzzles
+pub fn event_days(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+// Puzzles unlock at midnight EST, which is 05:00 UTC. Days without a puzzle never unlock.
+pub fn unlock_time(year: u16, day: u8) -> Option<DateTime<Utc>> {
+    if !(1..=event_days(year)).contains(&day) {
+        return None;
+    }
+    Utc.with_ymd_and_hms(year as i32, 12, day as u32, 5, 0, 0)
+        .single()
+}
+
+pub fn next_unlock(now: DateTime<Utc>) -> (u16, u8) {
+    let year = now.year() as u16;
+    (1..=event_days(year))
+        .find(|&day| unlock_time(year, day).is_some_and(|unlock| unlock > now))
+        .map(|day| (year, day))
+        .unwrap_or((year + 1, 1))
+}
+
+// The most recently unlocked puzzle, the last day of the previous event before December
+pub fn latest_unlock(now: DateTime<Utc>) -> (u16, u8) {
+    match next_unlock(now) {
+        (year, 1) => (year - 1, event_days(year - 1)),
+        (year, day) => (year, day - 1),
+    }
+}
+
+fn format_countdown(remaining: chrono::Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let days = seconds / 86400;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+// The input can briefly be unavailable right after the unlock, so failed requests are retried
+// after a growing delay
+fn with_retries<T>(mut request: impl FnMut() -> Result<T, AocError>) -> Result<T, AocError> {
+    let mut delay = RETRY_DELAY;
+    for _ in 1..DOWNLOAD_ATTEMPTS {
+        match request() {
+            Err(AocError::RequestError { .. }) => {
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    request()
+}
+
+fn download_input(client: &AocClient, year: u16, day: u8) -> Result<String, AocError> {
+    with_retries(|| client.input(year, day))
+}
+
+fn count_down(year: u16, day: u8) {
+    let Some(unlock) = unlock_time(year, day) else {
+        return;
+    };
+    println!(
+        "{} Day {} of {} unlocks at {}",
+        DOT.info(),
+        day.to_string().bold(),
+        year.to_string().bold(),
+        unlock
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S %Z")
+            .to_string()
+            .bold()
+    );
+
+    loop {
+        let remaining = unlock - Utc::now();
+        if remaining <= chrono::Duration::zero() {
+            break;
+        }
+        print!("\r{} {}", DOT.info(), format_countdown(remaining).info());
+        let _ = execute!(stdout(), Clear(ClearType::UntilNewLine));
+        let _ = stdout().flush();
+        let tick = remaining
+            .to_std()
+            .unwrap_or_default()
+            .min(Duration::from_secs(1));
+        thread::sleep(tick);
+    }
+    println!();
+}
+
+// Counts down to the next unlock, then scaffolds the task of the day in the directory of its
+// year and downloads its input
+pub fn wait_for_unlock(tasks_directory: &Path) -> Result<PathBuf, AocError> {
+    let client = AocClient::load()?;
+    let (year, day) = next_unlock(Utc::now());
+    count_down(year, day);
+
+    let year_directory = scaffold::year_directory(tasks_directory, year);
+    let task_directory = scaffold::scaffold_task(day, &year_directory)?;
+    let input = download_input(&client, year, day)?;
+    input::write_input(&input::data_directory(&task_directory).join("in"), &input)?;
+    println!(
+        "{} Day {} is ready in {}",
+        CHECKMARK.success(),
+        day.to_string().bold(),
+        task_directory.to_string_lossy().bold()
+    );
+    Ok(task_directory)
+}
+
+// Failures are only reported, so that a single failed download does not stop the prefetching
+fn prefetch_input(client: &AocClient, tasks: &[SharedAocTask], year: u16, day: u8) {
+    match with_retries(|| save_input(client, tasks, year, day)) {
+        Ok(Some(input_path)) => println!(
+            "{} Downloaded the input of day {} to {}",
+            CHECKMARK.success(),
+            day.to_string().bold(),
+            input_path.to_string_lossy().bold()
+        ),
+        Ok(None) => println!(
+            "{} The input of day {} is already downloaded",
+            DOT.muted(),
+            day.to_string().bold()
+        ),
+        Err(err) => eprintln!(
+            "{} Failed to download the input of day {}: {err}",
+            CROSS.failure(),
+            day.to_string().bold()
+        ),
+    }
+}
+
+// Downloads the input of every remaining puzzle of the event as soon as it unlocks, to the same
+// places as the download command
+pub fn prefetch_inputs(tasks: &[SharedAocTask]) -> Result<(), AocError> {
+    let client = AocClient::load()?;
+    loop {
+        let (year, day) = next_unlock(Utc::now());
+        count_down(year, day);
+        prefetch_input(&client, tasks, year, day);
+        if day == event_days(year) {
+            return Ok(());
+        }
+    }
+}
+
+fn check_puzzle_date(year: u16, day: u8) -> Result<(), AocError> {
+    if year < FIRST_EVENT_YEAR || !(1..=event_days(year)).contains(&day) {
+        return Err(AocError::MissingPuzzle { year, day });
+    }
+    Ok(())
+}
+
+// Where the input of a day is kept, next to its registered task or in the data directory
+fn input_destination(tasks: &[SharedAocTask], year: u16, day: u8) -> Option<PathBuf> {
+    match tasks.iter().find(|task| task.date() == Some((year, day))) {
+        Some(task) => Some(task.input_path()),
+        None => env::var_os(input::DATA_DIR_ENV_VAR)
+            .map(|_| input::dated_data_directory(Path::new(""), Some((year, day))).join("in")),
+    }
+}
+
+// Writes the input of the day unless it is on disk already. Inputs of days without a task are
+// only cached, they are used once the task is created. Returns the written file.
+fn save_input(
+    client: &AocClient,
+    tasks: &[SharedAocTask],
+    year: u16,
+    day: u8,
+) -> Result<Option<PathBuf>, AocError> {
+    match input_destination(tasks, year, day) {
+        Some(input_path) if !input_path.is_file() => {
+            input::write_input(&input_path, &client.input(year, day)?)?;
+            Ok(Some(input_path))
+        }
+        Some(_) => Ok(None),
+        None => {
+            let cache_path = client.input_cache_path(year, day);
+            if cache_path
+                .as_ref()
+                .is_some_and(|cache_path| cache_path.is_file())
+            {
+                return Ok(None);
+            }
+            client.input(year, day)?;
+            Ok(cache_path)
+        }
+    }
+}
+
+// Downloads the inputs of the unlocked days that are not on disk yet. Cached inputs are not
+// requested again and the client keeps the requests apart. Returns the written inputs.
+pub fn download_inputs(
+    client: &AocClient,
+    tasks: &[SharedAocTask],
+    year: u16,
+    days: impl IntoIterator<Item = u8>,
+) -> Result<Vec<PathBuf>, AocError> {
+    let now = Utc::now();
+    let mut written = vec![];
+    for day in days {
+        check_puzzle_date(year, day)?;
+        if unlock_time(year, day).is_some_and(|unlock| unlock > now) {
+            println!(
+                "{} Day {} of {} is not unlocked yet",
+                DOT.muted(),
+                day.to_string().bold(),
+                year.to_string().bold()
+            );
+            break;
+        }
+        written.extend(save_input(client, tasks, year, day)?);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc};
+
+    use super::*;
+    use crate::{
+        session::{Session, SessionSource},
+        testing::{MockTask, TempDir},
+    };
+
+    #[test]
+    fn next_unlock_dates() {
+        let at =
+            |year, month, day, hour| Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap();
+        assert_eq!(next_unlock(at(2024, 10, 16, 0)), (2024, 1));
+        assert_eq!(next_unlock(at(2024, 12, 3, 4)), (2024, 3));
+        assert_eq!(next_unlock(at(2024, 12, 3, 5)), (2024, 4));
+        assert_eq!(next_unlock(at(2024, 12, 25, 6)), (2025, 1));
+        assert_eq!(next_unlock(at(2025, 12, 12, 6)), (2026, 1));
+        assert_eq!(latest_unlock(at(2024, 10, 16, 0)), (2023, 25));
+        assert_eq!(latest_unlock(at(2024, 12, 3, 5)), (2024, 3));
+        assert_eq!(unlock_time(2024, 3), Some(at(2024, 12, 3, 5)));
+        assert_eq!(unlock_time(2025, 13), None);
+        assert_eq!(unlock_time(2024, 0), None);
+        assert_eq!(latest_unlock(at(2026, 1, 2, 0)), (2025, 12));
+    }
+
+    #[test]
+    fn inputs_are_downloaded_next_to_their_tasks() {
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(
+            MockTask::new(|_, _| Ok(vec![]))
+                .with_name("Day 5")
+                .with_date(2023, 5),
+        )];
+        assert_eq!(
+            input_destination(&tasks, 2023, 5),
+            Some(PathBuf::from("mock/Day 5/in"))
+        );
+        if env::var_os(input::DATA_DIR_ENV_VAR).is_none() {
+            assert_eq!(input_destination(&tasks, 2023, 6), None);
+        }
+    }
+
+    #[test]
+    fn downloads_skip_inputs_on_disk_and_in_the_cache() {
+        let directory = TempDir::new("download");
+        let tasks: Vec<SharedAocTask> = [1, 2]
+            .map(|day| {
+                Arc::new(
+                    MockTask::new(|_, _| Ok(vec![]))
+                        .with_date(2015, day)
+                        .with_directory(directory.join(format!("day_{day:02}"))),
+                ) as SharedAocTask
+            })
+            .into();
+        // Any request would fail with the invalid session
+        let session = Session::new("invalid", SessionSource::Environment).unwrap();
+        let client = AocClient::new(session).cache_directory(Some(directory.join("cache")));
+        directory.write("day_01/in", "(()\n");
+        directory.write(client.input_cache_path(2015, 2).unwrap(), "^v\n");
+
+        let written = download_inputs(&client, &tasks, 2015, 1..=2).unwrap();
+        assert_eq!(written, [directory.join("day_02/in")]);
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), "^v\n");
+        assert!(matches!(
+            download_inputs(&client, &tasks, 2025, [13]),
+            Err(AocError::MissingPuzzle {
+                year: 2025,
+                day: 13
+            })
+        ));
+    }
+
+    #[test]
+    fn prefetching_keeps_inputs_on_disk() {
+        let directory = TempDir::new("prefetch");
+        let tasks: Vec<SharedAocTask> = vec![Arc::new(
+            MockTask::new(|_, _| Ok(vec![]))
+                .with_date(2016, 1)
+                .with_directory(directory.join("day_01")),
+        )];
+        let session = Session::new("invalid", SessionSource::Environment).unwrap();
+        let client = AocClient::new(session).cache_directory(Some(directory.join("cache")));
+        let input_path = directory.write("day_01/in", "R2, L3\n");
+        directory.write(client.input_cache_path(2016, 1).unwrap(), "R5, L5\n");
+
+        prefetch_input(&client, &tasks, 2016, 1);
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "R2, L3\n");
+        fs::remove_file(&input_path).unwrap();
+        prefetch_input(&client, &tasks, 2016, 1);
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "R5, L5\n");
+    }
+}
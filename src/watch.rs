@@ -0,0 +1,192 @@
+use std::{
+    io::stdout,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    terminal::{Clear, ClearType},
+};
+use itertools::Itertools;
+use notify::{Event, RecursiveMode, Watcher};
+
+use crate::{
+    error::AocError, reporter::Reporter, run_tasks, runner::filter_tasks, style::Stylize,
+    RunOptions, SharedAocTask, CROSS, DOT,
+};
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub run_options: RunOptions,
+    pub source_paths: Vec<PathBuf>,
+    pub debounce: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            run_options: RunOptions::default(),
+            source_paths: vec![PathBuf::from("src")],
+            debounce: Duration::from_millis(200),
+        }
+    }
+}
+
+enum Change {
+    TaskData,
+    Source,
+}
+
+// Hidden files such as the solved phase markers are written by the runner itself
+fn is_relevant(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| !name.to_string_lossy().starts_with('.'))
+        .unwrap_or(false)
+}
+
+fn classify(event: &Event, source_paths: &[PathBuf]) -> Option<Change> {
+    if event.kind.is_access() {
+        return None;
+    }
+
+    let mut change = None;
+    for path in event.paths.iter().filter(|path| is_relevant(path)) {
+        let is_source = path.extension().is_some_and(|extension| extension == "rs")
+            && source_paths.iter().any(|source| {
+                let source = source.canonicalize().unwrap_or_else(|_| source.clone());
+                path.starts_with(source)
+            });
+        if is_source {
+            return Some(Change::Source);
+        }
+        change = Some(Change::TaskData);
+    }
+    change
+}
+
+fn wait_for_change(
+    events: &Receiver<notify::Result<Event>>,
+    options: &WatchOptions,
+) -> Result<Change, AocError> {
+    let mut change = None;
+    loop {
+        let event = if change.is_some() {
+            match events.recv_timeout(options.debounce) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Err(AocError::WatchStopped),
+            }
+        } else {
+            events.recv().map_err(|_| AocError::WatchStopped)?
+        };
+
+        let event = event.map_err(|err| AocError::WatchError { source: err })?;
+        match classify(&event, &options.source_paths) {
+            Some(Change::Source) => change = Some(Change::Source),
+            Some(Change::TaskData) if change.is_none() => change = Some(Change::TaskData),
+            _ => {}
+        }
+    }
+    Ok(change.unwrap_or(Change::TaskData))
+}
+
+// Solutions are compiled into the running binary, so a source change requires a rebuild
+// followed by a restart of the process with the same arguments
+fn rebuild_and_restart() -> Result<(), AocError> {
+    let executable = std::env::current_exe().map_err(|err| AocError::IOReadError {
+        path: "<current executable>".to_owned(),
+        source: err,
+    })?;
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let mut build = Command::new(&cargo);
+    build.arg("build");
+    if executable
+        .components()
+        .any(|component| component.as_os_str() == "release")
+    {
+        build.arg("--release");
+    }
+
+    println!("{} {}", DOT.info(), "Rebuilding the solutions...".info());
+    let status = build.status().map_err(|err| AocError::CommandError {
+        command: cargo.clone(),
+        source: err,
+    })?;
+    if !status.success() {
+        println!(
+            "{} {}",
+            CROSS.failure(),
+            "The build failed, waiting for further changes".failure()
+        );
+        return Ok(());
+    }
+
+    let mut restart = Command::new(&executable);
+    restart.args(std::env::args_os().skip(1));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let err = restart.exec();
+        Err(AocError::CommandError {
+            command: executable.to_string_lossy().to_string(),
+            source: err,
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = restart.status().map_err(|err| AocError::CommandError {
+            command: executable.to_string_lossy().to_string(),
+            source: err,
+        })?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
+
+pub fn watch(
+    tasks: &[SharedAocTask],
+    options: &WatchOptions,
+    reporter: &mut dyn Reporter,
+) -> Result<(), AocError> {
+    let (sender, events) = channel();
+    let mut watcher =
+        notify::recommended_watcher(sender).map_err(|err| AocError::WatchError { source: err })?;
+
+    let task_directories = filter_tasks(tasks, &options.run_options.filter)
+        .into_iter()
+        .flat_map(|task| [task.directory(), task.data_directory()])
+        .unique()
+        .collect::<Vec<_>>();
+    for (path, mode) in task_directories
+        .iter()
+        .map(|path| (path, RecursiveMode::NonRecursive))
+        .chain(
+            options
+                .source_paths
+                .iter()
+                .map(|path| (path, RecursiveMode::Recursive)),
+        )
+        .filter(|(path, _)| path.exists())
+    {
+        watcher
+            .watch(path, mode)
+            .map_err(|err| AocError::WatchError { source: err })?;
+    }
+
+    loop {
+        let _ = execute!(stdout(), Clear(ClearType::All), MoveTo(0, 0));
+        if let Err(err) = run_tasks(tasks, &options.run_options, reporter) {
+            println!("{} {}", CROSS.failure(), err.to_string().failure());
+        }
+        println!("{} {}", DOT.info(), "Watching for changes...".info());
+
+        if let Change::Source = wait_for_change(&events, options)? {
+            rebuild_and_restart()?;
+        }
+    }
+}